@@ -0,0 +1,406 @@
+// Binary property lists ("bplist00"), as written by Apple's CFBinaryPList
+// See <https://opensource.apple.com/source/CF/CF-550/CFBinaryPList.c>
+
+use std::cell::Cell;
+
+#[derive(Debug)]
+pub struct BplistError(pub String);
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum BplistValue {
+    Null,
+    Bool(bool),
+    Integer(i64),
+    Real(f64),
+    Date(f64), // Seconds since 2001-01-01 00:00:00 UTC
+    Data(Vec<u8>),
+    String(String),
+    Uid(u64),
+    Array(Vec<BplistValue>),
+    Dictionary(Vec<(String, BplistValue)>), // In the order they're stored
+}
+
+impl BplistValue {
+    /// Looks up a key of a dictionary
+    pub fn get(&self, key: &str) -> Option<&BplistValue> {
+        match self {
+            BplistValue::Dictionary(entries) => entries
+                .iter()
+                .find(|(entry_key, _)| entry_key == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            BplistValue::Integer(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            BplistValue::Integer(v) => Some(*v as f64),
+            BplistValue::Real(v) => Some(*v),
+            _ => None,
+        }
+    }
+}
+
+struct Trailer {
+    offset_size: usize,
+    ref_size: usize,
+    object_count: usize,
+    top_object: usize,
+    offset_table_offset: usize,
+}
+
+// Deeper nesting than this is either corrupt or a reference cycle
+const MAX_DEPTH: usize = 32;
+// Objects can be referenced more than once, so this bounds how far a small list can expand
+const MAX_DECODED_OBJECTS: usize = 1 << 16;
+
+fn read_be(data: &[u8], position: usize, size: usize) -> Result<u64, BplistError> {
+    let bytes = data
+        .get(position..position.saturating_add(size))
+        .ok_or(BplistError(format!(
+            "Read of {} bytes at {} is out of bounds",
+            size, position
+        )))?;
+    if size > 8 {
+        return Err(BplistError(format!("Integer size {} is too large", size)));
+    }
+
+    Ok(bytes.iter().fold(0, |value, b| (value << 8) | *b as u64))
+}
+
+fn read_trailer(data: &[u8]) -> Result<Trailer, BplistError> {
+    if data.len() < 40 {
+        return Err(BplistError(
+            "Too short to be a binary property list".to_string(),
+        ));
+    }
+    let start = data.len() - 32;
+
+    let trailer = Trailer {
+        offset_size: data[start + 6] as usize,
+        ref_size: data[start + 7] as usize,
+        object_count: read_be(data, start + 8, 8)? as usize,
+        top_object: read_be(data, start + 16, 8)? as usize,
+        offset_table_offset: read_be(data, start + 24, 8)? as usize,
+    };
+
+    for (name, size) in [
+        ("offset", trailer.offset_size),
+        ("reference", trailer.ref_size),
+    ] {
+        if !(1..=8).contains(&size) {
+            return Err(BplistError(format!("Invalid {} size {}", name, size)));
+        }
+    }
+
+    if trailer.top_object >= trailer.object_count {
+        return Err(BplistError(format!(
+            "Top object {} is out of bounds ({} objects)",
+            trailer.top_object, trailer.object_count
+        )));
+    }
+
+    // The offset table sits between the objects and the trailer
+    let table_end = trailer
+        .object_count
+        .checked_mul(trailer.offset_size)
+        .and_then(|size| size.checked_add(trailer.offset_table_offset));
+    if !matches!(table_end, Some(end) if end <= start) {
+        return Err(BplistError(format!(
+            "The offset table ({} offsets of {} bytes at {}) is out of bounds",
+            trailer.object_count, trailer.offset_size, trailer.offset_table_offset
+        )));
+    }
+
+    Ok(trailer)
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    trailer: Trailer,
+    decoded_objects: Cell<usize>,
+}
+
+impl Reader<'_> {
+    fn object_offset(&self, object: usize) -> Result<usize, BplistError> {
+        if object >= self.trailer.object_count {
+            return Err(BplistError(format!("Object {} is out of bounds", object)));
+        }
+
+        let position = object
+            .checked_mul(self.trailer.offset_size)
+            .and_then(|offset| offset.checked_add(self.trailer.offset_table_offset))
+            .ok_or(BplistError(format!("Object {} is out of bounds", object)))?;
+        Ok(read_be(self.data, position, self.trailer.offset_size)? as usize)
+    }
+
+    /// Reads the length in the low nibble of a marker, which if 0xF is followed by an integer
+    /// object. Returns the length and the position after it.
+    fn read_length(&self, marker: u8, position: usize) -> Result<(usize, usize), BplistError> {
+        if marker & 0x0F != 0x0F {
+            return Ok(((marker & 0x0F) as usize, position));
+        }
+
+        let int_marker = *self
+            .data
+            .get(position)
+            .ok_or(BplistError("Length is out of bounds".to_string()))?;
+        if int_marker & 0xF0 != 0x10 {
+            return Err(BplistError(format!(
+                "Expected an integer length (got {:#x})",
+                int_marker
+            )));
+        }
+        let size = 1 << (int_marker & 0x0F);
+
+        Ok((
+            read_be(self.data, position + 1, size)? as usize,
+            position + 1 + size,
+        ))
+    }
+
+    fn read_bytes(&self, position: usize, length: usize) -> Result<&[u8], BplistError> {
+        self.data
+            .get(position..position.saturating_add(length))
+            .ok_or(BplistError(format!(
+                "Data at {} is out of bounds",
+                position
+            )))
+    }
+
+    fn read_refs(&self, position: usize, count: usize) -> Result<Vec<usize>, BplistError> {
+        if count > self.trailer.object_count {
+            return Err(BplistError(format!(
+                "{} references is more than the {} objects",
+                count, self.trailer.object_count
+            )));
+        }
+
+        (0..count)
+            .map(|i| {
+                read_be(
+                    self.data,
+                    position + i * self.trailer.ref_size,
+                    self.trailer.ref_size,
+                )
+                .map(|r| r as usize)
+            })
+            .collect()
+    }
+
+    fn read_object(&self, object: usize, depth: usize) -> Result<BplistValue, BplistError> {
+        if depth > MAX_DEPTH {
+            return Err(BplistError("Objects are nested too deeply".to_string()));
+        }
+        let decoded_objects = self.decoded_objects.get() + 1;
+        if decoded_objects > MAX_DECODED_OBJECTS {
+            return Err(BplistError("Too many objects".to_string()));
+        }
+        self.decoded_objects.set(decoded_objects);
+
+        let position = self.object_offset(object)?;
+        let marker = *self
+            .data
+            .get(position)
+            .ok_or(BplistError(format!("Object {} is out of bounds", object)))?;
+        let position = position + 1;
+
+        let value = match marker >> 4 {
+            0x0 => match marker {
+                0x00 => BplistValue::Null,
+                0x08 => BplistValue::Bool(false),
+                0x09 => BplistValue::Bool(true),
+                _ => return Err(BplistError(format!("Unknown marker {:#x}", marker))),
+            },
+            0x1 => {
+                let size = 1 << (marker & 0x0F);
+                let value = read_be(self.data, position, size)?;
+                // 1, 2 and 4 byte integers are unsigned, 8 byte integers are signed
+                BplistValue::Integer(value as i64)
+            }
+            0x2 => match marker & 0x0F {
+                2 => {
+                    BplistValue::Real(f32::from_bits(read_be(self.data, position, 4)? as u32) as f64)
+                }
+                3 => BplistValue::Real(f64::from_bits(read_be(self.data, position, 8)?)),
+                size => return Err(BplistError(format!("Unknown real size {}", size))),
+            },
+            0x3 => BplistValue::Date(f64::from_bits(read_be(self.data, position, 8)?)),
+            0x4 => {
+                let (length, position) = self.read_length(marker, position)?;
+                BplistValue::Data(self.read_bytes(position, length)?.to_vec())
+            }
+            0x5 => {
+                let (length, position) = self.read_length(marker, position)?;
+                BplistValue::String(
+                    String::from_utf8_lossy(self.read_bytes(position, length)?).to_string(),
+                )
+            }
+            0x6 => {
+                let (length, position) = self.read_length(marker, position)?;
+                let units: Vec<u16> = self
+                    .read_bytes(position, length.saturating_mul(2))?
+                    .chunks_exact(2)
+                    .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                    .collect();
+                BplistValue::String(String::from_utf16_lossy(&units))
+            }
+            0x8 => BplistValue::Uid(read_be(self.data, position, (marker & 0x0F) as usize + 1)?),
+            0xA => {
+                let (count, position) = self.read_length(marker, position)?;
+                BplistValue::Array(
+                    self.read_refs(position, count)?
+                        .into_iter()
+                        .map(|r| self.read_object(r, depth + 1))
+                        .collect::<Result<Vec<BplistValue>, BplistError>>()?,
+                )
+            }
+            0xD => {
+                let (count, position) = self.read_length(marker, position)?;
+                let keys = self.read_refs(position, count)?;
+                let values = self.read_refs(position + count * self.trailer.ref_size, count)?;
+
+                let mut entries: Vec<(String, BplistValue)> = vec![];
+                for (key, value) in keys.into_iter().zip(values) {
+                    let key = match self.read_object(key, depth + 1)? {
+                        BplistValue::String(key) => key,
+                        other => {
+                            return Err(BplistError(format!(
+                                "Expected dictionary keys to be strings (got {:?})",
+                                other
+                            )))
+                        }
+                    };
+                    entries.push((key, self.read_object(value, depth + 1)?));
+                }
+                BplistValue::Dictionary(entries)
+            }
+            _ => return Err(BplistError(format!("Unknown marker {:#x}", marker))),
+        };
+
+        Ok(value)
+    }
+}
+
+pub fn read_bplist(data: &[u8]) -> Result<BplistValue, BplistError> {
+    if !data.starts_with(b"bplist00") {
+        return Err(BplistError("Missing bplist00 header".to_string()));
+    }
+
+    let trailer = read_trailer(data)?;
+    let top_object = trailer.top_object;
+    let reader = Reader {
+        data,
+        trailer,
+        decoded_objects: Cell::new(0),
+    };
+
+    reader.read_object(top_object, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_bplist, BplistValue};
+
+    #[test]
+    fn test_read_bplist() {
+        // {"flags": 1, "value": 1234567, "name": "Test", "list": [true, 1.5]}
+        let mut data = b"bplist00".to_vec();
+        let mut offsets: Vec<u8> = vec![];
+        let mut push = |data: &mut Vec<u8>, object: &[u8]| {
+            offsets.push(data.len() as u8);
+            data.extend(object);
+        };
+        push(&mut data, b"\xD4\x01\x02\x03\x04\x05\x06\x07\x08"); // 0
+        push(&mut data, b"\x55flags"); // 1
+        push(&mut data, b"\x55value"); // 2
+        push(&mut data, b"\x54name"); // 3
+        push(&mut data, b"\x54list"); // 4
+        push(&mut data, b"\x10\x01"); // 5
+        push(&mut data, b"\x12\x00\x12\xD6\x87"); // 6
+        push(&mut data, b"\x64\0T\0e\0s\0t"); // 7
+        push(&mut data, b"\xA2\x09\x0A"); // 8
+        push(&mut data, b"\x09"); // 9
+        push(&mut data, b"\x23\x3F\xF8\0\0\0\0\0\0"); // 10
+
+        let offset_table_offset = data.len() as u8;
+        data.extend(&offsets);
+        data.extend([0, 0, 0, 0, 0, 0, 1, 1]);
+        data.extend([0, 0, 0, 0, 0, 0, 0, offsets.len() as u8]);
+        data.extend([0; 8]);
+        data.extend([0, 0, 0, 0, 0, 0, 0, offset_table_offset]);
+
+        let value = read_bplist(&data).unwrap();
+        assert_eq!(value.get("flags").and_then(|v| v.as_i64()), Some(1));
+        assert_eq!(value.get("value").and_then(|v| v.as_i64()), Some(1234567));
+        assert_eq!(
+            value.get("name"),
+            Some(&BplistValue::String("Test".to_string()))
+        );
+        assert_eq!(
+            value.get("list"),
+            Some(&BplistValue::Array(vec![
+                BplistValue::Bool(true),
+                BplistValue::Real(1.5)
+            ]))
+        );
+        assert_eq!(value.get("missing"), None);
+
+        assert!(read_bplist(b"bplist00").is_err());
+        assert!(read_bplist(&data[..data.len() - 1]).is_err());
+
+        // An object count too large for the offset table to fit in the data
+        let length = data.len();
+        data[length - 24..length - 16].copy_from_slice(&[0xFF; 8]);
+        assert!(read_bplist(&data).is_err());
+    }
+
+    /// A bplist of `objects` with 1 byte offsets and references
+    fn build_bplist(objects: &[&[u8]], ref_size: u8) -> Vec<u8> {
+        let mut data = b"bplist00".to_vec();
+        let mut offsets: Vec<u8> = vec![];
+        for object in objects {
+            offsets.push(data.len() as u8);
+            data.extend(*object);
+        }
+
+        let offset_table_offset = data.len() as u8;
+        data.extend(&offsets);
+        data.extend([0, 0, 0, 0, 0, 0, 1, ref_size]);
+        data.extend([0, 0, 0, 0, 0, 0, 0, offsets.len() as u8]);
+        data.extend([0; 8]);
+        data.extend([0, 0, 0, 0, 0, 0, 0, offset_table_offset]);
+        data
+    }
+
+    #[test]
+    fn test_read_bplist_limits() {
+        // A reference size of 0 with a huge extended count
+        let data = build_bplist(&[b"\xAF\x13\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF"], 0);
+        assert!(read_bplist(&data).is_err());
+
+        // More references than there are objects
+        let data = build_bplist(&[b"\xA3\x01\x01\x01", b"\x09"], 1);
+        assert!(read_bplist(&data).is_err());
+
+        // Each array references the next one twice, which would expand to 2^20 values
+        let mut objects: Vec<Vec<u8>> = (1..=20_u8).map(|next| vec![0xA2, next, next]).collect();
+        objects.push(b"\x09".to_vec());
+        let objects: Vec<&[u8]> = objects.iter().map(|o| o.as_slice()).collect();
+        assert!(read_bplist(&build_bplist(&objects, 1)).is_err());
+
+        // Shared references within the limit are still read
+        let data = build_bplist(&[b"\xA2\x01\x01", b"\x09"], 1);
+        assert_eq!(
+            read_bplist(&data).unwrap(),
+            BplistValue::Array(vec![BplistValue::Bool(true), BplistValue::Bool(true)])
+        );
+    }
+}
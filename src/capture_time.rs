@@ -0,0 +1,359 @@
+use std::fmt;
+
+use crate::{
+    get_tag_value,
+    tiff::{Tiff, TiffTag},
+};
+
+/// Which of the timestamps stored in EXIF to read
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum TimestampKind {
+    Original,  // When the photo was taken (DateTimeOriginal)
+    Digitized, // When the image was stored digitally (DateTimeDigitized)
+    Modified,  // When the file was last changed (DateTime)
+}
+
+/// A timestamp assembled from the EXIF date/time, sub-second and offset tags
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct CaptureTime {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    pub nanosecond: u32,
+    pub utc_offset_minutes: Option<i32>,
+    pub offset_from_gps: bool, // Whether the offset was inferred by comparing to the GPS (UTC) time
+}
+
+// Offsets are inferred from GPS to the nearest 15 minutes, and real offsets go from -12:00 to +14:00
+const OFFSET_GRANULARITY_SECONDS: i64 = 15 * 60;
+const MAX_OFFSET_SECONDS: i64 = 14 * 60 * 60;
+
+fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i64, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Days since 1970-01-01 in the proleptic Gregorian calendar
+// See <https://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+fn days_from_civil(year: i64, month: u8, day: u8) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month as i64 + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146097 + day_of_era - 719468
+}
+
+fn parse_number(value: &str) -> Option<u32> {
+    if value.is_empty() || !value.bytes().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    value.parse().ok()
+}
+
+/// Parses an EXIF "YYYY:MM:DD" date, returning None for blank or out-of-range values
+pub fn parse_exif_date(value: &str) -> Option<(u16, u8, u8)> {
+    let mut parts = value.trim().split([':', '-']);
+
+    let year = parse_number(parts.next()?)?;
+    let month = parse_number(parts.next()?)?;
+    let day = parse_number(parts.next()?)?;
+
+    if parts.next().is_some() || !(1..=9999).contains(&year) || !(1..=12).contains(&month) {
+        return None;
+    }
+
+    if day < 1 || day > days_in_month(year as i64, month as u8) as u32 {
+        return None;
+    }
+
+    Some((year as u16, month as u8, day as u8))
+}
+
+/// Parses an EXIF "HH:MM:SS" time, returning None for blank or out-of-range values
+pub fn parse_exif_time(value: &str) -> Option<(u8, u8, u8)> {
+    let mut parts = value.trim().split(':');
+
+    let hour = parse_number(parts.next()?)?;
+    let minute = parse_number(parts.next()?)?;
+    let second = parse_number(parts.next()?)?;
+
+    // A second of 60 is allowed for leap seconds
+    if parts.next().is_some() || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    Some((hour as u8, minute as u8, second as u8))
+}
+
+/// Parses an EXIF "+HH:MM" / "-HH:MM" offset into minutes
+pub fn parse_exif_offset(value: &str) -> Option<i32> {
+    let value = value.trim().trim_end_matches('\0');
+
+    if value == "Z" {
+        return Some(0);
+    }
+
+    let (sign, rest) = match value.chars().next()? {
+        '+' => (1, &value[1..]),
+        '-' => (-1, &value[1..]),
+        _ => return None,
+    };
+
+    let (hours, minutes) = rest.split_once(':')?;
+    let (hours, minutes) = (parse_number(hours)?, parse_number(minutes)?);
+
+    if hours > 14 || minutes > 59 {
+        return None;
+    }
+
+    Some(sign * (hours * 60 + minutes) as i32)
+}
+
+/// Parses the digits of a SubsecTime* tag (the fractional part of a second) into nanoseconds
+fn parse_subsec(value: &str) -> Option<u32> {
+    let digits = value.trim().trim_end_matches('\0');
+    parse_number(digits)?;
+
+    // Only the first 9 digits fit into nanoseconds
+    let digits = &digits[..digits.len().min(9)];
+
+    Some(digits.parse::<u32>().ok()? * 10_u32.pow(9 - digits.len() as u32))
+}
+
+impl CaptureTime {
+    /// Parses an EXIF "YYYY:MM:DD HH:MM:SS" date/time, returning None for malformed or blank
+    /// values (such as "0000:00:00 00:00:00" or "    :  :     :  :  ")
+    pub fn parse(datetime: &str) -> Option<CaptureTime> {
+        let (date, time) = datetime.trim().trim_end_matches('\0').split_once([' ', 'T'])?;
+
+        let (year, month, day) = parse_exif_date(date)?;
+        let (hour, minute, second) = parse_exif_time(time)?;
+
+        Some(CaptureTime {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            nanosecond: 0,
+            utc_offset_minutes: None,
+            offset_from_gps: false,
+        })
+    }
+
+    /// Seconds since the Unix epoch, ignoring the UTC offset (i.e. as if this local time were UTC)
+    fn local_seconds(&self) -> i64 {
+        days_from_civil(self.year as i64, self.month, self.day) * 86400
+            + self.hour as i64 * 3600
+            + self.minute as i64 * 60
+            + self.second as i64
+    }
+
+    /// Seconds since the Unix epoch, only available when the UTC offset is known
+    pub fn unix_timestamp(&self) -> Option<i64> {
+        Some(self.local_seconds() - self.utc_offset_minutes? as i64 * 60)
+    }
+
+    /// Unix timestamp in nanoseconds, only available when the UTC offset is known
+    pub fn unix_timestamp_nanos(&self) -> Option<i128> {
+        Some(self.unix_timestamp()? as i128 * 1_000_000_000 + self.nanosecond as i128)
+    }
+}
+
+impl fmt::Display for CaptureTime {
+    /// Formats as RFC 3339 / ISO 8601, without an offset if it is unknown
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )?;
+
+        if self.nanosecond != 0 {
+            let fraction = format!("{:09}", self.nanosecond);
+            write!(f, ".{}", fraction.trim_end_matches('0'))?;
+        }
+
+        match self.utc_offset_minutes {
+            Some(0) => write!(f, "Z"),
+            Some(offset) => write!(
+                f,
+                "{}{:02}:{:02}",
+                if offset < 0 { '-' } else { '+' },
+                offset.abs() / 60,
+                offset.abs() % 60
+            ),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Tiff {
+    /// When the photo was taken, see [`Tiff::timestamp`]
+    pub fn capture_time(&self) -> Option<CaptureTime> {
+        self.timestamp(TimestampKind::Original)
+    }
+
+    /// Assembles one of the EXIF timestamps from its date/time, SubsecTime* and OffsetTime* tags.
+    ///
+    /// When the offset tag is missing, the offset of the original/digitized timestamps is inferred
+    /// from the GPS date and time (which are UTC) if they are close enough to be the same moment.
+    pub fn timestamp(&self, kind: TimestampKind) -> Option<CaptureTime> {
+        let (datetime, subsec, offset) = match kind {
+            TimestampKind::Original => (
+                get_tag_value!(self.tags, TiffTag::DateTimeOriginal),
+                get_tag_value!(self.tags, TiffTag::SubsecTimeOriginal),
+                get_tag_value!(self.tags, TiffTag::OffsetTimeOriginal),
+            ),
+            TimestampKind::Digitized => (
+                get_tag_value!(self.tags, TiffTag::DateTimeDigitized),
+                get_tag_value!(self.tags, TiffTag::SubsecTimeDigitized),
+                get_tag_value!(self.tags, TiffTag::OffsetTimeDigitized),
+            ),
+            TimestampKind::Modified => (
+                get_tag_value!(self.tags, TiffTag::DateTime),
+                get_tag_value!(self.tags, TiffTag::SubsecTime),
+                get_tag_value!(self.tags, TiffTag::OffsetTime),
+            ),
+        };
+
+        let mut time = CaptureTime::parse(datetime?)?;
+        time.nanosecond = subsec.and_then(|s| parse_subsec(s)).unwrap_or(0);
+        time.utc_offset_minutes = offset.and_then(|o| parse_exif_offset(o));
+
+        if time.utc_offset_minutes.is_none() && kind != TimestampKind::Modified {
+            if let Some(gps_time) = self.gps_timestamp() {
+                let difference = time.local_seconds() - gps_time.local_seconds();
+                let rounded = (difference as f64 / OFFSET_GRANULARITY_SECONDS as f64).round() as i64
+                    * OFFSET_GRANULARITY_SECONDS;
+
+                if rounded.abs() <= MAX_OFFSET_SECONDS {
+                    time.utc_offset_minutes = Some((rounded / 60) as i32);
+                    time.offset_from_gps = true;
+                }
+            }
+        }
+
+        Some(time)
+    }
+
+    /// The (UTC) time of the GPS fix, from GPSDateStamp and GPSTimeStamp
+    pub fn gps_timestamp(&self) -> Option<CaptureTime> {
+        let (year, month, day) = parse_exif_date(get_tag_value!(self.tags, TiffTag::GPSDateStamp)?)?;
+        let [hour, minute, second] = *get_tag_value!(self.tags, TiffTag::GPSTimeStamp)?;
+
+        if !(0.0..24.0).contains(&hour) || !(0.0..60.0).contains(&minute) || !(0.0..61.0).contains(&second) {
+            return None;
+        }
+
+        // The components are rationals, so in theory any of them could have a fractional part
+        let total = hour * 3600.0 + minute * 60.0 + second;
+        let whole_seconds = total.trunc() as u32;
+
+        Some(CaptureTime {
+            year,
+            month,
+            day,
+            hour: (whole_seconds / 3600).min(23) as u8,
+            minute: (whole_seconds / 60 % 60) as u8,
+            second: (whole_seconds % 60) as u8,
+            nanosecond: (total.fract() * 1e9).round().min(999_999_999.0) as u32,
+            utc_offset_minutes: Some(0),
+            offset_from_gps: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{parse_exif_offset, CaptureTime, TimestampKind};
+    use crate::{
+        jpeg::read_jpeg,
+        tiff::{Tiff, TiffTag},
+    };
+
+    #[test]
+    fn test_parse() {
+        let time = CaptureTime::parse("2008:10:22 16:29:49").unwrap();
+        assert_eq!((time.year, time.month, time.day), (2008, 10, 22));
+        assert_eq!((time.hour, time.minute, time.second), (16, 29, 49));
+
+        assert_eq!(CaptureTime::parse("0000:00:00 00:00:00"), None);
+        assert_eq!(CaptureTime::parse("    :  :     :  :  "), None);
+        assert_eq!(CaptureTime::parse("2023:02:29 10:00:00"), None);
+        assert_eq!(CaptureTime::parse("2023:01:01 25:00:00"), None);
+        assert_eq!(CaptureTime::parse(""), None);
+
+        assert_eq!(parse_exif_offset("+09:00"), Some(540));
+        assert_eq!(parse_exif_offset("-05:30"), Some(-330));
+        assert_eq!(parse_exif_offset("   :  "), None);
+    }
+
+    #[test]
+    fn test_timestamp_with_subsec_and_offset() {
+        let tiff = Tiff::from_tags(vec![
+            TiffTag::DateTimeOriginal("2021:07:04 12:30:15".to_string()),
+            TiffTag::SubsecTimeOriginal("042".to_string()),
+            TiffTag::OffsetTimeOriginal("-04:00".to_string()),
+        ]);
+
+        let time = tiff.capture_time().unwrap();
+        assert_eq!(time.nanosecond, 42_000_000);
+        assert_eq!(time.utc_offset_minutes, Some(-240));
+        assert!(!time.offset_from_gps);
+        assert_eq!(time.unix_timestamp(), Some(1625416215));
+        assert_eq!(time.to_string(), "2021-07-04T12:30:15.042-04:00");
+
+        assert_eq!(tiff.timestamp(TimestampKind::Modified), None);
+    }
+
+    #[test]
+    fn test_offset_from_gps() {
+        let tiff = Tiff::from_tags(vec![
+            TiffTag::DateTimeOriginal("2021:07:04 08:30:15".to_string()),
+            TiffTag::GPSDateStamp("2021:07:04".to_string()),
+            TiffTag::GPSTimeStamp([12.0, 30.0, 14.5]),
+        ]);
+
+        let time = tiff.capture_time().unwrap();
+        assert_eq!(time.utc_offset_minutes, Some(-240));
+        assert!(time.offset_from_gps);
+
+        let gps_time = tiff.gps_timestamp().unwrap();
+        assert_eq!(gps_time.to_string(), "2021-07-04T12:30:14.5Z");
+    }
+
+    #[test]
+    fn test_gps_jpeg() {
+        let data = fs::read("test_images/gps.jpeg").unwrap();
+        let exif = read_jpeg(&data).unwrap().exif.unwrap();
+
+        // The GPS fix is almost a day after the local time, so no offset can be inferred from it
+        let time = exif.capture_time().unwrap();
+        assert_eq!(time.to_string(), "2008-10-22T16:29:49");
+
+        assert_eq!(exif.gps_timestamp().unwrap().to_string(), "2008-10-23T14:28:17.24Z");
+
+        let data = fs::read("test_images/test9.jpeg").unwrap();
+        let exif = read_jpeg(&data).unwrap().exif.unwrap();
+        assert_eq!(exif.capture_time(), None);
+    }
+}
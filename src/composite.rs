@@ -0,0 +1,381 @@
+use crate::{
+    get_tag_value,
+    tiff::{Tiff, TiffTag},
+};
+
+/// The diagonal of a 36x24mm full frame, in millimeters
+const FULL_FRAME_DIAGONAL: f64 = 43.266615305567875;
+
+/// The circle of confusion of a full frame, in millimeters
+const FULL_FRAME_CIRCLE_OF_CONFUSION: f64 = 0.03;
+
+/// A value derived from other tags, along with the names of the tags it was computed from
+#[derive(Debug, PartialEq, Clone)]
+pub struct CompositeValue {
+    pub value: f64,
+    pub sources: Vec<&'static str>,
+}
+
+impl CompositeValue {
+    fn new(value: f64, sources: Vec<&'static str>) -> Option<CompositeValue> {
+        match value.is_finite() {
+            true => Some(CompositeValue { value, sources }),
+            false => None,
+        }
+    }
+
+    /// Combines two values, keeping the sources of both
+    fn combine(
+        &self,
+        other: &CompositeValue,
+        f: impl Fn(f64, f64) -> f64,
+    ) -> Option<CompositeValue> {
+        let mut sources = self.sources.clone();
+        sources.extend(other.sources.iter().filter(|s| !self.sources.contains(s)));
+
+        CompositeValue::new(f(self.value, other.value), sources)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Composite {
+    pub focal_length_35mm: Option<CompositeValue>, // Millimeters
+    pub crop_factor: Option<CompositeValue>,
+    pub field_of_view_horizontal: Option<CompositeValue>, // Degrees
+    pub field_of_view_vertical: Option<CompositeValue>,   // Degrees
+    pub exposure_value: Option<CompositeValue>,           // EV of the aperture and shutter speed
+    pub light_value: Option<CompositeValue>, // The exposure value normalized to ISO 100
+    pub hyperfocal_distance: Option<CompositeValue>, // Meters
+}
+
+/// Parses a rational in the "numerator/denominator" representation used by TiffTag
+fn parse_rational(repr: &str) -> Option<f64> {
+    let (numerator, denominator) = repr.split_once('/')?;
+    let value = numerator.trim().parse::<f64>().ok()? / denominator.trim().parse::<f64>().ok()?;
+
+    value.is_finite().then_some(value)
+}
+
+impl Tiff {
+    fn rational(&self, tag: Option<&String>, name: &'static str) -> Option<CompositeValue> {
+        CompositeValue::new(parse_rational(tag?)?, vec![name])
+    }
+
+    fn focal_length(&self) -> Option<CompositeValue> {
+        self.rational(
+            get_tag_value!(self.tags, TiffTag::FocalLength),
+            "FocalLength",
+        )
+        .filter(|f| f.value > 0.0)
+    }
+
+    fn f_number(&self) -> Option<CompositeValue> {
+        match self.rational(get_tag_value!(self.tags, TiffTag::FNumber), "FNumber") {
+            Some(n) if n.value > 0.0 => Some(n),
+            // APEX aperture value, Av = 2 * log2(N)
+            _ => self
+                .rational(
+                    get_tag_value!(self.tags, TiffTag::ApertureValue),
+                    "ApertureValue",
+                )
+                .and_then(|av| CompositeValue::new(2_f64.powf(av.value / 2.0), av.sources)),
+        }
+    }
+
+    fn exposure_time(&self) -> Option<CompositeValue> {
+        match self.rational(
+            get_tag_value!(self.tags, TiffTag::ExposureTime),
+            "ExposureTime",
+        ) {
+            Some(t) if t.value > 0.0 => Some(t),
+            // APEX shutter speed value, Tv = -log2(t)
+            _ => self
+                .rational(
+                    get_tag_value!(self.tags, TiffTag::ShutterSpeedValue),
+                    "ShutterSpeedValue",
+                )
+                .and_then(|tv| CompositeValue::new(2_f64.powf(-tv.value), tv.sources)),
+        }
+    }
+
+    fn iso(&self) -> Option<CompositeValue> {
+        match get_tag_value!(self.tags, TiffTag::PhotographicSensitivity).and_then(|v| v.first()) {
+            // 65535 means the ISO is too high to fit and is stored in ISOSpeed instead
+            Some(iso) if *iso > 0 && *iso < 65535 => {
+                CompositeValue::new(*iso as f64, vec!["PhotographicSensitivity"])
+            }
+            _ => get_tag_value!(self.tags, TiffTag::ISOSpeed)
+                .filter(|iso| **iso > 0)
+                .and_then(|iso| CompositeValue::new(*iso as f64, vec!["ISOSpeed"])),
+        }
+    }
+
+    /// The width and height of the image in pixels
+    fn image_size(&self) -> Option<(f64, f64, Vec<&'static str>)> {
+        let pixel_dimensions = (
+            get_tag_value!(self.tags, TiffTag::PixelXDimension),
+            get_tag_value!(self.tags, TiffTag::PixelYDimension),
+        );
+
+        match pixel_dimensions {
+            (Some(width), Some(height)) if *width > 0 && *height > 0 => Some((
+                *width as f64,
+                *height as f64,
+                vec!["PixelXDimension", "PixelYDimension"],
+            )),
+            _ => match (
+                get_tag_value!(self.tags, TiffTag::ImageWidth),
+                get_tag_value!(self.tags, TiffTag::ImageLength),
+            ) {
+                (Some(width), Some(height)) if *width > 0 && *height > 0 => Some((
+                    *width as f64,
+                    *height as f64,
+                    vec!["ImageWidth", "ImageLength"],
+                )),
+                _ => None,
+            },
+        }
+    }
+
+    /// The sensor's diagonal in millimeters, from the focal plane resolution and the image size
+    fn sensor_diagonal(&self) -> Option<CompositeValue> {
+        let millimeters_per_unit = match get_tag_value!(
+            self.tags,
+            TiffTag::FocalPlaneResolutionUnit
+        )
+        .map(|u| u.as_str())
+        {
+            Some("inch") | None => 25.4,
+            Some("centimeter") => 10.0,
+            _ => return None,
+        };
+
+        let x_resolution = self.rational(
+            get_tag_value!(self.tags, TiffTag::FocalPlaneXResolution),
+            "FocalPlaneXResolution",
+        )?;
+        // Pixels are square unless the Y resolution says otherwise
+        let y_resolution = self
+            .rational(
+                get_tag_value!(self.tags, TiffTag::FocalPlaneYResolution),
+                "FocalPlaneYResolution",
+            )
+            .unwrap_or(x_resolution.clone());
+        if x_resolution.value <= 0.0 || y_resolution.value <= 0.0 {
+            return None;
+        }
+
+        let (width, height, size_sources) = self.image_size()?;
+        let sensor_width = width / x_resolution.value * millimeters_per_unit;
+        let sensor_height = height / y_resolution.value * millimeters_per_unit;
+
+        let mut sources = x_resolution.combine(&y_resolution, |a, _| a)?.sources;
+        sources.extend(size_sources);
+        if get_tag_value!(self.tags, TiffTag::FocalPlaneResolutionUnit).is_some() {
+            sources.push("FocalPlaneResolutionUnit");
+        }
+
+        CompositeValue::new(sensor_width.hypot(sensor_height), sources)
+    }
+
+    fn focal_length_in_35mm_film(&self) -> Option<CompositeValue> {
+        get_tag_value!(self.tags, TiffTag::FocalLengthIn35mmFilm)
+            .filter(|f| **f > 0)
+            .and_then(|f| CompositeValue::new(*f as f64, vec!["FocalLengthIn35mmFilm"]))
+    }
+
+    /// How much smaller the sensor's diagonal is than that of a 36x24mm full frame
+    pub fn crop_factor(&self) -> Option<CompositeValue> {
+        if let (Some(focal_length_35mm), Some(focal_length)) =
+            (self.focal_length_in_35mm_film(), self.focal_length())
+        {
+            return focal_length_35mm.combine(&focal_length, |f35, f| f35 / f);
+        }
+
+        let diagonal = self.sensor_diagonal()?;
+        CompositeValue::new(FULL_FRAME_DIAGONAL / diagonal.value, diagonal.sources)
+    }
+
+    pub fn focal_length_35mm(&self) -> Option<CompositeValue> {
+        match self.focal_length_in_35mm_film() {
+            Some(focal_length_35mm) => Some(focal_length_35mm),
+            None => self
+                .focal_length()?
+                .combine(&self.crop_factor()?, |f, crop| f * crop),
+        }
+    }
+
+    /// The angles of view in degrees, as (horizontal, vertical). This assumes a rectilinear lens
+    /// focused at infinity, on a sensor with the aspect ratio of the image (3:2 if unknown).
+    pub fn field_of_view(&self) -> Option<(CompositeValue, CompositeValue)> {
+        let focal_length_35mm = self.focal_length_35mm()?;
+
+        let (width, height, mut sources) = self.image_size().unwrap_or((36.0, 24.0, vec![]));
+        let frame_width = FULL_FRAME_DIAGONAL * width / width.hypot(height);
+        let frame_height = FULL_FRAME_DIAGONAL * height / width.hypot(height);
+
+        sources.retain(|s| !focal_length_35mm.sources.contains(s));
+        sources.splice(0..0, focal_length_35mm.sources.iter().copied());
+
+        let angle =
+            |size: f64| (2.0 * (size / (2.0 * focal_length_35mm.value)).atan()).to_degrees();
+        Some((
+            CompositeValue::new(angle(frame_width), sources.clone())?,
+            CompositeValue::new(angle(frame_height), sources)?,
+        ))
+    }
+
+    /// The exposure value of the aperture and exposure time, EV = log2(N^2 / t)
+    pub fn exposure_value(&self) -> Option<CompositeValue> {
+        self.f_number()?
+            .combine(&self.exposure_time()?, |n, t| (n * n / t).log2())
+    }
+
+    /// The light value, which is the exposure value normalized to ISO 100 the same way as
+    /// exiftool's LightValue, LV = EV - log2(ISO / 100)
+    pub fn light_value(&self) -> Option<CompositeValue> {
+        self.exposure_value()?
+            .combine(&self.iso()?, |ev, iso| ev - (iso / 100.0).log2())
+    }
+
+    /// The focus distance in meters beyond which everything is acceptably sharp, using a circle
+    /// of confusion of 0.03mm scaled by the crop factor
+    pub fn hyperfocal_distance(&self) -> Option<CompositeValue> {
+        let crop_factor = self.crop_factor()?;
+        let circle_of_confusion = CompositeValue::new(
+            FULL_FRAME_CIRCLE_OF_CONFUSION / crop_factor.value,
+            crop_factor.sources,
+        )?;
+
+        let focal_length = self.focal_length()?;
+        let f_number = self.f_number()?;
+        let denominator = f_number.combine(&circle_of_confusion, |n, c| n * c)?;
+
+        focal_length.combine(&denominator, |f, nc| (f * f / nc + f) / 1000.0)
+    }
+
+    /// All composite values that can be computed from the tags
+    pub fn composite(&self) -> Composite {
+        let (field_of_view_horizontal, field_of_view_vertical) = self.field_of_view().unzip();
+
+        Composite {
+            focal_length_35mm: self.focal_length_35mm(),
+            crop_factor: self.crop_factor(),
+            field_of_view_horizontal,
+            field_of_view_vertical,
+            exposure_value: self.exposure_value(),
+            light_value: self.light_value(),
+            hyperfocal_distance: self.hyperfocal_distance(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::{
+        jpeg::read_jpeg,
+        tiff::{Tiff, TiffTag},
+    };
+
+    fn assert_close(value: f64, expected: f64) {
+        assert!(
+            (value - expected).abs() < 0.01,
+            "{} is not close to {}",
+            value,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_composite_from_35mm_focal_length() {
+        let data = fs::read("test_images/test9.jpeg").unwrap();
+        let composite = read_jpeg(&data).unwrap().exif.unwrap().composite();
+
+        let crop_factor = composite.crop_factor.unwrap();
+        assert_close(crop_factor.value, 105.0 / 18.7);
+        assert_eq!(
+            crop_factor.sources,
+            vec!["FocalLengthIn35mmFilm", "FocalLength"]
+        );
+
+        let focal_length_35mm = composite.focal_length_35mm.unwrap();
+        assert_eq!(focal_length_35mm.value, 105.0);
+        assert_eq!(focal_length_35mm.sources, vec!["FocalLengthIn35mmFilm"]);
+
+        // A 4:3 image, so the frame is 34.61 x 25.96mm
+        let horizontal = composite.field_of_view_horizontal.unwrap();
+        assert_close(horizontal.value, 18.72);
+        assert_eq!(
+            horizontal.sources,
+            vec![
+                "FocalLengthIn35mmFilm",
+                "PixelXDimension",
+                "PixelYDimension"
+            ]
+        );
+        assert_close(composite.field_of_view_vertical.unwrap().value, 14.09);
+
+        // f/4.2, 1/100s at ISO 200
+        let exposure_value = composite.exposure_value.unwrap();
+        assert_close(exposure_value.value, (4.2_f64 * 4.2 / 0.01).log2());
+        assert_eq!(exposure_value.sources, vec!["FNumber", "ExposureTime"]);
+
+        let light_value = composite.light_value.unwrap();
+        assert_close(light_value.value, (4.2_f64 * 4.2 / 0.01).log2() - 1.0);
+        assert_eq!(
+            light_value.sources,
+            vec!["FNumber", "ExposureTime", "PhotographicSensitivity"]
+        );
+
+        assert_close(composite.hyperfocal_distance.unwrap().value, 15.60);
+    }
+
+    #[test]
+    fn test_composite_from_apex_and_focal_plane() {
+        // A 6000x4000 image from a 23.5 x 15.67mm sensor, or a 1.53x crop
+        let tiff = Tiff::from_tags(vec![
+            TiffTag::FNumber("0/0".to_string()),
+            TiffTag::ApertureValue("4/1".to_string()),
+            TiffTag::ShutterSpeedValue("7/1".to_string()),
+            TiffTag::PhotographicSensitivity(vec![100]),
+            TiffTag::FocalLength("50/1".to_string()),
+            TiffTag::PixelXDimension(6000),
+            TiffTag::PixelYDimension(4000),
+            TiffTag::FocalPlaneXResolution("2553191/1000".to_string()),
+            TiffTag::FocalPlaneYResolution("2553191/1000".to_string()),
+            TiffTag::FocalPlaneResolutionUnit("centimeter".to_string()),
+        ]);
+
+        let crop_factor = tiff.crop_factor().unwrap();
+        assert_close(crop_factor.value, 1.53);
+        assert_eq!(
+            crop_factor.sources,
+            vec![
+                "FocalPlaneXResolution",
+                "FocalPlaneYResolution",
+                "PixelXDimension",
+                "PixelYDimension",
+                "FocalPlaneResolutionUnit"
+            ]
+        );
+        assert_close(tiff.focal_length_35mm().unwrap().value, 76.6);
+
+        // f/4 (Av 4) and 1/128s (Tv 7) at ISO 100
+        let light_value = tiff.light_value().unwrap();
+        assert_close(light_value.value, 11.0);
+        assert_eq!(
+            light_value.sources,
+            vec![
+                "ApertureValue",
+                "ShutterSpeedValue",
+                "PhotographicSensitivity"
+            ]
+        );
+
+        let composite = tiff.composite();
+        assert_eq!(composite.exposure_value.unwrap().value, light_value.value);
+        assert!(composite.hyperfocal_distance.is_some());
+    }
+}
@@ -0,0 +1,404 @@
+// Decompressors for the compression schemes used in TIFF strips and tiles
+
+#[derive(Debug)]
+pub struct DecompressError(pub String);
+
+/// PackBits, a simple run-length encoding. A header byte n in 0..=127 is followed by n + 1 literal
+/// bytes, n in -127..=-1 means the next byte is repeated 1 - n times and -128 is a no-op.
+pub fn unpack_bits(data: &[u8]) -> Result<Vec<u8>, DecompressError> {
+    let mut result: Vec<u8> = vec![];
+
+    let mut position = 0;
+    while position < data.len() {
+        let header = data[position] as i8;
+        position += 1;
+
+        match header {
+            0..=127 => {
+                let count = header as usize + 1;
+                let literal = data.get(position..position + count).ok_or_else(|| {
+                    DecompressError(format!(
+                        "PackBits literal of {} bytes at offset {} is truncated",
+                        count, position
+                    ))
+                })?;
+
+                result.extend(literal);
+                position += count;
+            }
+            -127..=-1 => {
+                let value = *data.get(position).ok_or_else(|| {
+                    DecompressError(format!("PackBits run at offset {} is truncated", position))
+                })?;
+
+                result.extend(std::iter::repeat_n(value, (1 - header as isize) as usize));
+                position += 1;
+            }
+            -128 => {}
+        }
+    }
+
+    Ok(result)
+}
+
+/// TIFF's variant of LZW, which reads codes MSB first and (unlike GIF) increases the code width
+/// one code early. Code 256 clears the table and 257 ends the data.
+pub fn lzw_decode(data: &[u8]) -> Result<Vec<u8>, DecompressError> {
+    const CLEAR_CODE: usize = 256;
+    const END_OF_INFORMATION: usize = 257;
+
+    let mut result: Vec<u8> = vec![];
+    let mut table: Vec<Vec<u8>> = (0..=255).map(|b| vec![b as u8]).collect();
+    table.extend([vec![], vec![]]);
+
+    let mut code_width = 9;
+    let mut bit_position = 0;
+    let mut previous: Option<usize> = None;
+
+    while bit_position + code_width <= data.len() * 8 {
+        let mut code = 0_usize;
+        for _ in 0..code_width {
+            let bit = (data[bit_position / 8] >> (7 - bit_position % 8)) & 1;
+            code = (code << 1) | bit as usize;
+            bit_position += 1;
+        }
+
+        if code == END_OF_INFORMATION {
+            break;
+        }
+
+        if code == CLEAR_CODE {
+            table.truncate(258);
+            code_width = 9;
+            previous = None;
+            continue;
+        }
+
+        let entry = match (previous, code.cmp(&table.len())) {
+            (_, std::cmp::Ordering::Less) => table[code].clone(),
+            // The code being defined right now, which is the previous entry plus its first byte
+            (Some(previous), std::cmp::Ordering::Equal) => {
+                let mut entry = table[previous].clone();
+                entry.push(table[previous][0]);
+                entry
+            }
+            _ => {
+                return Err(DecompressError(format!(
+                    "Invalid LZW code {} (the table only has {} entries)",
+                    code,
+                    table.len()
+                )))
+            }
+        };
+
+        result.extend(&entry);
+
+        if let Some(previous) = previous {
+            let mut new_entry = table[previous].clone();
+            new_entry.push(entry[0]);
+            table.push(new_entry);
+        }
+        previous = Some(code);
+
+        code_width = match table.len() + 1 {
+            512 => 10,
+            1024 => 11,
+            2048 => 12,
+            _ => code_width,
+        };
+    }
+
+    Ok(result)
+}
+
+struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize, // In bits
+}
+
+impl BitReader<'_> {
+    fn read_bits(&mut self, count: usize) -> Result<usize, DecompressError> {
+        let mut value = 0;
+        for i in 0..count {
+            let byte = self
+                .data
+                .get(self.position / 8)
+                .ok_or_else(|| DecompressError("Deflate data ended unexpectedly".to_string()))?;
+            value |= (((byte >> (self.position % 8)) & 1) as usize) << i;
+            self.position += 1;
+        }
+
+        Ok(value)
+    }
+}
+
+/// A canonical Huffman code, stored as the number of codes of each length and the symbols ordered
+/// by their code
+struct Huffman {
+    counts: [u16; 16],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Huffman {
+        let mut counts = [0_u16; 16];
+        for length in lengths {
+            counts[*length as usize] += 1;
+        }
+        counts[0] = 0;
+
+        let mut symbols: Vec<u16> = vec![];
+        for length in 1..16 {
+            for (symbol, l) in lengths.iter().enumerate() {
+                if *l as usize == length {
+                    symbols.push(symbol as u16);
+                }
+            }
+        }
+
+        Huffman { counts, symbols }
+    }
+
+    fn decode(&self, reader: &mut BitReader) -> Result<u16, DecompressError> {
+        let (mut code, mut first, mut index) = (0_i32, 0_i32, 0_i32);
+
+        for length in 1..16 {
+            code |= reader.read_bits(1)? as i32;
+            let count = self.counts[length] as i32;
+
+            if code - count < first {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+
+        Err(DecompressError(
+            "Invalid Huffman code in Deflate data".to_string(),
+        ))
+    }
+}
+
+const LENGTH_BASES: [usize; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA_BITS: [usize; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASES: [usize; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA_BITS: [usize; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+fn inflate_block(
+    reader: &mut BitReader,
+    result: &mut Vec<u8>,
+    literals: &Huffman,
+    distances: &Huffman,
+) -> Result<(), DecompressError> {
+    loop {
+        let symbol = literals.decode(reader)? as usize;
+
+        match symbol {
+            0..=255 => result.push(symbol as u8),
+            256 => return Ok(()),
+            257..=285 => {
+                let i = symbol - 257;
+                let length = LENGTH_BASES[i] + reader.read_bits(LENGTH_EXTRA_BITS[i])?;
+
+                let i = distances.decode(reader)? as usize;
+                if i >= 30 {
+                    return Err(DecompressError(format!(
+                        "Invalid Deflate distance code {}",
+                        i
+                    )));
+                }
+                let distance = DISTANCE_BASES[i] + reader.read_bits(DISTANCE_EXTRA_BITS[i])?;
+
+                if distance > result.len() {
+                    return Err(DecompressError(format!(
+                        "Deflate distance {} is before the start of the data",
+                        distance
+                    )));
+                }
+
+                // The copy can overlap with itself, so it has to be done byte by byte
+                let start = result.len() - distance;
+                for i in 0..length {
+                    result.push(result[start + i]);
+                }
+            }
+            _ => {
+                return Err(DecompressError(format!(
+                    "Invalid Deflate symbol {}",
+                    symbol
+                )))
+            }
+        }
+    }
+}
+
+fn read_dynamic_huffman(reader: &mut BitReader) -> Result<(Huffman, Huffman), DecompressError> {
+    const CODE_LENGTH_ORDER: [usize; 19] = [
+        16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+    ];
+
+    let literal_count = reader.read_bits(5)? + 257;
+    let distance_count = reader.read_bits(5)? + 1;
+    let code_length_count = reader.read_bits(4)? + 4;
+
+    let mut code_lengths = [0_u8; 19];
+    for i in CODE_LENGTH_ORDER.iter().take(code_length_count) {
+        code_lengths[*i] = reader.read_bits(3)? as u8;
+    }
+    let code_length_huffman = Huffman::new(&code_lengths);
+
+    let mut lengths: Vec<u8> = vec![];
+    while lengths.len() < literal_count + distance_count {
+        let (value, repeat) = match code_length_huffman.decode(reader)? {
+            symbol @ 0..=15 => (symbol as u8, 1),
+            16 => match lengths.last() {
+                Some(previous) => (*previous, 3 + reader.read_bits(2)?),
+                None => {
+                    return Err(DecompressError(
+                        "Deflate code length repeat without a previous length".to_string(),
+                    ))
+                }
+            },
+            17 => (0, 3 + reader.read_bits(3)?),
+            _ => (0, 11 + reader.read_bits(7)?),
+        };
+
+        lengths.extend(std::iter::repeat_n(value, repeat));
+    }
+
+    if lengths.len() != literal_count + distance_count {
+        return Err(DecompressError("Too many Deflate code lengths".to_string()));
+    }
+
+    Ok((
+        Huffman::new(&lengths[..literal_count]),
+        Huffman::new(&lengths[literal_count..]),
+    ))
+}
+
+/// Decompresses raw Deflate data (RFC 1951)
+pub fn inflate(data: &[u8]) -> Result<Vec<u8>, DecompressError> {
+    let mut reader = BitReader { data, position: 0 };
+    let mut result: Vec<u8> = vec![];
+
+    loop {
+        let is_final = reader.read_bits(1)? == 1;
+
+        match reader.read_bits(2)? {
+            0 => {
+                // Stored blocks start at the next byte boundary
+                let start = reader.position.div_ceil(8);
+                let header = data.get(start..start + 4).ok_or_else(|| {
+                    DecompressError("Stored Deflate block header is truncated".to_string())
+                })?;
+                let length = u16::from_le_bytes([header[0], header[1]]) as usize;
+
+                let block = data.get(start + 4..start + 4 + length).ok_or_else(|| {
+                    DecompressError("Stored Deflate block is truncated".to_string())
+                })?;
+                result.extend(block);
+                reader.position = (start + 4 + length) * 8;
+            }
+            1 => {
+                let mut lengths = [0_u8; 288];
+                lengths[0..144].fill(8);
+                lengths[144..256].fill(9);
+                lengths[256..280].fill(7);
+                lengths[280..288].fill(8);
+
+                let literals = Huffman::new(&lengths);
+                let distances = Huffman::new(&[5; 30]);
+                inflate_block(&mut reader, &mut result, &literals, &distances)?;
+            }
+            2 => {
+                let (literals, distances) = read_dynamic_huffman(&mut reader)?;
+                inflate_block(&mut reader, &mut result, &literals, &distances)?;
+            }
+            _ => return Err(DecompressError("Invalid Deflate block type".to_string())),
+        }
+
+        if is_final {
+            return Ok(result);
+        }
+    }
+}
+
+/// Decompresses zlib data (RFC 1950), which is Deflate data with a 2 byte header and a checksum
+pub fn zlib_decode(data: &[u8]) -> Result<Vec<u8>, DecompressError> {
+    match data {
+        [cmf, flg, rest @ ..]
+            if cmf & 0x0F == 8 && ((*cmf as u16) << 8 | *flg as u16).is_multiple_of(31) =>
+        {
+            inflate(rest)
+        }
+        _ => Err(DecompressError("Expected a zlib header".to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lzw_decode, unpack_bits, zlib_decode};
+
+    #[test]
+    fn test_unpack_bits() {
+        // The example from the TIFF 6.0 specification
+        let data = b"\xFE\xAA\x02\x80\x00\x2A\xFD\xAA\x03\x80\x00\x2A\x22\xF7\xAA";
+        let expected = b"\xAA\xAA\xAA\x80\x00\x2A\xAA\xAA\xAA\xAA\x80\x00\x2A\x22\xAA\xAA\xAA\xAA\xAA\xAA\xAA\xAA\xAA\xAA";
+
+        assert_eq!(unpack_bits(data).unwrap(), expected);
+        assert!(unpack_bits(b"\x05ab").is_err());
+    }
+
+    #[test]
+    fn test_lzw_decode() {
+        let data = [
+            0x80, 0x15, 0x09, 0xE4, 0x22, 0x29, 0x3C, 0xA4, 0x4E, 0x27, 0x95, 0x20, 0x50, 0x48,
+            0x34, 0x2E, 0x0B, 0x07, 0x84, 0xC0, 0x40,
+        ];
+
+        assert_eq!(lzw_decode(&data).unwrap(), b"TOBEORNOTTOBEORTOBEORNOT");
+    }
+
+    #[test]
+    fn test_zlib_decode() {
+        // Fixed Huffman codes
+        let data = [
+            0x78, 0xDA, 0xCB, 0x48, 0xCD, 0xC9, 0xC9, 0x57, 0xC8, 0x40, 0x90, 0x00, 0x3A, 0x2E,
+            0x06, 0x7D,
+        ];
+        assert_eq!(zlib_decode(&data).unwrap(), b"hello hello hello");
+
+        // Dynamic Huffman codes
+        let data = [
+            0x78, 0xDA, 0x1D, 0x89, 0xC1, 0x11, 0x00, 0x30, 0x0C, 0x82, 0x56, 0xC9, 0x6A, 0x10,
+            0xF7, 0x9F, 0xA1, 0xA6, 0x0F, 0x81, 0x3B, 0x19, 0x40, 0x8D, 0x55, 0x63, 0x4A, 0x3E,
+            0xC8, 0x62, 0x5A, 0x95, 0x1D, 0xD7, 0xB8, 0xE9, 0x73, 0x76, 0x1F, 0x96, 0x71, 0x16,
+            0x2A,
+        ];
+        assert_eq!(
+            zlib_decode(&data).unwrap(),
+            b"a aaabbbdbaababb abaa abaaadcabdabacabbcabadabaabcdaadaabcbc"
+        );
+
+        // Stored
+        let data = [
+            0x78, 0x01, 0x01, 0x03, 0x00, 0xFC, 0xFF, 0x61, 0x62, 0x63, 0x02, 0x4D, 0x01, 0x27,
+        ];
+        assert_eq!(zlib_decode(&data).unwrap(), b"abc");
+
+        assert!(zlib_decode(b"\x00\x00").is_err());
+    }
+}
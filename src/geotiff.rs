@@ -0,0 +1,422 @@
+// http://docs.opengeospatial.org/is/19-008r4/19-008r4.html
+
+use crate::{
+    get_tag_value,
+    tiff::{Tiff, TiffTag},
+};
+
+#[derive(Debug)]
+pub struct GeoTiffError(pub String);
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum GeoKeyValue {
+    Short(Vec<u16>),
+    Double(Vec<f64>),
+    Ascii(String),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct GeoKey {
+    pub id: u16,
+    pub name: String, // e.g. "GTModelTypeGeoKey"
+    pub value: GeoKeyValue,
+}
+
+/// The georeferencing of a single page
+#[derive(Debug, PartialEq, Clone)]
+pub struct GeoTiff {
+    pub version: String, // KeyDirectoryVersion.KeyRevision.MinorRevision, e.g. "1.1.0"
+    pub keys: Vec<GeoKey>,
+    pub pixel_scale: Option<[f64; 3]>,
+    pub tiepoints: Vec<[f64; 6]>, // (I, J, K) raster point and the matching (X, Y, Z) model point
+    pub transformation: Option<[f64; 16]>,
+    /// Affine transform from pixel (column, row) to model coordinates, in GDAL's order:
+    /// x = gt[0] + column * gt[1] + row * gt[2] and y = gt[3] + column * gt[4] + row * gt[5].
+    /// It always refers to pixel corners, PixelIsPoint rasters are shifted by half a pixel.
+    pub geotransform: Option<[f64; 6]>,
+    /// Model coordinates of the upper left, upper right, lower left and lower right corners
+    pub corners: Option<[(f64, f64); 4]>,
+}
+
+fn get_geo_key_name(id: u16) -> &'static str {
+    match id {
+        1024 => "GTModelTypeGeoKey",
+        1025 => "GTRasterTypeGeoKey",
+        1026 => "GTCitationGeoKey",
+        2048 => "GeographicTypeGeoKey",
+        2049 => "GeogCitationGeoKey",
+        2050 => "GeogGeodeticDatumGeoKey",
+        2051 => "GeogPrimeMeridianGeoKey",
+        2052 => "GeogLinearUnitsGeoKey",
+        2053 => "GeogLinearUnitSizeGeoKey",
+        2054 => "GeogAngularUnitsGeoKey",
+        2055 => "GeogAngularUnitSizeGeoKey",
+        2056 => "GeogEllipsoidGeoKey",
+        2057 => "GeogSemiMajorAxisGeoKey",
+        2058 => "GeogSemiMinorAxisGeoKey",
+        2059 => "GeogInvFlatteningGeoKey",
+        2060 => "GeogAzimuthUnitsGeoKey",
+        2061 => "GeogPrimeMeridianLongGeoKey",
+        2062 => "GeogTOWGS84GeoKey",
+        3072 => "ProjectedCSTypeGeoKey",
+        3073 => "PCSCitationGeoKey",
+        3074 => "ProjectionGeoKey",
+        3075 => "ProjCoordTransGeoKey",
+        3076 => "ProjLinearUnitsGeoKey",
+        3077 => "ProjLinearUnitSizeGeoKey",
+        3078 => "ProjStdParallel1GeoKey",
+        3079 => "ProjStdParallel2GeoKey",
+        3080 => "ProjNatOriginLongGeoKey",
+        3081 => "ProjNatOriginLatGeoKey",
+        3082 => "ProjFalseEastingGeoKey",
+        3083 => "ProjFalseNorthingGeoKey",
+        3084 => "ProjFalseOriginLongGeoKey",
+        3085 => "ProjFalseOriginLatGeoKey",
+        3086 => "ProjFalseOriginEastingGeoKey",
+        3087 => "ProjFalseOriginNorthingGeoKey",
+        3088 => "ProjCenterLongGeoKey",
+        3089 => "ProjCenterLatGeoKey",
+        3090 => "ProjCenterEastingGeoKey",
+        3091 => "ProjCenterNorthingGeoKey",
+        3092 => "ProjScaleAtNatOriginGeoKey",
+        3093 => "ProjScaleAtCenterGeoKey",
+        3094 => "ProjAzimuthAngleGeoKey",
+        3095 => "ProjStraightVertPoleLongGeoKey",
+        3096 => "ProjRectifiedGridAngleGeoKey",
+        4096 => "VerticalCSTypeGeoKey",
+        4097 => "VerticalCitationGeoKey",
+        4098 => "VerticalDatumGeoKey",
+        4099 => "VerticalUnitsGeoKey",
+        5120 => "CoordinateEpochGeoKey",
+        _ => "Unknown",
+    }
+}
+
+/// Resolves the GeoKeyDirectory, where each key is (KeyID, TIFFTagLocation, Count, ValueOffset).
+/// A location of 0 means the value is the offset itself, otherwise it's the tag the values are
+/// stored in: GeoKeyDirectory, GeoDoubleParams or GeoAsciiParams.
+pub fn read_geo_keys(
+    directory: &[u16],
+    double_params: &[f64],
+    ascii_params: &str,
+) -> Result<(String, Vec<GeoKey>), GeoTiffError> {
+    let header = directory.get(0..4).ok_or_else(|| {
+        GeoTiffError(format!(
+            "Expected at least 4 values for the header (got {})",
+            directory.len()
+        ))
+    })?;
+
+    let version = format!("{}.{}.{}", header[0], header[1], header[2]);
+    let ascii_params: Vec<char> = ascii_params.chars().collect();
+
+    let mut keys: Vec<GeoKey> = vec![];
+    for i in 0..header[3] as usize {
+        let entry = directory.get(4 + i * 4..8 + i * 4).ok_or_else(|| {
+            GeoTiffError(format!(
+                "Expected {} keys but the directory ends after {}",
+                header[3], i
+            ))
+        })?;
+
+        let (id, location, count, offset) =
+            (entry[0], entry[1], entry[2] as usize, entry[3] as usize);
+
+        let out_of_range = || {
+            GeoTiffError(format!(
+                "[GeoKey {}] Values {}..{} of tag {} are out of range",
+                id,
+                offset,
+                offset + count,
+                location
+            ))
+        };
+
+        let value = match location {
+            0 => GeoKeyValue::Short(vec![entry[3]]),
+            34735 => GeoKeyValue::Short(
+                directory
+                    .get(offset..offset + count)
+                    .ok_or_else(out_of_range)?
+                    .to_vec(),
+            ),
+            34736 => GeoKeyValue::Double(
+                double_params
+                    .get(offset..offset + count)
+                    .ok_or_else(out_of_range)?
+                    .to_vec(),
+            ),
+            // Strings are terminated by a '|', which is included in the count
+            34737 => GeoKeyValue::Ascii(
+                ascii_params
+                    .get(offset..offset + count)
+                    .ok_or_else(out_of_range)?
+                    .iter()
+                    .collect::<String>()
+                    .trim_end_matches(['|', '\0'])
+                    .to_string(),
+            ),
+            _ => {
+                return Err(GeoTiffError(format!(
+                    "[GeoKey {}] Unexpected tag location {}",
+                    id, location
+                )))
+            }
+        };
+
+        keys.push(GeoKey {
+            id,
+            name: get_geo_key_name(id).to_string(),
+            value,
+        });
+    }
+
+    Ok((version, keys))
+}
+
+impl GeoTiff {
+    pub fn get(&self, id: u16) -> Option<&GeoKeyValue> {
+        self.keys.iter().find(|k| k.id == id).map(|k| &k.value)
+    }
+
+    fn get_short(&self, id: u16) -> Option<u16> {
+        match self.get(id)? {
+            GeoKeyValue::Short(values) => values.first().copied(),
+            _ => None,
+        }
+    }
+
+    fn get_ascii(&self, id: u16) -> Option<String> {
+        match self.get(id)? {
+            GeoKeyValue::Ascii(value) => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn model_type(&self) -> Option<String> {
+        let model_type = match self.get_short(1024)? {
+            1 => "Projected",
+            2 => "Geographic",
+            3 => "Geocentric",
+            32767 => "User defined",
+            _ => "Invalid",
+        };
+
+        Some(model_type.to_string())
+    }
+
+    pub fn raster_type(&self) -> Option<String> {
+        let raster_type = match self.get_short(1025)? {
+            1 => "PixelIsArea",
+            2 => "PixelIsPoint",
+            32767 => "User defined",
+            _ => "Invalid",
+        };
+
+        Some(raster_type.to_string())
+    }
+
+    /// The EPSG code of the projected or geographic coordinate system, ignoring the "user defined"
+    /// (32767) and "undefined" (0) codes
+    pub fn epsg(&self) -> Option<u16> {
+        [3072, 2048]
+            .iter()
+            .filter_map(|id| self.get_short(*id))
+            .find(|code| ![0, 32767].contains(code))
+    }
+
+    /// The first of the GT, PCS and Geog citations that's present
+    pub fn citation(&self) -> Option<String> {
+        [1026, 3073, 2049].iter().find_map(|id| self.get_ascii(*id))
+    }
+
+    /// Maps a pixel (column, row) position to model coordinates
+    pub fn pixel_to_model(&self, column: f64, row: f64) -> Option<(f64, f64)> {
+        let gt = self.geotransform?;
+
+        Some((
+            gt[0] + column * gt[1] + row * gt[2],
+            gt[3] + column * gt[4] + row * gt[5],
+        ))
+    }
+}
+
+/// Decodes the GeoTIFF tags of a single page, returning None if the page isn't georeferenced
+pub fn read_geotiff(tags: &[TiffTag]) -> Result<Option<GeoTiff>, GeoTiffError> {
+    let pixel_scale = match get_tag_value!(tags, TiffTag::ModelPixelScale) {
+        Some(scale) => Some(<[f64; 3]>::try_from(scale.as_slice()).map_err(|_| {
+            GeoTiffError(format!(
+                "Expected 3 values for ModelPixelScale (got {})",
+                scale.len()
+            ))
+        })?),
+        None => None,
+    };
+
+    let tiepoints: Vec<[f64; 6]> = get_tag_value!(tags, TiffTag::ModelTiepoint)
+        .map(|t| {
+            t.chunks_exact(6)
+                .map(|c| [c[0], c[1], c[2], c[3], c[4], c[5]])
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let transformation = get_tag_value!(tags, TiffTag::ModelTransformation).copied();
+
+    let (version, keys) = match get_tag_value!(tags, TiffTag::GeoKeyDirectory) {
+        Some(directory) => read_geo_keys(
+            directory,
+            get_tag_value!(tags, TiffTag::GeoDoubleParams)
+                .map(|v| v.as_slice())
+                .unwrap_or_default(),
+            get_tag_value!(tags, TiffTag::GeoAsciiParams)
+                .map(|v| v.as_str())
+                .unwrap_or_default(),
+        )?,
+        None if pixel_scale.is_none() && tiepoints.is_empty() && transformation.is_none() => {
+            return Ok(None)
+        }
+        None => ("".to_string(), vec![]),
+    };
+
+    let mut geotiff = GeoTiff {
+        version,
+        keys,
+        pixel_scale,
+        tiepoints,
+        transformation,
+        geotransform: None,
+        corners: None,
+    };
+
+    let geotransform = match (transformation, pixel_scale, geotiff.tiepoints.first()) {
+        (Some(m), _, _) => Some([m[3], m[0], m[1], m[7], m[4], m[5]]),
+        (None, Some([sx, sy, _]), Some([i, j, _, x, y, _])) => {
+            Some([x - i * sx, sx, 0.0, y + j * sy, 0.0, -sy])
+        }
+        _ => None,
+    };
+
+    // Shift the origin from the center of the first pixel to its corner
+    geotiff.geotransform = geotransform.map(|mut gt| {
+        if geotiff.raster_type().as_deref() == Some("PixelIsPoint") {
+            gt[0] -= 0.5 * (gt[1] + gt[2]);
+            gt[3] -= 0.5 * (gt[4] + gt[5]);
+        }
+        gt
+    });
+
+    if let (Some(width), Some(height)) = (
+        get_tag_value!(tags, TiffTag::ImageWidth),
+        get_tag_value!(tags, TiffTag::ImageLength),
+    ) {
+        let (width, height) = (*width as f64, *height as f64);
+        geotiff.corners = (|| {
+            Some([
+                geotiff.pixel_to_model(0.0, 0.0)?,
+                geotiff.pixel_to_model(width, 0.0)?,
+                geotiff.pixel_to_model(0.0, height)?,
+                geotiff.pixel_to_model(width, height)?,
+            ])
+        })();
+    }
+
+    Ok(Some(geotiff))
+}
+
+impl Tiff {
+    /// The georeferencing of each page, None for pages which aren't georeferenced or whose
+    /// GeoTIFF tags are invalid
+    pub fn geotiff(&self) -> Vec<Option<GeoTiff>> {
+        self.pages()
+            .iter()
+            .map(|tags| read_geotiff(tags).ok().flatten())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{read_geotiff, GeoKeyValue};
+    use crate::tiff::{read_tiff_file, TiffTag};
+
+    #[test]
+    fn test_read_geotiff() {
+        let tags = vec![
+            TiffTag::ImageWidth(100),
+            TiffTag::ImageLength(50),
+            TiffTag::ModelPixelScale(vec![30.0, 30.0, 0.0]),
+            TiffTag::ModelTiepoint(vec![0.0, 0.0, 0.0, 440720.0, 3751320.0, 0.0]),
+            TiffTag::GeoKeyDirectory(vec![
+                1, 1, 0, 5, // Header
+                1024, 0, 1, 1, // Projected
+                1025, 0, 1, 1, // PixelIsArea
+                1026, 34737, 25, 0, // Citation
+                3072, 0, 1, 26711, // NAD27 / UTM zone 11N
+                3077, 34736, 1, 0, // Linear unit size
+            ]),
+            TiffTag::GeoDoubleParams(vec![0.3048]),
+            TiffTag::GeoAsciiParams("UTM Zone 11 N with NAD27|\0".to_string()),
+        ];
+
+        let geotiff = read_geotiff(&tags).unwrap().unwrap();
+        assert_eq!(geotiff.version, "1.1.0");
+        assert_eq!(geotiff.keys.len(), 5);
+        assert_eq!(geotiff.keys[2].name, "GTCitationGeoKey");
+        assert_eq!(geotiff.model_type().as_deref(), Some("Projected"));
+        assert_eq!(geotiff.raster_type().as_deref(), Some("PixelIsArea"));
+        assert_eq!(geotiff.epsg(), Some(26711));
+        assert_eq!(
+            geotiff.citation().as_deref(),
+            Some("UTM Zone 11 N with NAD27")
+        );
+        assert_eq!(geotiff.get(3077), Some(&GeoKeyValue::Double(vec![0.3048])));
+
+        assert_eq!(
+            geotiff.geotransform,
+            Some([440720.0, 30.0, 0.0, 3751320.0, 0.0, -30.0])
+        );
+        assert_eq!(
+            geotiff.corners,
+            Some([
+                (440720.0, 3751320.0),
+                (443720.0, 3751320.0),
+                (440720.0, 3749820.0),
+                (443720.0, 3749820.0),
+            ])
+        );
+
+        let mut transformation = [0.0; 16];
+        transformation[0] = 0.5;
+        transformation[3] = 10.0;
+        transformation[5] = -0.5;
+        transformation[7] = 20.0;
+        let tags = vec![
+            TiffTag::ModelTransformation(transformation),
+            TiffTag::GeoKeyDirectory(vec![1, 1, 0, 1, 1025, 0, 1, 2]),
+        ];
+
+        let geotiff = read_geotiff(&tags).unwrap().unwrap();
+        assert_eq!(
+            geotiff.geotransform,
+            Some([9.75, 0.5, 0.0, 20.25, 0.0, -0.5])
+        );
+        assert_eq!(geotiff.corners, None);
+
+        let tags = vec![TiffTag::GeoKeyDirectory(vec![
+            1, 1, 0, 1, 1026, 34737, 10, 0,
+        ])];
+        assert!(read_geotiff(&tags).is_err());
+    }
+
+    #[test]
+    fn test_no_geotiff() {
+        let data = fs::read("test_images/test.tif").unwrap();
+        let tiff = read_tiff_file(&data).unwrap();
+
+        assert_eq!(tiff.pages().len(), 1);
+        assert_eq!(tiff.geotiff(), vec![None]);
+    }
+}
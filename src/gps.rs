@@ -0,0 +1,167 @@
+use std::fmt;
+
+use crate::{
+    capture_time::CaptureTime,
+    get_tag_value,
+    tiff::{Tiff, TiffTag},
+};
+
+/// A position assembled from the GPS IFD, with everything converted to signed decimal values
+#[derive(Debug, PartialEq, Clone)]
+pub struct GpsPosition {
+    pub latitude: f64,                  // Decimal degrees, negative is south
+    pub longitude: f64,                 // Decimal degrees, negative is west
+    pub altitude: Option<f64>,          // Meters, negative is below sea level
+    pub timestamp: Option<CaptureTime>, // UTC time of the fix
+    pub datum: Option<String>,
+    pub dop: Option<f64>,              // Dilution of precision
+    pub horizontal_error: Option<f64>, // Meters
+}
+
+/// Whether GPSAltitude is above or below sea level
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum GpsAltitudeRef {
+    AboveSeaLevel,
+    BelowSeaLevel,
+    Invalid(u8),
+}
+
+impl From<u8> for GpsAltitudeRef {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => GpsAltitudeRef::AboveSeaLevel,
+            1 => GpsAltitudeRef::BelowSeaLevel,
+            v => GpsAltitudeRef::Invalid(v),
+        }
+    }
+}
+
+impl fmt::Display for GpsAltitudeRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GpsAltitudeRef::AboveSeaLevel => write!(f, "Above sea level"),
+            GpsAltitudeRef::BelowSeaLevel => write!(f, "Below sea level"),
+            GpsAltitudeRef::Invalid(_) => write!(f, "Invalid"),
+        }
+    }
+}
+
+/// Converts a degrees, minutes, seconds coordinate and its reference (N/S/E/W) to signed decimal
+/// degrees. Degrees-only and degrees + decimal minutes encodings work too since the other
+/// components are zero.
+pub fn dms_to_decimal(dms: &[f64; 3], reference: Option<&str>) -> f64 {
+    let [degrees, minutes, seconds] = *dms;
+    let value = degrees + minutes / 60.0 + seconds / 3600.0;
+
+    match reference.map(|r| r.trim()) {
+        Some("S") | Some("W") => -value.abs(),
+        _ => value,
+    }
+}
+
+impl Tiff {
+    /// The GPS position, if the GPS IFD has a valid latitude and longitude
+    pub fn gps_position(&self) -> Option<GpsPosition> {
+        let latitude = dms_to_decimal(
+            get_tag_value!(self.tags, TiffTag::GPSLatitude)?,
+            get_tag_value!(self.tags, TiffTag::GPSLatitudeRef).map(|r| r.as_str()),
+        );
+        let longitude = dms_to_decimal(
+            get_tag_value!(self.tags, TiffTag::GPSLongitude)?,
+            get_tag_value!(self.tags, TiffTag::GPSLongitudeRef).map(|r| r.as_str()),
+        );
+
+        // Catches NaN too, which is what unknown (0/0) rationals become
+        if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+            return None;
+        }
+
+        let altitude = get_tag_value!(self.tags, TiffTag::GPSAltitude)
+            .filter(|a| a.is_finite())
+            .map(
+                |a| match get_tag_value!(self.tags, TiffTag::GPSAltitudeRef) {
+                    Some(GpsAltitudeRef::BelowSeaLevel) => -a.abs(),
+                    _ => *a,
+                },
+            );
+
+        let datum = get_tag_value!(self.tags, TiffTag::GPSMapDatum)
+            .map(|d| d.trim().to_string())
+            .filter(|d| !d.is_empty());
+
+        Some(GpsPosition {
+            latitude,
+            longitude,
+            altitude,
+            timestamp: self.gps_timestamp(),
+            datum,
+            dop: get_tag_value!(self.tags, TiffTag::GPSDop)
+                .copied()
+                .filter(|v| v.is_finite()),
+            horizontal_error: get_tag_value!(self.tags, TiffTag::GPSHPositioningError)
+                .copied()
+                .filter(|v| v.is_finite()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{dms_to_decimal, GpsAltitudeRef};
+    use crate::{
+        jpeg::read_jpeg,
+        tiff::{Tiff, TiffTag},
+    };
+
+    #[test]
+    fn test_dms_to_decimal() {
+        assert_eq!(dms_to_decimal(&[35.0, 30.0, 0.0], Some("N")), 35.5);
+        assert_eq!(dms_to_decimal(&[82.0, 30.0, 36.0], Some("W")), -82.51);
+        assert_eq!(dms_to_decimal(&[12.25, 0.0, 0.0], Some("S")), -12.25);
+        assert_eq!(
+            dms_to_decimal(&[12.0, 15.5, 0.0], Some("E")),
+            12.258333333333333
+        );
+    }
+
+    #[test]
+    fn test_gps_position() {
+        let data = fs::read("test_images/gps.jpeg").unwrap();
+        let exif = read_jpeg(&data).unwrap().exif.unwrap();
+
+        let position = exif.gps_position().unwrap();
+        assert!((position.latitude - 43.467157).abs() < 1e-6);
+        assert!((position.longitude - 11.885395).abs() < 1e-6);
+        assert_eq!(position.altitude, None);
+        assert_eq!(position.datum.as_deref(), Some("WGS-84"));
+        assert_eq!(
+            position.timestamp.unwrap().to_string(),
+            "2008-10-23T14:28:17.24Z"
+        );
+
+        let tiff = Tiff::from_tags(vec![
+            TiffTag::GPSLatitudeRef("S".to_string()),
+            TiffTag::GPSLatitude([33.0, 51.5, 0.0]),
+            TiffTag::GPSLongitudeRef("E".to_string()),
+            TiffTag::GPSLongitude([151.25, 0.0, 0.0]),
+            TiffTag::GPSAltitudeRef(GpsAltitudeRef::BelowSeaLevel),
+            TiffTag::GPSAltitude(12.5),
+            TiffTag::GPSDop(1.5),
+            TiffTag::GPSHPositioningError(4.0),
+        ]);
+
+        let position = tiff.gps_position().unwrap();
+        assert!((position.latitude + 33.858333).abs() < 1e-6);
+        assert_eq!(position.longitude, 151.25);
+        assert_eq!(position.altitude, Some(-12.5));
+        assert_eq!(position.dop, Some(1.5));
+        assert_eq!(position.horizontal_error, Some(4.0));
+        assert_eq!(position.timestamp, None);
+
+        let data = fs::read("test_images/test9.jpeg").unwrap();
+        let exif = read_jpeg(&data).unwrap().exif.unwrap();
+        assert_eq!(exif.gps_position(), None);
+    }
+}
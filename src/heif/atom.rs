@@ -24,6 +24,12 @@ pub enum AtomVariant {
     MetaIinfInfe(atoms::AtomMetaIinfInfe),
     MetaIref(atoms::AtomMetaIref),
     MetaIloc(atoms::AtomMetaIloc),
+    MetaIprp(atoms::AtomMetaIprp),
+    MetaIprpIpco(atoms::AtomMetaIprpIpco),
+    MetaIprpIpma(atoms::AtomMetaIprpIpma),
+    MetaIprpIpcoIrot(atoms::AtomMetaIprpIpcoIrot),
+    MetaIprpIpcoImir(atoms::AtomMetaIprpIpcoImir),
+    MetaIprpIpcoColr(atoms::AtomMetaIprpIpcoColr),
 }
 
 #[macro_export]
@@ -86,6 +92,22 @@ pub fn read_sub_atom(parent: &str, cursor: &mut Cursor<Vec<u8>>) -> AtomVariant
         }
         "meta.iref" => AtomVariant::MetaIref(atoms::AtomMetaIref::read_from(name, size, cursor)),
         "meta.iloc" => AtomVariant::MetaIloc(atoms::AtomMetaIloc::read_from(name, size, cursor)),
+        "meta.iprp" => AtomVariant::MetaIprp(atoms::AtomMetaIprp::read_from(name, size, cursor)),
+        "meta.iprp.ipco" => {
+            AtomVariant::MetaIprpIpco(atoms::AtomMetaIprpIpco::read_from(name, size, cursor))
+        }
+        "meta.iprp.ipma" => {
+            AtomVariant::MetaIprpIpma(atoms::AtomMetaIprpIpma::read_from(name, size, cursor))
+        }
+        "meta.iprp.ipco.irot" => {
+            AtomVariant::MetaIprpIpcoIrot(atoms::AtomMetaIprpIpcoIrot::read_from(name, size, cursor))
+        }
+        "meta.iprp.ipco.imir" => {
+            AtomVariant::MetaIprpIpcoImir(atoms::AtomMetaIprpIpcoImir::read_from(name, size, cursor))
+        }
+        "meta.iprp.ipco.colr" => {
+            AtomVariant::MetaIprpIpcoColr(atoms::AtomMetaIprpIpcoColr::read_from(name, size, cursor))
+        }
         _ => AtomVariant::Unknown(atoms::AtomUnknown::read_from(name, size, cursor)),
     }
 }
@@ -0,0 +1,279 @@
+use std::io::{Cursor, Read};
+
+use crate::{
+    heif::{
+        atom::{read_sub_atom, Atom, AtomVariant},
+        read_version_and_flags,
+    },
+    read_unpack,
+    utils::{read_sized_string, Endianness},
+};
+
+#[derive(Debug)]
+pub struct AtomMetaIprp {
+    pub children: Vec<AtomVariant>,
+}
+
+impl Atom for AtomMetaIprp {
+    fn read_from(_: String, size: u64, cursor: &mut Cursor<Vec<u8>>) -> Self
+    where
+        Self: Sized,
+    {
+        let start_position = cursor.position();
+        let size_minus_already_read = size - 8;
+        let mut children: Vec<AtomVariant> = vec![];
+        while cursor.position() - start_position < size_minus_already_read {
+            children.push(read_sub_atom("meta.iprp", cursor));
+        }
+
+        AtomMetaIprp { children }
+    }
+}
+
+/// Item property container, the properties are referenced by their (1-based) index from ipma
+#[derive(Debug)]
+pub struct AtomMetaIprpIpco {
+    pub properties: Vec<AtomVariant>,
+}
+
+impl Atom for AtomMetaIprpIpco {
+    fn read_from(_: String, size: u64, cursor: &mut Cursor<Vec<u8>>) -> Self
+    where
+        Self: Sized,
+    {
+        let start_position = cursor.position();
+        let size_minus_already_read = size - 8;
+        let mut properties: Vec<AtomVariant> = vec![];
+        while cursor.position() - start_position < size_minus_already_read {
+            properties.push(read_sub_atom("meta.iprp.ipco", cursor));
+        }
+
+        AtomMetaIprpIpco { properties }
+    }
+}
+
+#[derive(Debug)]
+pub struct AtomMetaIprpIpmaAssociation {
+    pub essential: bool,
+    pub property_index: u16, // 1-based, 0 means no property
+}
+
+#[derive(Debug)]
+pub struct AtomMetaIprpIpmaEntry {
+    pub item_id: u32,
+    pub associations: Vec<AtomMetaIprpIpmaAssociation>,
+}
+
+/// Item property associations, which map items to the properties in ipco which apply to them
+#[derive(Debug)]
+pub struct AtomMetaIprpIpma {
+    pub version: u8,
+    pub flags: u32,
+    pub entries: Vec<AtomMetaIprpIpmaEntry>,
+}
+
+impl Atom for AtomMetaIprpIpma {
+    fn read_from(_: String, _: u64, cursor: &mut Cursor<Vec<u8>>) -> Self
+    where
+        Self: Sized,
+    {
+        let (version, flags) = read_version_and_flags(cursor);
+
+        let entry_count = read_unpack!(cursor, u32, Endianness::Big);
+
+        let mut entries: Vec<AtomMetaIprpIpmaEntry> = vec![];
+        for _ in 0..entry_count {
+            let item_id: u32 = match version {
+                0 => read_unpack!(cursor, u16, Endianness::Big) as u32,
+                _ => read_unpack!(cursor, u32, Endianness::Big),
+            };
+
+            let association_count = read_unpack!(cursor, u8, Endianness::Big);
+            let associations: Vec<AtomMetaIprpIpmaAssociation> = (0..association_count)
+                .map(|_| match flags & 1 {
+                    1 => {
+                        let value = read_unpack!(cursor, u16, Endianness::Big);
+                        AtomMetaIprpIpmaAssociation {
+                            essential: value & 0x8000 != 0,
+                            property_index: value & 0x7FFF,
+                        }
+                    }
+                    _ => {
+                        let value = read_unpack!(cursor, u8, Endianness::Big);
+                        AtomMetaIprpIpmaAssociation {
+                            essential: value & 0x80 != 0,
+                            property_index: (value & 0x7F) as u16,
+                        }
+                    }
+                })
+                .collect();
+
+            entries.push(AtomMetaIprpIpmaEntry {
+                item_id,
+                associations,
+            });
+        }
+
+        AtomMetaIprpIpma {
+            version,
+            flags,
+            entries,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum AtomMetaIprpIpcoColrVariant {
+    Nclx {
+        colour_primaries: u16,
+        transfer_characteristics: u16,
+        matrix_coefficients: u16,
+        full_range: bool,
+    },
+    IccProfile(Vec<u8>), // Either a restricted ('rICC') or unrestricted ('prof') ICC profile
+    Unknown(Vec<u8>),
+    Invalid(String), // The atom's size doesn't fit its header or the rest of the file
+}
+
+/// Colour information property
+#[derive(Debug)]
+pub struct AtomMetaIprpIpcoColr {
+    pub colour_type: String,
+    pub value: AtomMetaIprpIpcoColrVariant,
+}
+
+/// Reads the rest of a colr atom, whose size includes the 8 byte header and the colour type
+fn read_colr_data(size: u64, cursor: &mut Cursor<Vec<u8>>) -> Result<Vec<u8>, String> {
+    let length = size
+        .checked_sub(12)
+        .ok_or_else(|| format!("colr atom is too small ({} bytes)", size))?;
+
+    let remaining = (cursor.get_ref().len() as u64).saturating_sub(cursor.position());
+    if length > remaining {
+        return Err(format!(
+            "colr atom needs {} bytes but only {} are left",
+            length, remaining
+        ));
+    }
+
+    let mut data = vec![0_u8; length as usize];
+    cursor.read_exact(&mut data).map_err(|e| e.to_string())?;
+
+    Ok(data)
+}
+
+impl Atom for AtomMetaIprpIpcoColr {
+    fn read_from(_: String, size: u64, cursor: &mut Cursor<Vec<u8>>) -> Self
+    where
+        Self: Sized,
+    {
+        let start_position = cursor.position();
+        let colour_type = read_sized_string(cursor, 4);
+
+        let value = match colour_type.as_str() {
+            "nclx" => {
+                let colour_primaries = read_unpack!(cursor, u16, Endianness::Big);
+                let transfer_characteristics = read_unpack!(cursor, u16, Endianness::Big);
+                let matrix_coefficients = read_unpack!(cursor, u16, Endianness::Big);
+                let full_range = read_unpack!(cursor, u8, Endianness::Big) & 0x80 != 0;
+
+                AtomMetaIprpIpcoColrVariant::Nclx {
+                    colour_primaries,
+                    transfer_characteristics,
+                    matrix_coefficients,
+                    full_range,
+                }
+            }
+            _ => match read_colr_data(size, cursor) {
+                Ok(data) => match colour_type.as_str() {
+                    "prof" | "rICC" => AtomMetaIprpIpcoColrVariant::IccProfile(data),
+                    _ => AtomMetaIprpIpcoColrVariant::Unknown(data),
+                },
+                Err(e) => {
+                    // Leave the cursor at the end of the atom so the next property lines up,
+                    // without going back before the header or past the end of the data
+                    let end_position = (start_position + size.saturating_sub(8))
+                        .min(cursor.get_ref().len() as u64);
+                    cursor.set_position(end_position);
+
+                    AtomMetaIprpIpcoColrVariant::Invalid(e)
+                }
+            },
+        };
+
+        AtomMetaIprpIpcoColr { colour_type, value }
+    }
+}
+
+/// Image rotation property
+#[derive(Debug)]
+pub struct AtomMetaIprpIpcoIrot {
+    pub angle: u8, // Anti-clockwise rotation in units of 90 degrees
+}
+
+impl Atom for AtomMetaIprpIpcoIrot {
+    fn read_from(_: String, _: u64, cursor: &mut Cursor<Vec<u8>>) -> Self
+    where
+        Self: Sized,
+    {
+        let angle = read_unpack!(cursor, u8, Endianness::Big) & 0b11;
+
+        AtomMetaIprpIpcoIrot { angle }
+    }
+}
+
+/// Image mirroring property
+#[derive(Debug)]
+pub struct AtomMetaIprpIpcoImir {
+    pub axis: u8, // 0 = vertical axis (left-right flip), 1 = horizontal axis (top-bottom flip)
+}
+
+impl Atom for AtomMetaIprpIpcoImir {
+    fn read_from(_: String, _: u64, cursor: &mut Cursor<Vec<u8>>) -> Self
+    where
+        Self: Sized,
+    {
+        let axis = read_unpack!(cursor, u8, Endianness::Big) & 1;
+
+        AtomMetaIprpIpcoImir { axis }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::{AtomMetaIprpIpcoColr, AtomMetaIprpIpcoColrVariant};
+    use crate::heif::atom::Atom;
+
+    #[test]
+    fn test_colr_sizes() {
+        let mut cursor = Cursor::new(b"prof\x01\x02\x03".to_vec());
+        let colr = AtomMetaIprpIpcoColr::read_from("colr".to_string(), 15, &mut cursor);
+        assert!(matches!(colr.value, AtomMetaIprpIpcoColrVariant::IccProfile(v) if v == [1, 2, 3]));
+
+        let mut cursor = Cursor::new(b"prof\x01\x02\x03".to_vec());
+        let colr = AtomMetaIprpIpcoColr::read_from("colr".to_string(), 10, &mut cursor);
+        assert!(matches!(
+            colr.value,
+            AtomMetaIprpIpcoColrVariant::Invalid(_)
+        ));
+        assert_eq!(cursor.position(), 2);
+
+        let mut cursor = Cursor::new(b"prof\x01\x02\x03".to_vec());
+        let colr = AtomMetaIprpIpcoColr::read_from("colr".to_string(), 4, &mut cursor);
+        assert!(matches!(
+            colr.value,
+            AtomMetaIprpIpcoColrVariant::Invalid(_)
+        ));
+        assert_eq!(cursor.position(), 0);
+
+        let mut cursor = Cursor::new(b"prof\x01\x02\x03".to_vec());
+        let colr = AtomMetaIprpIpcoColr::read_from("colr".to_string(), 1000, &mut cursor);
+        assert!(matches!(
+            colr.value,
+            AtomMetaIprpIpcoColrVariant::Invalid(_)
+        ));
+        assert_eq!(cursor.position(), 7);
+    }
+}
@@ -1,8 +1,13 @@
 mod ftyp;
+mod iprp;
 mod meta;
 mod unknown;
 
 pub use ftyp::AtomFtyp;
+pub use iprp::{
+    AtomMetaIprp, AtomMetaIprpIpco, AtomMetaIprpIpcoColr, AtomMetaIprpIpcoColrVariant,
+    AtomMetaIprpIpcoImir, AtomMetaIprpIpcoIrot, AtomMetaIprpIpma,
+};
 pub use meta::{
     AtomMeta, AtomMetaDinf, AtomMetaDinfDref, AtomMetaDinfDrefEntry, AtomMetaHdlr, AtomMetaIinf,
     AtomMetaIinfInfe, AtomMetaIinfInfeVariant, AtomMetaIloc, AtomMetaIlocItem, AtomMetaIref,
@@ -7,7 +7,12 @@ use std::io::{Cursor, Read, Seek, SeekFrom};
 
 use crate::{
     find_atom_value,
-    heif::{atom::read_top_atom, atoms::AtomMetaIinfInfeVariant},
+    heif::{
+        atom::read_top_atom,
+        atoms::{AtomMetaIinfInfeVariant, AtomMetaIprpIpcoColr, AtomMetaIprpIpcoColrVariant},
+    },
+    icc::{read_icc_profile, IccProfile},
+    orientation::Orientation,
     tiff::{self, read_exif_section},
 };
 
@@ -18,6 +23,7 @@ pub struct Heif {
     pub atoms: Vec<AtomVariant>,
     pub exif: Option<tiff::Tiff>,
     pub xmp: Option<String>,
+    pub icc_profile: Option<IccProfile>,
 }
 
 fn get_iloc_item_for_item_type<'a, 'b>(
@@ -46,6 +52,62 @@ fn get_iloc_item_for_item_type<'a, 'b>(
     iloc.items.iter().find(|item| item.item_id == *exif_item_id)
 }
 
+/// Get the properties (from ipco) associated with an item (via ipma), in the order they apply
+fn get_item_properties(atoms: &[AtomVariant], item_id: u32) -> Vec<&AtomVariant> {
+    let properties = (|| {
+        let meta = find_atom_value!(atoms, AtomVariant::Meta)?;
+        let iprp = find_atom_value!(meta.children, AtomVariant::MetaIprp)?;
+        let ipco = find_atom_value!(iprp.children, AtomVariant::MetaIprpIpco)?;
+
+        let associations = iprp
+            .children
+            .iter()
+            .filter_map(|atom| match atom {
+                AtomVariant::MetaIprpIpma(ipma) => Some(ipma),
+                _ => None,
+            })
+            .flat_map(|ipma| ipma.entries.iter())
+            .filter(|entry| entry.item_id == item_id)
+            .flat_map(|entry| entry.associations.iter());
+
+        Some(
+            associations
+                .filter_map(|a| ipco.properties.get((a.property_index as usize).checked_sub(1)?))
+                .collect(),
+        )
+    })();
+
+    properties.unwrap_or_default()
+}
+
+fn get_icc_profile(atoms: &[AtomVariant]) -> Option<IccProfile> {
+    let meta = find_atom_value!(atoms, AtomVariant::Meta)?;
+    let pitm = find_atom_value!(meta.children, AtomVariant::MetaPitm)?;
+
+    let find_profile = |item_id: u32| {
+        get_item_properties(atoms, item_id).into_iter().find_map(|p| match p {
+            AtomVariant::MetaIprpIpcoColr(AtomMetaIprpIpcoColr {
+                value: AtomMetaIprpIpcoColrVariant::IccProfile(data),
+                ..
+            }) => Some(data),
+            _ => None,
+        })
+    };
+
+    // Derived images (e.g. grids) usually only carry the profile on the images they're made from
+    let data = find_profile(pitm.item_id).or_else(|| {
+        let iref = find_atom_value!(meta.children, AtomVariant::MetaIref)?;
+        let dimg = iref
+            .entries
+            .iter()
+            .find(|r| r.name == "dimg" && r.from_item_id == pitm.item_id)?;
+
+        find_profile(*dimg.references.first()?)
+    })?;
+
+    read_icc_profile(data.clone()).ok()
+}
+
 fn get_exif(atoms: &Vec<AtomVariant>, cursor: &mut Cursor<Vec<u8>>) -> Option<tiff::Tiff> {
     let exif_iloc_item = get_iloc_item_for_item_type(atoms, "Exif")?;
 
@@ -75,6 +137,39 @@ fn get_xmp(atoms: &Vec<AtomVariant>, cursor: &mut Cursor<Vec<u8>>) -> Option<Str
     Some(String::from_utf8_lossy(&data).to_string())
 }
 
+impl Heif {
+    /// The ID of the primary item (usually the main image), from pitm
+    pub fn primary_item_id(&self) -> Option<u32> {
+        let meta = find_atom_value!(self.atoms, AtomVariant::Meta)?;
+        let pitm = find_atom_value!(meta.children, AtomVariant::MetaPitm)?;
+
+        Some(pitm.item_id)
+    }
+
+    /// How the primary image should be transformed for display.
+    ///
+    /// This is based on the primary item's irot/imir properties, since the orientation in the Exif
+    /// data is informational only for HEIF files and must not be applied on top of them.
+    pub fn orientation(&self) -> Orientation {
+        let properties = match self.primary_item_id() {
+            Some(item_id) => get_item_properties(&self.atoms, item_id),
+            None => vec![],
+        };
+
+        let irot_angle = properties.iter().find_map(|p| match p {
+            AtomVariant::MetaIprpIpcoIrot(irot) => Some(irot.angle),
+            _ => None,
+        });
+
+        let imir_axis = properties.iter().find_map(|p| match p {
+            AtomVariant::MetaIprpIpcoImir(imir) => Some(imir.axis),
+            _ => None,
+        });
+
+        Orientation::from_heif(irot_angle, imir_axis)
+    }
+}
+
 pub fn read_heif(data: Vec<u8>) -> Heif {
     let file_size = data.len() as u64;
     let mut cursor = Cursor::new(data);
@@ -86,15 +181,24 @@ pub fn read_heif(data: Vec<u8>) -> Heif {
 
     let exif = get_exif(&atoms, &mut cursor);
     let xmp = get_xmp(&atoms, &mut cursor);
+    let icc_profile = get_icc_profile(&atoms);
 
-    Heif { atoms, exif, xmp }
+    Heif {
+        atoms,
+        exif,
+        xmp,
+        icc_profile,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::fs;
 
-    use crate::{find_atom_value, get_tag_value, heif::AtomVariant, tiff::TiffTag, utils::Endianness};
+    use crate::{
+        find_atom_value, get_tag_value, heif::AtomVariant, orientation::Orientation, tiff::TiffTag,
+        utils::Endianness,
+    };
 
     use super::read_heif;
 
@@ -127,6 +231,9 @@ mod tests {
 
         let heif = read_heif(data);
 
+        assert_eq!(heif.primary_item_id(), Some(49));
+        assert_eq!(heif.orientation(), Orientation::Normal);
+
         let exif = heif.exif.unwrap();
         assert_eq!(get_tag_value!(exif.tags, TiffTag::Orientation).unwrap(), &1);
         assert_eq!(get_tag_value!(exif.tags, TiffTag::XResolution).unwrap(), &72.0);
@@ -0,0 +1,240 @@
+// https://www.color.org/specification/ICC.1-2022-05.pdf
+// https://www.color.org/ICC_Minor_Revision_for_Web.pdf (v2, for the 'desc' tag type)
+
+#[derive(Debug)]
+pub struct IccError(pub String);
+
+/// The fixed 128 byte header at the start of every ICC profile
+#[derive(Debug, PartialEq, Clone)]
+pub struct IccHeader {
+    pub size: u32,
+    pub preferred_cmm: String,
+    pub version: String, // major.minor.bugfix, e.g. "4.3.0"
+    pub device_class: String,
+    pub color_space: String,
+    pub pcs: String, // Profile connection space, XYZ or Lab
+    pub platform: String,
+    pub manufacturer: String,
+    pub model: u32,
+    pub rendering_intent: String,
+    pub creator: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct IccProfile {
+    pub header: IccHeader,
+    pub description: Option<String>, // e.g. "sRGB IEC61966-2.1" or "Display P3"
+    pub data: Vec<u8>,               // The complete, raw profile
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, IccError> {
+    match data.get(offset..offset + 4) {
+        Some(bytes) => Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])),
+        None => Err(IccError(format!(
+            "Expected 4 bytes at offset {} but the profile is only {} bytes",
+            offset,
+            data.len()
+        ))),
+    }
+}
+
+fn read_signature(data: &[u8], offset: usize) -> Result<String, IccError> {
+    let bytes = read_u32(data, offset)?.to_be_bytes();
+
+    Ok(String::from_utf8_lossy(&bytes)
+        .trim_end_matches([' ', '\0'])
+        .to_string())
+}
+
+fn read_header(data: &[u8]) -> Result<IccHeader, IccError> {
+    if data.len() < 128 {
+        return Err(IccError(format!(
+            "Expected at least 128 bytes for the header (got {})",
+            data.len()
+        )));
+    }
+
+    if data[36..40] != *b"acsp" {
+        return Err(IccError(format!(
+            "Expected 'acsp' but got {:?} instead",
+            String::from_utf8_lossy(&data[36..40])
+        )));
+    }
+
+    let device_class = match &data[12..16] {
+        b"scnr" => "Input device",
+        b"mntr" => "Display device",
+        b"prtr" => "Output device",
+        b"link" => "Device link",
+        b"spac" => "Color space",
+        b"abst" => "Abstract",
+        b"nmcl" => "Named color",
+        _ => "Invalid",
+    };
+
+    let rendering_intent = match read_u32(data, 64)? & 0xFFFF {
+        0 => "Perceptual",
+        1 => "Media-relative colorimetric",
+        2 => "Saturation",
+        3 => "ICC-absolute colorimetric",
+        _ => "Invalid",
+    };
+
+    Ok(IccHeader {
+        size: read_u32(data, 0)?,
+        preferred_cmm: read_signature(data, 4)?,
+        version: format!("{}.{}.{}", data[8], data[9] >> 4, data[9] & 0x0F),
+        device_class: device_class.to_string(),
+        color_space: read_signature(data, 16)?,
+        pcs: read_signature(data, 20)?,
+        platform: read_signature(data, 40)?,
+        manufacturer: read_signature(data, 48)?,
+        model: read_u32(data, 52)?,
+        rendering_intent: rendering_intent.to_string(),
+        creator: read_signature(data, 80)?,
+    })
+}
+
+/// Decodes a text tag, which depending on the profile version is a textDescriptionType (v2),
+/// multiLocalizedUnicodeType (v4) or textType
+fn read_text_tag(data: &[u8]) -> Option<String> {
+    let text = match data.get(0..4)? {
+        b"desc" => {
+            let length = read_u32(data, 8).ok()? as usize;
+            String::from_utf8_lossy(data.get(12..12 + length)?).to_string()
+        }
+        b"mluc" => {
+            let record_count = read_u32(data, 8).ok()? as usize;
+            let record_size = read_u32(data, 12).ok()? as usize;
+
+            // Records are at least 12 bytes, so don't trust a count the data can't hold
+            let record_count = record_count.min(data.len() / record_size.max(12));
+
+            let records: Vec<(&[u8], usize, usize)> = (0..record_count)
+                .map_while(|i| {
+                    let record = data.get(16 + i * record_size..16 + i * record_size + 12)?;
+                    let length = read_u32(record, 4).ok()? as usize;
+                    let offset = read_u32(record, 8).ok()? as usize;
+
+                    Some((&record[0..4], offset, length))
+                })
+                .collect();
+
+            // Prefer English, otherwise just use the first translation
+            let (_, offset, length) = records
+                .iter()
+                .find(|(locale, _, _)| *locale == b"enUS")
+                .or(records.first())?;
+
+            let code_units: Vec<u16> = data
+                .get(*offset..offset.checked_add(*length)?)?
+                .chunks_exact(2)
+                .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                .collect();
+
+            String::from_utf16_lossy(&code_units)
+        }
+        b"text" => String::from_utf8_lossy(data.get(8..)?).to_string(),
+        _ => return None,
+    };
+
+    Some(text.trim_end_matches('\0').to_string())
+}
+
+impl IccProfile {
+    /// The raw data of a tag (including its type signature), e.g. `tag_data(b"desc")`
+    pub fn tag_data(&self, signature: &[u8; 4]) -> Option<&[u8]> {
+        let tag_count = read_u32(&self.data, 128).ok()? as usize;
+        let tag_count = tag_count.min(self.data.len().saturating_sub(132) / 12);
+
+        (0..tag_count).find_map(|i| {
+            let entry = self.data.get(132 + i * 12..144 + i * 12)?;
+            if entry[0..4] != *signature {
+                return None;
+            }
+
+            let offset = read_u32(entry, 4).ok()? as usize;
+            let length = read_u32(entry, 8).ok()? as usize;
+
+            self.data.get(offset..offset.checked_add(length)?)
+        })
+    }
+}
+
+pub fn read_icc_profile(data: Vec<u8>) -> Result<IccProfile, IccError> {
+    let header = read_header(&data)?;
+
+    let mut profile = IccProfile {
+        header,
+        description: None,
+        data,
+    };
+
+    profile.description = profile.tag_data(b"desc").and_then(read_text_tag);
+
+    Ok(profile)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{read_icc_profile, read_text_tag};
+    use crate::{heif::read_heif, jpeg::read_jpeg};
+
+    #[test]
+    fn test_read_icc_profiles() {
+        let data = fs::read("test_images/no_exif_only_xmp.jpeg").unwrap();
+        let jpeg_profile = read_jpeg(&data).unwrap().icc_profile.unwrap();
+
+        let data = fs::read("test_images/image1.heic").unwrap();
+        let heif_profile = read_heif(data).icc_profile.unwrap();
+
+        for profile in [jpeg_profile, heif_profile] {
+            assert_eq!(profile.data.len(), 3144);
+            assert_eq!(profile.header.size, 3144);
+            assert_eq!(profile.header.preferred_cmm, "Lino");
+            assert_eq!(profile.header.version, "2.1.0");
+            assert_eq!(profile.header.device_class, "Display device");
+            assert_eq!(profile.header.color_space, "RGB");
+            assert_eq!(profile.header.pcs, "XYZ");
+            assert_eq!(profile.header.rendering_intent, "Perceptual");
+            assert_eq!(profile.description.as_deref(), Some("sRGB IEC61966-2.1"));
+        }
+    }
+
+    #[test]
+    fn test_mluc_description() {
+        let mut mluc = b"mluc\0\0\0\0\0\0\0\x01\0\0\0\x0CenUS\0\0\0\x14\0\0\0\x1C".to_vec();
+        mluc.extend("Display P3".encode_utf16().flat_map(|c| c.to_be_bytes()));
+
+        let mut data = vec![0_u8; 128];
+        data[8] = 4;
+        data[12..16].copy_from_slice(b"mntr");
+        data[16..20].copy_from_slice(b"RGB ");
+        data[20..24].copy_from_slice(b"XYZ ");
+        data[36..40].copy_from_slice(b"acsp");
+        data.extend(1_u32.to_be_bytes());
+        data.extend(b"desc");
+        data.extend(144_u32.to_be_bytes());
+        data.extend((mluc.len() as u32).to_be_bytes());
+        data.extend(mluc);
+
+        let profile = read_icc_profile(data).unwrap();
+        assert_eq!(profile.header.version, "4.0.0");
+        assert_eq!(profile.description.as_deref(), Some("Display P3"));
+
+        assert!(read_icc_profile(b"too short".to_vec()).is_err());
+
+        // Absurd tag and record counts are limited to what the data can hold
+        let mut profile = read_icc_profile(profile.data).unwrap();
+        profile.data[128..132].copy_from_slice(&u32::MAX.to_be_bytes());
+        profile.data[152..156].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert!(profile.tag_data(b"desc").is_some());
+        assert_eq!(profile.tag_data(b"cprt"), None);
+        assert_eq!(
+            profile.tag_data(b"desc").and_then(read_text_tag).as_deref(),
+            Some("Display P3")
+        );
+    }
+}
@@ -0,0 +1,594 @@
+use crate::{
+    decompress::{lzw_decode, unpack_bits, zlib_decode},
+    get_tag_value,
+    tiff::{Tiff, TiffError, TiffTag},
+    unpack,
+    utils::Endianness,
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Samples {
+    U8(Vec<u8>),
+    U16(Vec<u16>),
+}
+
+/// The pixels of a page, as interleaved samples row by row starting at the top left
+#[derive(Debug, PartialEq, Clone)]
+pub struct DecodedImage {
+    pub width: u32,
+    pub height: u32,
+    /// Samples per pixel in `samples`. Palette images are expanded to RGB and 1-bit images are
+    /// expanded to 8 bits, so this can differ from the SamplesPerPixel tag.
+    pub channels: u16,
+    pub samples: Samples,
+}
+
+/// How a page's image data is split up, either into strips (which span the whole width) or into
+/// tiles
+struct Segments<'a> {
+    width: u32,
+    height: u32,
+    across: u32,
+    down: u32,
+    offsets: &'a [u32],
+    byte_counts: &'a [u32],
+}
+
+fn get_segments(tags: &[TiffTag], width: u32, height: u32) -> Result<Segments<'_>, TiffError> {
+    let tile_width = get_tag_value!(tags, TiffTag::TileWidth);
+    let tile_length = get_tag_value!(tags, TiffTag::TileLength);
+    let tile_offsets = get_tag_value!(tags, TiffTag::TileOffsets);
+    let tile_byte_counts = get_tag_value!(tags, TiffTag::TileByteCounts);
+
+    let empty = || TiffError("Strips and tiles can't be empty".to_string());
+
+    let segments = match (tile_width, tile_length, tile_offsets, tile_byte_counts) {
+        (Some(0), ..) | (_, Some(0), ..) => return Err(empty()),
+        (Some(tile_width), Some(tile_length), Some(offsets), Some(byte_counts)) => Segments {
+            width: *tile_width,
+            height: *tile_length,
+            across: width.div_ceil(*tile_width),
+            down: height.div_ceil(*tile_length),
+            offsets,
+            byte_counts,
+        },
+        _ => {
+            let rows_per_strip = get_tag_value!(tags, TiffTag::RowsPerStrip)
+                .copied()
+                .unwrap_or(height)
+                .min(height);
+            if rows_per_strip == 0 {
+                return Err(empty());
+            }
+
+            Segments {
+                width,
+                height: rows_per_strip,
+                across: 1,
+                down: height.div_ceil(rows_per_strip),
+                offsets: get_tag_value!(tags, TiffTag::StripOffsets)
+                    .ok_or(TiffError("Missing StripOffsets".to_string()))?,
+                byte_counts: get_tag_value!(tags, TiffTag::StripByteCounts)
+                    .ok_or(TiffError("Missing StripByteCounts".to_string()))?,
+            }
+        }
+    };
+
+    if segments.width == 0 {
+        return Err(empty());
+    }
+
+    let count = (segments.across as usize)
+        .checked_mul(segments.down as usize)
+        .ok_or(TiffError("Too many strips/tiles".to_string()))?;
+    if segments.offsets.len() < count || segments.byte_counts.len() < count {
+        return Err(TiffError(format!(
+            "Expected {} strips/tiles but only {} offsets and {} byte counts are given",
+            count,
+            segments.offsets.len(),
+            segments.byte_counts.len()
+        )));
+    }
+
+    Ok(segments)
+}
+
+fn decompress_segment(data: &[u8], compression: &str) -> Result<Vec<u8>, TiffError> {
+    let result = match compression {
+        "No compression" => Ok(data.to_vec()),
+        "PackBits" => unpack_bits(data),
+        "LZW" => lzw_decode(data),
+        "Deflate" | "Deflate (PKZIP)" => zlib_decode(data),
+        _ => {
+            return Err(TiffError(format!(
+                "Decoding {} compressed image data isn't supported",
+                compression
+            )))
+        }
+    };
+
+    result.map_err(|e| TiffError(e.0))
+}
+
+/// Undoes horizontal differencing (Predictor = 2), where each sample is stored as the difference
+/// to the same sample of the previous pixel
+fn undo_horizontal_predictor(
+    row: &mut [u8],
+    bits_per_sample: u16,
+    samples_per_pixel: usize,
+    endianness: &Endianness,
+) -> Result<(), TiffError> {
+    match bits_per_sample {
+        8 => {
+            for i in samples_per_pixel..row.len() {
+                row[i] = row[i].wrapping_add(row[i - samples_per_pixel]);
+            }
+        }
+        16 => {
+            let stride = samples_per_pixel * 2;
+            for i in (stride..row.len() - 1).step_by(2) {
+                let previous = unpack!([row[i - stride], row[i - stride + 1]], u16, endianness);
+                let value = unpack!([row[i], row[i + 1]], u16, endianness).wrapping_add(previous);
+
+                let bytes = match endianness {
+                    Endianness::Little => value.to_le_bytes(),
+                    Endianness::Big => value.to_be_bytes(),
+                };
+                row[i..i + 2].copy_from_slice(&bytes);
+            }
+        }
+        _ => {
+            return Err(TiffError(format!(
+                "The horizontal predictor isn't supported for {} bits per sample",
+                bits_per_sample
+            )))
+        }
+    }
+
+    Ok(())
+}
+
+/// Unpacks a row of samples, which for 1 bit samples are packed MSB first
+fn unpack_row(row: &[u8], count: usize, bits_per_sample: u16, endianness: &Endianness) -> Vec<u16> {
+    match bits_per_sample {
+        1 => (0..count)
+            .map(|i| ((row[i / 8] >> (7 - i % 8)) & 1) as u16)
+            .collect(),
+        8 => row[..count].iter().map(|v| *v as u16).collect(),
+        _ => row[..count * 2]
+            .chunks_exact(2)
+            .map(|c| unpack!([c[0], c[1]], u16, endianness))
+            .collect(),
+    }
+}
+
+impl Tiff {
+    /// Decodes the strips or tiles of a page into interleaved samples. `data` must be the TIFF
+    /// the tags were read from, since the strip and tile offsets are relative to its start.
+    ///
+    /// Supports uncompressed, PackBits, LZW and Deflate data (optionally with the horizontal
+    /// predictor) with 1, 8 or 16 bits per sample, for bilevel, grayscale, RGB and palette images.
+    pub fn decode_page(&self, data: &[u8], page: usize) -> Result<DecodedImage, TiffError> {
+        let tags = self
+            .pages()
+            .get(page)
+            .ok_or(TiffError(format!("There is no page {}", page)))?;
+        let endianness = &self.endianness;
+
+        let width = *get_tag_value!(tags, TiffTag::ImageWidth)
+            .ok_or(TiffError("Missing ImageWidth".to_string()))?;
+        let height = *get_tag_value!(tags, TiffTag::ImageLength)
+            .ok_or(TiffError("Missing ImageLength".to_string()))?;
+        let photometric_interpretation = *get_tag_value!(tags, TiffTag::PhotometricInterpretation)
+            .ok_or(TiffError("Missing PhotometricInterpretation".to_string()))?;
+        let samples_per_pixel = get_tag_value!(tags, TiffTag::SamplesPerPixel)
+            .copied()
+            .unwrap_or(1) as usize;
+        let compression = get_tag_value!(tags, TiffTag::Compression)
+            .map(|c| c.as_str())
+            .unwrap_or("No compression");
+        let predictor = get_tag_value!(tags, TiffTag::Predictor)
+            .copied()
+            .unwrap_or(1);
+
+        let bits_per_sample = get_tag_value!(tags, TiffTag::BitsPerSample)
+            .cloned()
+            .unwrap_or(vec![1]);
+        if bits_per_sample.iter().any(|b| *b != bits_per_sample[0])
+            || ![1, 8, 16].contains(&bits_per_sample[0])
+        {
+            return Err(TiffError(format!(
+                "Only 1, 8 or 16 bits per sample are supported (got {:?})",
+                bits_per_sample
+            )));
+        }
+        let bits_per_sample = bits_per_sample[0];
+
+        if get_tag_value!(tags, TiffTag::PlanarConfiguration)
+            .copied()
+            .unwrap_or(1)
+            != 1
+        {
+            return Err(TiffError(
+                "Only chunky (interleaved) planar configurations are supported".to_string(),
+            ));
+        }
+
+        if let Some(formats) = get_tag_value!(tags, TiffTag::SampleFormat) {
+            if formats.iter().any(|f| *f != 1) {
+                return Err(TiffError(format!(
+                    "Only unsigned integer samples are supported (got {:?})",
+                    formats
+                )));
+            }
+        }
+
+        let row_bytes = |pixels: u32| {
+            (pixels as usize * samples_per_pixel * bits_per_sample as usize).div_ceil(8)
+        };
+
+        if width == 0 || height == 0 || samples_per_pixel == 0 {
+            return Err(TiffError(format!(
+                "The image is empty ({}x{} with {} samples per pixel)",
+                width, height, samples_per_pixel
+            )));
+        }
+
+        // Decode every strip/tile first, so the image buffer is only allocated once the data is
+        // known to cover it
+        let segments = get_segments(tags, width, height)?;
+        let image_row_bytes = row_bytes(width);
+        let segment_row_bytes = row_bytes(segments.width);
+
+        let mut decoded_segments: Vec<Vec<u8>> = vec![];
+        for down in 0..segments.down {
+            for _ in 0..segments.across {
+                let i = decoded_segments.len();
+                let (offset, byte_count) = (
+                    segments.offsets[i] as usize,
+                    segments.byte_counts[i] as usize,
+                );
+
+                let mut segment_data = data
+                    .get(offset..offset.saturating_add(byte_count))
+                    .ok_or(TiffError(format!(
+                        "Strip/tile {} ({} bytes at offset {}) is outside of the data",
+                        i, byte_count, offset
+                    )))?
+                    .to_vec();
+
+                // FillOrder = 2 means the bits of each byte are stored in reverse order
+                if get_tag_value!(tags, TiffTag::FillOrder) == Some(&2) {
+                    segment_data.iter_mut().for_each(|b| *b = b.reverse_bits());
+                }
+
+                let segment = decompress_segment(&segment_data, compression)?;
+
+                // Strips don't extend past the image, but tiles always have the full size
+                let rows = match segments.across {
+                    1 => segments.height.min(height - down * segments.height),
+                    _ => segments.height,
+                } as usize;
+
+                let needed = rows
+                    .checked_mul(segment_row_bytes)
+                    .ok_or(TiffError(format!("Strip/tile {} is too large", i)))?;
+                if segment.len() < needed {
+                    return Err(TiffError(format!(
+                        "Strip/tile {} has {} bytes but {} are needed",
+                        i,
+                        segment.len(),
+                        needed
+                    )));
+                }
+
+                decoded_segments.push(segment);
+            }
+        }
+
+        let image_size = image_row_bytes
+            .checked_mul(height as usize)
+            .ok_or(TiffError(format!(
+                "The image is too large ({}x{})",
+                width, height
+            )))?;
+        let mut image = vec![0_u8; image_size];
+
+        for (i, mut segment) in decoded_segments.into_iter().enumerate() {
+            let (down, across) = (i as u32 / segments.across, i as u32 % segments.across);
+            let first_row = down * segments.height;
+
+            for (row_index, row) in segment
+                .chunks_exact_mut(segment_row_bytes)
+                .take(segments.height as usize)
+                .enumerate()
+            {
+                match predictor {
+                    1 => {}
+                    2 => undo_horizontal_predictor(
+                        row,
+                        bits_per_sample,
+                        samples_per_pixel,
+                        endianness,
+                    )?,
+                    _ => {
+                        return Err(TiffError(format!(
+                            "Predictor {} isn't supported",
+                            predictor
+                        )))
+                    }
+                }
+
+                let image_row = first_row as usize + row_index;
+                if image_row >= height as usize {
+                    break;
+                }
+
+                // Tiles on the right edge can extend past the image
+                let first_column = across * segments.width;
+                let start = image_row * image_row_bytes + row_bytes(first_column);
+                let length = row_bytes((width - first_column).min(segments.width));
+                image[start..start + length].copy_from_slice(&row[..length]);
+            }
+        }
+
+        let values: Vec<u16> = image
+            .chunks_exact(image_row_bytes)
+            .flat_map(|row| {
+                unpack_row(
+                    row,
+                    width as usize * samples_per_pixel,
+                    bits_per_sample,
+                    endianness,
+                )
+            })
+            .collect();
+
+        let (channels, samples) = match (photometric_interpretation, bits_per_sample) {
+            // WhiteIsZero and BlackIsZero
+            (0 | 1, _) => {
+                let max = (1_u32 << bits_per_sample) - 1;
+                let values = values
+                    .into_iter()
+                    .map(|v| match photometric_interpretation {
+                        0 => max as u16 - v,
+                        _ => v,
+                    });
+
+                let samples = match bits_per_sample {
+                    1 => Samples::U8(values.map(|v| v as u8 * 255).collect()),
+                    8 => Samples::U8(values.map(|v| v as u8).collect()),
+                    _ => Samples::U16(values.collect()),
+                };
+
+                (samples_per_pixel as u16, samples)
+            }
+            (2, 8) if samples_per_pixel >= 3 => (
+                samples_per_pixel as u16,
+                Samples::U8(values.into_iter().map(|v| v as u8).collect()),
+            ),
+            (2, 16) if samples_per_pixel >= 3 => (samples_per_pixel as u16, Samples::U16(values)),
+            // Palette, where the ColorMap has all red values, then all green and then all blue
+            (3, _) if samples_per_pixel == 1 => {
+                let color_map = get_tag_value!(tags, TiffTag::ColorMap)
+                    .ok_or(TiffError("Missing ColorMap".to_string()))?;
+                let color_count = 1_usize << bits_per_sample;
+
+                if color_map.len() != color_count * 3 {
+                    return Err(TiffError(format!(
+                        "Expected {} ColorMap values (got {})",
+                        color_count * 3,
+                        color_map.len()
+                    )));
+                }
+
+                let samples = values
+                    .into_iter()
+                    .flat_map(|v| (0..3).map(move |c| color_map[c * color_count + v as usize]))
+                    .collect();
+
+                (3, Samples::U16(samples))
+            }
+            _ => {
+                return Err(TiffError(format!(
+                "Decoding PhotometricInterpretation {} with {} samples of {} bits isn't supported",
+                photometric_interpretation, samples_per_pixel, bits_per_sample
+            )))
+            }
+        };
+
+        Ok(DecodedImage {
+            width,
+            height,
+            channels,
+            samples,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::Samples;
+    use crate::{
+        tiff::{read_tiff_file, Tiff, TiffTag},
+        utils::Endianness,
+    };
+
+    fn page(endianness: Endianness, tags: Vec<TiffTag>) -> Tiff {
+        let mut tiff = Tiff::from_tags(tags);
+        tiff.endianness = endianness;
+        tiff
+    }
+
+    #[test]
+    fn test_decode_uncompressed() {
+        let data = fs::read("test_images/test.tif").unwrap();
+        let tiff = read_tiff_file(&data).unwrap();
+
+        let image = tiff.decode_page(&data, 0).unwrap();
+        assert_eq!((image.width, image.height, image.channels), (1, 1, 1));
+        assert!(matches!(image.samples, Samples::U8(s) if s.len() == 1));
+
+        assert!(tiff.decode_page(&data, 1).is_err());
+    }
+
+    #[test]
+    fn test_decode_compressed_strips() {
+        // 2x2 RGB in two PackBits strips
+        let data = b"\x05\x01\x02\x03\x04\x05\x06\xFB\x09";
+        let tiff = page(
+            Endianness::Little,
+            vec![
+                TiffTag::ImageWidth(2),
+                TiffTag::ImageLength(2),
+                TiffTag::BitsPerSample(vec![8, 8, 8]),
+                TiffTag::Compression("PackBits".to_string()),
+                TiffTag::PhotometricInterpretation(2),
+                TiffTag::SamplesPerPixel(3),
+                TiffTag::RowsPerStrip(1),
+                TiffTag::StripOffsets(vec![0, 7]),
+                TiffTag::StripByteCounts(vec![7, 2]),
+            ],
+        );
+        let image = tiff.decode_page(data, 0).unwrap();
+        assert_eq!(image.channels, 3);
+        assert_eq!(
+            image.samples,
+            Samples::U8(vec![1, 2, 3, 4, 5, 6, 9, 9, 9, 9, 9, 9])
+        );
+
+        // 4x2 grayscale, LZW with the horizontal predictor
+        let data = [0x80, 0x02, 0xA0, 0x40, 0xA6, 0x40, 0x16, 0x0D, 0x01];
+        let tiff = page(
+            Endianness::Little,
+            vec![
+                TiffTag::ImageWidth(4),
+                TiffTag::ImageLength(2),
+                TiffTag::BitsPerSample(vec![8]),
+                TiffTag::Compression("LZW".to_string()),
+                TiffTag::PhotometricInterpretation(1),
+                TiffTag::Predictor(2),
+                TiffTag::StripOffsets(vec![0]),
+                TiffTag::StripByteCounts(vec![9]),
+            ],
+        );
+        let image = tiff.decode_page(&data, 0).unwrap();
+        assert_eq!(
+            image.samples,
+            Samples::U8(vec![10, 20, 30, 40, 200, 205, 210, 215])
+        );
+
+        // 2x1 RGB, Deflate
+        let data = [
+            0x78, 0x9C, 0x63, 0x60, 0x60, 0xF8, 0xFF, 0xFF, 0x3F, 0x00, 0x06, 0x00, 0x02, 0xFE,
+        ];
+        let tiff = page(
+            Endianness::Big,
+            vec![
+                TiffTag::ImageWidth(2),
+                TiffTag::ImageLength(1),
+                TiffTag::BitsPerSample(vec![8, 8, 8]),
+                TiffTag::Compression("Deflate".to_string()),
+                TiffTag::PhotometricInterpretation(2),
+                TiffTag::SamplesPerPixel(3),
+                TiffTag::StripOffsets(vec![0]),
+                TiffTag::StripByteCounts(vec![14]),
+            ],
+        );
+        let image = tiff.decode_page(&data, 0).unwrap();
+        assert_eq!(image.samples, Samples::U8(vec![0, 0, 0, 255, 255, 255]));
+    }
+
+    #[test]
+    fn test_decode_bilevel_and_palette() {
+        // 10x1 WhiteIsZero
+        let tags = vec![
+            TiffTag::ImageWidth(10),
+            TiffTag::ImageLength(1),
+            TiffTag::PhotometricInterpretation(0),
+            TiffTag::StripOffsets(vec![0]),
+            TiffTag::StripByteCounts(vec![2]),
+        ];
+        let image = page(Endianness::Little, tags.clone())
+            .decode_page(&[0b1010_0000, 0b1100_0000], 0)
+            .unwrap();
+        assert_eq!(
+            image.samples,
+            Samples::U8(vec![0, 255, 0, 255, 255, 255, 255, 255, 0, 0])
+        );
+
+        // The same bits as a 1-bit palette image
+        let mut tags = tags;
+        tags[2] = TiffTag::PhotometricInterpretation(3);
+        tags.push(TiffTag::ColorMap(vec![0, 65535, 100, 200, 300, 400]));
+        let image = page(Endianness::Little, tags)
+            .decode_page(&[0b1000_0000, 0], 0)
+            .unwrap();
+        assert_eq!(image.channels, 3);
+        assert!(
+            matches!(image.samples, Samples::U16(s) if s[0..6] == [65535, 200, 400, 0, 100, 300])
+        );
+    }
+
+    #[test]
+    fn test_decode_tiles() {
+        // 20x2 16-bit grayscale in two 16x16 big-endian tiles, where each pixel is its column
+        let mut data: Vec<u8> = vec![];
+        for tile in 0..2_u16 {
+            for _ in 0..16 {
+                for column in 0..16 {
+                    data.extend((tile * 16 + column).to_be_bytes());
+                }
+            }
+        }
+
+        let tiff = page(
+            Endianness::Big,
+            vec![
+                TiffTag::ImageWidth(20),
+                TiffTag::ImageLength(2),
+                TiffTag::BitsPerSample(vec![16]),
+                TiffTag::PhotometricInterpretation(1),
+                TiffTag::TileWidth(16),
+                TiffTag::TileLength(16),
+                TiffTag::TileOffsets(vec![0, 512]),
+                TiffTag::TileByteCounts(vec![512, 512]),
+            ],
+        );
+
+        let image = tiff.decode_page(&data, 0).unwrap();
+        let expected: Vec<u16> = (0..2).flat_map(|_| 0..20).collect();
+        assert_eq!(image.samples, Samples::U16(expected));
+    }
+
+    #[test]
+    fn test_decode_invalid_geometry() {
+        let tiff = |width, height, tile_width| {
+            page(
+                Endianness::Little,
+                vec![
+                    TiffTag::ImageWidth(width),
+                    TiffTag::ImageLength(height),
+                    TiffTag::BitsPerSample(vec![8]),
+                    TiffTag::PhotometricInterpretation(1),
+                    TiffTag::TileWidth(tile_width),
+                    TiffTag::TileLength(16),
+                    TiffTag::TileOffsets(vec![0]),
+                    TiffTag::TileByteCounts(vec![4]),
+                ],
+            )
+        };
+
+        assert!(tiff(0, 1, 16).decode_page(&[0; 4], 0).is_err());
+        assert!(tiff(1, 0, 16).decode_page(&[0; 4], 0).is_err());
+        assert!(tiff(1, 1, 0).decode_page(&[0; 4], 0).is_err());
+
+        // A huge image whose only tile is far too small for it
+        assert!(tiff(u32::MAX, u32::MAX, u32::MAX)
+            .decode_page(&[0; 4], 0)
+            .is_err());
+        assert!(tiff(16, 16, 16).decode_page(&[0; 4], 0).is_err());
+    }
+}
@@ -0,0 +1,298 @@
+// https://www.iptc.org/std/IIM/4.2/specification/IIMV4.2.pdf
+
+use crate::{
+    get_tag_value,
+    tiff::{Tiff, TiffTag},
+};
+
+#[derive(Debug)]
+pub struct IptcError(pub String);
+
+/// A single IIM dataset, identified by its record and dataset number (e.g. 2:120 is the caption)
+#[derive(Debug, PartialEq, Clone)]
+pub struct IptcDataSet {
+    pub record: u8,
+    pub dataset: u8,
+    pub name: String, // e.g. "Caption-Abstract", or "Unknown" for datasets we don't know
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Iptc {
+    pub datasets: Vec<IptcDataSet>,
+    pub utf8: bool, // Whether 1:90 CodedCharacterSet declares UTF-8
+}
+
+fn get_dataset_name(record: u8, dataset: u8) -> &'static str {
+    match (record, dataset) {
+        (1, 0) => "EnvelopeRecordVersion",
+        (1, 5) => "Destination",
+        (1, 20) => "FileFormat",
+        (1, 22) => "FileFormatVersion",
+        (1, 30) => "ServiceIdentifier",
+        (1, 40) => "EnvelopeNumber",
+        (1, 50) => "ProductID",
+        (1, 60) => "EnvelopePriority",
+        (1, 70) => "DateSent",
+        (1, 80) => "TimeSent",
+        (1, 90) => "CodedCharacterSet",
+        (1, 100) => "UniqueObjectName",
+        (1, 120) => "ARMIdentifier",
+        (1, 122) => "ARMVersion",
+
+        (2, 0) => "ApplicationRecordVersion",
+        (2, 3) => "ObjectTypeReference",
+        (2, 4) => "ObjectAttributeReference",
+        (2, 5) => "ObjectName",
+        (2, 7) => "EditStatus",
+        (2, 8) => "EditorialUpdate",
+        (2, 10) => "Urgency",
+        (2, 12) => "SubjectReference",
+        (2, 15) => "Category",
+        (2, 20) => "SupplementalCategories",
+        (2, 22) => "FixtureIdentifier",
+        (2, 25) => "Keywords",
+        (2, 26) => "ContentLocationCode",
+        (2, 27) => "ContentLocationName",
+        (2, 30) => "ReleaseDate",
+        (2, 35) => "ReleaseTime",
+        (2, 37) => "ExpirationDate",
+        (2, 38) => "ExpirationTime",
+        (2, 40) => "SpecialInstructions",
+        (2, 42) => "ActionAdvised",
+        (2, 45) => "ReferenceService",
+        (2, 47) => "ReferenceDate",
+        (2, 50) => "ReferenceNumber",
+        (2, 55) => "DateCreated",
+        (2, 60) => "TimeCreated",
+        (2, 62) => "DigitalCreationDate",
+        (2, 63) => "DigitalCreationTime",
+        (2, 65) => "OriginatingProgram",
+        (2, 70) => "ProgramVersion",
+        (2, 75) => "ObjectCycle",
+        (2, 80) => "By-line",
+        (2, 85) => "By-lineTitle",
+        (2, 90) => "City",
+        (2, 92) => "Sub-location",
+        (2, 95) => "Province-State",
+        (2, 100) => "Country-PrimaryLocationCode",
+        (2, 101) => "Country-PrimaryLocationName",
+        (2, 103) => "OriginalTransmissionReference",
+        (2, 105) => "Headline",
+        (2, 110) => "Credit",
+        (2, 115) => "Source",
+        (2, 116) => "CopyrightNotice",
+        (2, 118) => "Contact",
+        (2, 120) => "Caption-Abstract",
+        (2, 122) => "Writer-Editor",
+        (2, 125) => "RasterizedCaption",
+        (2, 130) => "ImageType",
+        (2, 131) => "ImageOrientation",
+        (2, 135) => "LanguageIdentifier",
+        (2, 150) => "AudioType",
+        (2, 151) => "AudioSamplingRate",
+        (2, 152) => "AudioSamplingResolution",
+        (2, 153) => "AudioDuration",
+        (2, 154) => "AudioOutcue",
+        (2, 200) => "ObjectDataPreviewFileFormat",
+        (2, 201) => "ObjectDataPreviewFileFormatVersion",
+        (2, 202) => "ObjectDataPreviewData",
+        _ => "Unknown",
+    }
+}
+
+/// Decodes the IIM datasets in `data`. Each dataset starts with a 0x1C tag marker, followed by the
+/// record and dataset numbers and the data's length, which is either a u16 or (if the top bit is
+/// set) an "extended" length stored in the following 1-4 bytes.
+pub fn read_iptc(data: &[u8]) -> Result<Iptc, IptcError> {
+    let mut datasets: Vec<IptcDataSet> = vec![];
+
+    let mut position = 0;
+    while position < data.len() {
+        // Some writers pad the data, e.g. to an even length
+        if data[position] == 0 {
+            position += 1;
+            continue;
+        }
+
+        let header = match data.get(position..position + 5) {
+            Some(header) if header[0] == 0x1C => header,
+            _ => {
+                return Err(IptcError(format!(
+                    "Expected a tag marker (0x1C) at offset {} but got {:#04x}",
+                    position, data[position]
+                )))
+            }
+        };
+        position += 5;
+
+        let (record, dataset) = (header[1], header[2]);
+        let mut length = u16::from_be_bytes([header[3], header[4]]) as usize;
+
+        if length & 0x8000 != 0 {
+            let length_size = length & 0x7FFF;
+            let length_bytes = match data.get(position..position + length_size) {
+                Some(bytes) if length_size <= 4 => bytes,
+                _ => {
+                    return Err(IptcError(format!(
+                        "[{}:{}] Invalid extended length of {} bytes",
+                        record, dataset, length_size
+                    )))
+                }
+            };
+
+            length = length_bytes
+                .iter()
+                .fold(0, |acc, b| (acc << 8) | *b as usize);
+            position += length_size;
+        }
+
+        let value = match data.get(position..position + length) {
+            Some(value) => value.to_vec(),
+            None => {
+                return Err(IptcError(format!(
+                    "[{}:{}] Expected {} bytes but only {} are left",
+                    record,
+                    dataset,
+                    length,
+                    data.len() - position
+                )))
+            }
+        };
+        position += length;
+
+        datasets.push(IptcDataSet {
+            record,
+            dataset,
+            name: get_dataset_name(record, dataset).to_string(),
+            data: value,
+        });
+    }
+
+    // The ISO 2022 escape sequence for UTF-8 is "ESC % G"
+    let utf8 = datasets
+        .iter()
+        .any(|d| (d.record, d.dataset) == (1, 90) && d.data == b"\x1B%G");
+
+    Ok(Iptc { datasets, utf8 })
+}
+
+impl Iptc {
+    fn decode_string(&self, data: &[u8]) -> String {
+        match self.utf8 {
+            true => String::from_utf8_lossy(data).to_string(),
+            // Without a declared character set the data is usually Latin-1 (or plain ASCII)
+            false => match std::str::from_utf8(data) {
+                Ok(s) => s.to_string(),
+                Err(_) => data.iter().map(|b| *b as char).collect(),
+            },
+        }
+    }
+
+    /// All values of a dataset as strings, for the repeatable datasets like Keywords (2:25)
+    pub fn get_all(&self, record: u8, dataset: u8) -> Vec<String> {
+        self.datasets
+            .iter()
+            .filter(|d| d.record == record && d.dataset == dataset)
+            .map(|d| self.decode_string(&d.data))
+            .collect()
+    }
+
+    /// The first value of a dataset as a string
+    pub fn get(&self, record: u8, dataset: u8) -> Option<String> {
+        self.get_all(record, dataset).into_iter().next()
+    }
+
+    pub fn object_name(&self) -> Option<String> {
+        self.get(2, 5)
+    }
+
+    pub fn keywords(&self) -> Vec<String> {
+        self.get_all(2, 25)
+    }
+
+    pub fn date_created(&self) -> Option<String> {
+        self.get(2, 55)
+    }
+
+    pub fn by_line(&self) -> Vec<String> {
+        self.get_all(2, 80)
+    }
+
+    pub fn city(&self) -> Option<String> {
+        self.get(2, 90)
+    }
+
+    pub fn country(&self) -> Option<String> {
+        self.get(2, 101)
+    }
+
+    pub fn headline(&self) -> Option<String> {
+        self.get(2, 105)
+    }
+
+    pub fn credit(&self) -> Option<String> {
+        self.get(2, 110)
+    }
+
+    pub fn source(&self) -> Option<String> {
+        self.get(2, 115)
+    }
+
+    pub fn copyright_notice(&self) -> Option<String> {
+        self.get(2, 116)
+    }
+
+    pub fn caption(&self) -> Option<String> {
+        self.get(2, 120)
+    }
+}
+
+impl Tiff {
+    /// The IPTC-IIM data from the IptcNaa tag or, failing that, from the Photoshop image resources
+    pub fn iptc(&self) -> Option<Iptc> {
+        match get_tag_value!(self.tags, TiffTag::IptcNaa) {
+            Some(data) => read_iptc(data).ok(),
+            None => self.photoshop()?.iptc(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::read_iptc;
+    use crate::jpeg::read_jpeg;
+
+    #[test]
+    fn test_read_iptc_from_jpeg() {
+        let data = fs::read("test_images/no_exif_only_xmp.jpeg").unwrap();
+        let iptc = read_jpeg(&data).unwrap().iptc.unwrap();
+
+        assert!(iptc.utf8);
+        assert_eq!(iptc.datasets.len(), 5);
+        assert_eq!(iptc.datasets[0].name, "CodedCharacterSet");
+        assert_eq!(iptc.by_line(), vec!["CREDIT"]);
+        assert_eq!(iptc.caption().as_deref(), Some("Der Goalie bin ig"));
+        assert_eq!(iptc.keywords(), vec!["tag"]);
+        assert_eq!(iptc.headline(), None);
+    }
+
+    #[test]
+    fn test_character_sets_and_extended_length() {
+        let mut data = b"\x1C\x02\x19\x00\x04caf\xE9\x1C\x02\x19\x00\x03tea".to_vec();
+        data.extend(b"\x1C\x02\x78\x80\x02\x00\x05hello\x00");
+
+        let iptc = read_iptc(&data).unwrap();
+        assert!(!iptc.utf8);
+        assert_eq!(iptc.keywords(), vec!["café", "tea"]);
+        assert_eq!(iptc.caption().as_deref(), Some("hello"));
+
+        let mut data = b"\x1C\x01\x5A\x00\x03\x1B%G".to_vec();
+        data.extend("\x1C\x02\x19\x00\x05café".as_bytes());
+        assert_eq!(read_iptc(&data).unwrap().keywords(), vec!["café"]);
+
+        assert!(read_iptc(b"\x1C\x02\x19\x00\x10short").is_err());
+    }
+}
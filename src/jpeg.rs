@@ -1,6 +1,10 @@
 use std::io::{Cursor, Read, Seek, SeekFrom};
 
 use crate::{
+    icc::{read_icc_profile, IccProfile},
+    iptc::Iptc,
+    orientation::Orientation,
+    photoshop::{read_photoshop_resources, Photoshop},
     read_unpack,
     tiff::{read_exif_section, Tiff},
     utils::Endianness,
@@ -16,6 +20,19 @@ pub struct Jpeg {
     pub comment: Option<String>,
     pub exif: Option<tiff::Tiff>,
     pub xmp: Option<String>,
+    pub icc_profile: Option<IccProfile>,
+    pub iptc: Option<Iptc>,
+    pub photoshop: Option<Photoshop>,
+}
+
+impl Jpeg {
+    /// How the image should be transformed for display, based on the EXIF Orientation tag
+    pub fn orientation(&self) -> Orientation {
+        self.exif
+            .as_ref()
+            .and_then(|exif| exif.orientation())
+            .unwrap_or(Orientation::Normal)
+    }
 }
 
 // This enum is very incomplete and only contains some markers I encountered when testing
@@ -38,6 +55,7 @@ pub enum JpegMarker {
     APP0,
     APP1,
     APP2,
+    APP13,
 
     SOS, // Start of scan
 
@@ -63,6 +81,7 @@ impl From<u8> for JpegMarker {
             0xE0 => JpegMarker::APP0,
             0xE1 => JpegMarker::APP1,
             0xE2 => JpegMarker::APP2,
+            0xED => JpegMarker::APP13,
             0xDA => JpegMarker::SOS,
             0xDB => JpegMarker::DQT,
             0xDD => JpegMarker::DRI,
@@ -130,6 +149,88 @@ fn get_jpeg_sections(data: &[u8]) -> Vec<(JpegMarker, Vec<u8>)> {
     sections
 }
 
+/// Reassembles the ICC profile, which is split into chunks across APP2 markers that each start
+/// with "ICC_PROFILE\0", a (1-based) sequence number and the total number of chunks
+fn get_icc_profile(sections: &[(JpegMarker, Vec<u8>)]) -> Option<IccProfile> {
+    let mut chunks: Vec<(u8, u8, &[u8])> = sections
+        .iter()
+        .filter(|(m, d)| m == &JpegMarker::APP2 && d.len() >= 14 && d[0..12] == *b"ICC_PROFILE\0")
+        .map(|(_, d)| (d[12], d[13], &d[14..]))
+        .collect();
+
+    chunks.sort_by_key(|(sequence_number, _, _)| *sequence_number);
+
+    // Make sure every chunk is present exactly once
+    let chunk_count = chunks.first()?.1;
+    if chunks.len() != chunk_count as usize
+        || chunks.iter().enumerate().any(|(i, (n, _, _))| *n as usize != i + 1)
+    {
+        return None;
+    }
+
+    let data: Vec<u8> = chunks.into_iter().flat_map(|(_, _, d)| d.to_vec()).collect();
+
+    read_icc_profile(data).ok()
+}
+
+/// Reads the Photoshop image resources from the APP13 "Photoshop 3.0" sections, large resources
+/// can be split across several sections
+fn get_photoshop(sections: &[(JpegMarker, Vec<u8>)]) -> Option<Photoshop> {
+    let data: Vec<u8> = sections
+        .iter()
+        .filter(|(m, d)| m == &JpegMarker::APP13 && d.starts_with(b"Photoshop 3.0\0"))
+        .flat_map(|(_, d)| d[14..].to_vec())
+        .collect();
+
+    if data.is_empty() {
+        return None;
+    }
+
+    read_photoshop_resources(&data).ok()
+}
+
+/// Finds the (width, height) of a JPEG from its start of frame marker, without reading the rest
+/// of the file. Returns None if the data isn't a valid JPEG.
+pub fn read_jpeg_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    if !data.starts_with(b"\xFF\xD8") {
+        return None;
+    }
+
+    let mut position = 2;
+    while position + 4 <= data.len() {
+        if data[position] != 0xFF {
+            return None;
+        }
+
+        let marker = data[position + 1];
+
+        // Fill bytes
+        if marker == 0xFF {
+            position += 1;
+            continue;
+        }
+
+        let size = u16::from_be_bytes([data[position + 2], data[position + 3]]) as usize;
+
+        // SOF0-SOF15, apart from DHT (0xC4), JPG (0xC8) and DAC (0xCC)
+        if (0xC0..=0xCF).contains(&marker) && ![0xC4, 0xC8, 0xCC].contains(&marker) {
+            let frame = data.get(position + 4..position + 9)?;
+            let height = u16::from_be_bytes([frame[1], frame[2]]) as u32;
+            let width = u16::from_be_bytes([frame[3], frame[4]]) as u32;
+
+            return Some((width, height));
+        }
+
+        if marker == 0xDA {
+            return None;
+        }
+
+        position += 2 + size;
+    }
+
+    None
+}
+
 pub fn read_jpeg(data: &[u8]) -> Result<Jpeg, JpegError> {
     let sections = get_jpeg_sections(data);
 
@@ -163,13 +264,24 @@ pub fn read_jpeg(data: &[u8]) -> Result<Jpeg, JpegError> {
         .map(|(_, d)| String::from_utf8_lossy(d).to_string())
         .next();
 
-    Ok(Jpeg { comment, exif, xmp })
+    let icc_profile = get_icc_profile(&sections);
+    let photoshop = get_photoshop(&sections);
+    let iptc = photoshop.as_ref().and_then(|p| p.iptc());
+
+    Ok(Jpeg {
+        comment,
+        exif,
+        xmp,
+        icc_profile,
+        iptc,
+        photoshop,
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::read_jpeg;
-    use crate::{get_tag_value, tiff::TiffTag, utils::Endianness};
+    use crate::{get_tag_value, orientation::Orientation, tiff::TiffTag, utils::Endianness};
     use std::fs;
 
     #[test]
@@ -177,6 +289,8 @@ mod tests {
         let data = fs::read("test_images/PaintTool_sample.jpeg").unwrap();
 
         let jpeg = read_jpeg(&data).unwrap();
+        assert_eq!(jpeg.orientation(), Orientation::Normal);
+
         let exif_data = jpeg.exif.unwrap();
 
         assert!(jpeg.comment.is_none());
@@ -231,6 +345,36 @@ mod tests {
             get_tag_value!(exif_data.tags, TiffTag::DigitalZoomRatio).unwrap(),
             "0/100"
         );
+
+        assert_eq!(
+            *get_tag_value!(exif_data.tags, TiffTag::PhotographicSensitivity).unwrap(),
+            vec![200],
+        );
+
+        assert_eq!(
+            get_tag_value!(exif_data.tags, TiffTag::SensitivityType).unwrap(),
+            "Standard output sensitivity (SOS)",
+        );
+
+        assert_eq!(
+            get_tag_value!(exif_data.tags, TiffTag::ComponentsConfiguration).unwrap(),
+            "Y, Cb, Cr, -",
+        );
+
+        assert_eq!(
+            get_tag_value!(exif_data.tags, TiffTag::ColorSpace).unwrap(),
+            "sRGB",
+        );
+
+        assert_eq!(
+            get_tag_value!(exif_data.tags, TiffTag::FileSource).unwrap(),
+            "Digital still camera",
+        );
+
+        assert_eq!(
+            get_tag_value!(exif_data.tags, TiffTag::CustomRendered).unwrap(),
+            "Normal process",
+        );
     }
 
     #[test]
@@ -263,7 +407,7 @@ mod tests {
         );
 
         assert_eq!(
-            get_tag_value!(exif_data.tags, TiffTag::GPSAltitudeRef).unwrap(),
+            get_tag_value!(exif_data.tags, TiffTag::GPSAltitudeRef).unwrap().to_string(),
             "Above sea level",
         );
 
@@ -1,4 +1,19 @@
+mod bplist;
+mod capture_time;
+mod composite;
+mod decompress;
+mod gps;
+mod geotiff;
 mod heif;
+mod icc;
+mod image_data;
+mod iptc;
 mod jpeg;
+mod makernote;
+mod orientation;
+mod photoshop;
+mod tag_dictionary;
+mod tag_registry;
+mod thumbnail;
 mod tiff;
 mod utils;
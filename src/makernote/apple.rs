@@ -0,0 +1,291 @@
+// See <https://exiftool.org/TagNames/Apple.html>
+
+use crate::{
+    bplist::{read_bplist, BplistValue},
+    get_tag_value,
+    tiff::{IFDEntry, IFDEntryValue, Tiff, TiffError},
+};
+
+use super::{decode_entries, get_integer, get_string, MakerNoteTags};
+
+/// The time since the device was powered up, which groups photos taken in the same session
+#[derive(Debug, PartialEq, Clone)]
+pub struct AppleRunTime {
+    pub flags: i64, // 1 = valid, 2 = has been rounded, 4 = positive infinity, 8 = negative infinity
+    pub value: i64,
+    pub timescale: i64,
+    pub epoch: i64,
+}
+
+impl AppleRunTime {
+    /// The run time in seconds, or None if it's invalid
+    pub fn seconds(&self) -> Option<f64> {
+        match self.flags & 1 != 0 && self.timescale != 0 {
+            true => Some(self.value as f64 / self.timescale as f64),
+            false => None,
+        }
+    }
+}
+
+/// Tags of "Apple iOS" makernotes, written by iPhones and iPads
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub enum AppleTag {
+    Unknown(IFDEntry),
+    Bplist(u16, BplistValue), // A bplist encoded tag we don't decode further
+
+    MakerNoteVersion(i32),
+    RunTime(AppleRunTime),
+    AEStable(bool),
+    AETarget(i32),
+    AEAverage(i32),
+    AFStable(bool),
+    AccelerationVector([f64; 3]), // Relative to the device, in g
+    HdrImageType(String),
+    BurstUuid(String),
+    FocusDistanceRange([f64; 2]), // Meters
+    OisMode(i32),
+    ContentIdentifier(String), // Shared by the photo and video of a Live Photo
+    ImageCaptureType(String),
+    ImageUniqueId(String),
+    LivePhotoVideoIndex(i64),
+    ImageCaptureRequestId(String),
+    HdrHeadroom(f64),
+    PhotoIdentifier(String),
+    ColorTemperature(i32),
+    CameraType(String),
+    FocusPosition(i32),
+    HdrGain(f64),
+    SemanticStyle(BplistValue),
+    SemanticStyleRenderingVersion(BplistValue),
+    SemanticStylePreset(BplistValue),
+}
+
+fn get_i32(entry: &IFDEntry) -> Result<i32, TiffError> {
+    Ok(get_integer(entry)? as i32)
+}
+
+fn get_numbers<const N: usize>(entry: &IFDEntry) -> Result<[f64; N], TiffError> {
+    let values: Vec<f64> = entry.clone().try_into()?;
+    let count = values.len();
+
+    values.try_into().map_err(|_| {
+        TiffError(format!(
+            "[Apple tag {}] Expected {} values (got {})",
+            entry.tag, N, count
+        ))
+    })
+}
+
+fn get_bplist(entry: &IFDEntry) -> Result<BplistValue, TiffError> {
+    let data: Vec<u8> = entry.clone().try_into()?;
+
+    read_bplist(&data).map_err(|e| TiffError(format!("[Apple tag {}] {}", entry.tag, e.0)))
+}
+
+fn get_run_time(entry: &IFDEntry) -> Result<AppleRunTime, TiffError> {
+    let value = get_bplist(entry)?;
+    let get = |key: &str| value.get(key).and_then(|v| v.as_i64()).unwrap_or_default();
+
+    Ok(AppleRunTime {
+        flags: get("flags"),
+        value: get("value"),
+        timescale: get("timescale"),
+        epoch: get("epoch"),
+    })
+}
+
+fn is_bplist(entry: &IFDEntry) -> bool {
+    let start: Vec<u8> = entry
+        .values
+        .iter()
+        .take(8)
+        .filter_map(|v| match v {
+            IFDEntryValue::UNDEFINED(b) => Some(*b),
+            _ => None,
+        })
+        .collect();
+
+    start == b"bplist00"
+}
+
+fn decode_apple_entry(entry: &IFDEntry) -> Result<AppleTag, TiffError> {
+    let tag = match entry.tag {
+        0x0001 => AppleTag::MakerNoteVersion(get_i32(entry)?),
+        0x0003 => AppleTag::RunTime(get_run_time(entry)?),
+        0x0004 => AppleTag::AEStable(get_integer(entry)? == 1),
+        0x0005 => AppleTag::AETarget(get_i32(entry)?),
+        0x0006 => AppleTag::AEAverage(get_i32(entry)?),
+        0x0007 => AppleTag::AFStable(get_integer(entry)? == 1),
+        0x0008 => AppleTag::AccelerationVector(get_numbers(entry)?),
+        0x000A => AppleTag::HdrImageType(
+            match get_integer(entry)? {
+                3 => "HDR Image",
+                4 => "Original Image",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        0x000B => AppleTag::BurstUuid(get_string(entry)?),
+        0x000C => AppleTag::FocusDistanceRange(get_numbers(entry)?),
+        0x000F => AppleTag::OisMode(get_i32(entry)?),
+        0x0011 => AppleTag::ContentIdentifier(get_string(entry)?),
+        0x0014 => AppleTag::ImageCaptureType(
+            match get_integer(entry)? {
+                1 => "ProRAW",
+                2 => "Portrait",
+                10 => "Photo",
+                11 => "Manual Focus",
+                12 => "Scene",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        0x0015 => AppleTag::ImageUniqueId(get_string(entry)?),
+        0x0017 => AppleTag::LivePhotoVideoIndex(get_integer(entry)?),
+        0x0020 => AppleTag::ImageCaptureRequestId(get_string(entry)?),
+        0x0021 => AppleTag::HdrHeadroom(entry.clone().try_into()?),
+        0x002B => AppleTag::PhotoIdentifier(get_string(entry)?),
+        0x002D => AppleTag::ColorTemperature(get_i32(entry)?),
+        0x002E => AppleTag::CameraType(
+            match get_integer(entry)? {
+                0 => "Back Wide Angle",
+                1 => "Back Normal",
+                6 => "Front",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        0x002F => AppleTag::FocusPosition(get_i32(entry)?),
+        0x0030 => AppleTag::HdrGain(entry.clone().try_into()?),
+        0x0040 => AppleTag::SemanticStyle(get_bplist(entry)?),
+        0x0041 => AppleTag::SemanticStyleRenderingVersion(get_bplist(entry)?),
+        0x0042 => AppleTag::SemanticStylePreset(get_bplist(entry)?),
+        _ if is_bplist(entry) => AppleTag::Bplist(entry.tag, get_bplist(entry)?),
+        _ => AppleTag::Unknown(entry.clone()),
+    };
+
+    Ok(tag)
+}
+
+/// Decodes the entries of an Apple makernote.
+pub(crate) fn decode_apple_tags(entries: &[IFDEntry]) -> Vec<AppleTag> {
+    decode_entries(entries, decode_apple_entry)
+}
+
+impl Tiff {
+    fn apple_tags(&self) -> Option<&Vec<AppleTag>> {
+        match &self.makernote()?.tags {
+            MakerNoteTags::Apple(tags) => Some(tags),
+            _ => None,
+        }
+    }
+
+    /// The identifier shared by the photo and video of a Live Photo, from an Apple makernote
+    pub fn content_identifier(&self) -> Option<&str> {
+        get_tag_value!(self.apple_tags()?, AppleTag::ContentIdentifier).map(|v| v.as_str())
+    }
+
+    /// The identifier shared by all photos of a burst, from an Apple makernote
+    pub fn burst_uuid(&self) -> Option<&str> {
+        get_tag_value!(self.apple_tags()?, AppleTag::BurstUuid).map(|v| v.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::AppleTag;
+    use crate::{
+        get_tag_value,
+        heif::read_heif,
+        makernote::{read_makernote, MakerNoteTags, MakerNoteVendor, TestIfd},
+        utils::Endianness,
+    };
+
+    /// A bplist of {"flags": 1, "value": 123456789000, "timescale": 1000000000, "epoch": 0}
+    fn run_time_bplist() -> Vec<u8> {
+        let mut data = b"bplist00".to_vec();
+        let objects: [&[u8]; 9] = [
+            b"\xD4\x01\x02\x03\x04\x05\x06\x07\x08",
+            b"\x55flags",
+            b"\x55value",
+            b"\x59timescale",
+            b"\x55epoch",
+            b"\x10\x01",
+            b"\x13\0\0\0\x1C\xBE\x99\x1A\x08",
+            b"\x12\x3B\x9A\xCA\x00",
+            b"\x10\x00",
+        ];
+
+        let mut offsets: Vec<u8> = vec![];
+        for object in objects {
+            offsets.push(data.len() as u8);
+            data.extend(object);
+        }
+        let offset_table_offset = data.len() as u8;
+        data.extend(&offsets);
+        data.extend([0, 0, 0, 0, 0, 0, 1, 1]);
+        data.extend([0, 0, 0, 0, 0, 0, 0, objects.len() as u8]);
+        data.extend([0; 8]);
+        data.extend([0, 0, 0, 0, 0, 0, 0, offset_table_offset]);
+        data
+    }
+
+    #[test]
+    fn test_apple_makernote() {
+        // "Apple iOS\0", version 1, big-endian, then an IFD at 14 whose values are relative to the
+        // start of the makernote
+        let mut data = b"Apple iOS\0\0\x01MM".to_vec();
+        data.extend(
+            TestIfd::new(Endianness::Big)
+                .add_slongs(0x0001, &[14])
+                .add_undefined(0x0003, &run_time_bplist())
+                .add_srationals(0x0008, &[(-2, 100), (0, 1), (1, 1)])
+                .add_ascii(0x000B, "0B5E1C7A-2F3D-4E6B-8C9A-1D2E3F4A5B6C")
+                .add_ascii(0x0011, "4F6A0E2C-5B1D-4C8E-9A73-2D6B1E0F8C41")
+                .add_slongs(0x0014, &[10])
+                .add_srationals(0x0021, &[(3, 2)])
+                .build(14),
+        );
+
+        let makernote = read_makernote(&[], 0, data, ("Apple", "iPhone 15 Pro"), Endianness::Big);
+        assert_eq!(makernote.vendor, MakerNoteVendor::Apple);
+        let tags = match &makernote.tags {
+            MakerNoteTags::Apple(tags) => tags,
+            other => panic!("Expected Apple tags (got {:?})", other),
+        };
+
+        assert_eq!(get_tag_value!(tags, AppleTag::MakerNoteVersion), Some(&14));
+        let run_time = get_tag_value!(tags, AppleTag::RunTime).unwrap();
+        assert_eq!(run_time.seconds(), Some(123.456789));
+        assert_eq!(
+            get_tag_value!(tags, AppleTag::AccelerationVector),
+            Some(&[-0.02, 0.0, 1.0])
+        );
+        assert_eq!(
+            get_tag_value!(tags, AppleTag::BurstUuid).unwrap(),
+            "0B5E1C7A-2F3D-4E6B-8C9A-1D2E3F4A5B6C"
+        );
+        assert_eq!(
+            get_tag_value!(tags, AppleTag::ContentIdentifier).unwrap(),
+            "4F6A0E2C-5B1D-4C8E-9A73-2D6B1E0F8C41"
+        );
+        assert_eq!(
+            get_tag_value!(tags, AppleTag::ImageCaptureType).unwrap(),
+            "Photo"
+        );
+        assert_eq!(get_tag_value!(tags, AppleTag::HdrHeadroom), Some(&1.5));
+        assert!(makernote.unknown_entries().is_empty());
+    }
+
+    #[test]
+    fn test_heic_without_makernote() {
+        let data = fs::read("test_images/image1.heic").unwrap();
+        let exif = read_heif(data).exif.unwrap();
+
+        assert_eq!(exif.content_identifier(), None);
+        assert_eq!(exif.burst_uuid(), None);
+    }
+}
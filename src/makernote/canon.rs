@@ -0,0 +1,1135 @@
+// See <https://exiftool.org/TagNames/Canon.html>
+
+use crate::tiff::{IFDEntry, IFDEntryValue, TiffError};
+
+use super::{decode_entries, get_string};
+
+/// The CameraSettings array (tag 0x0001), whose length depends on the model so every field is
+/// optional
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct CanonCameraSettings {
+    pub macro_mode: Option<String>,
+    pub self_timer: Option<String>, // "Off" or e.g. "10 s"
+    pub quality: Option<String>,
+    pub flash_mode: Option<String>,
+    pub continuous_drive: Option<String>,
+    pub focus_mode: Option<String>,
+    pub record_mode: Option<String>,
+    pub image_size: Option<String>,
+    pub easy_mode: Option<String>,
+    pub digital_zoom: Option<String>,
+    pub contrast: Option<i16>,
+    pub saturation: Option<i16>,
+    pub sharpness: Option<i16>,
+    pub metering_mode: Option<String>,
+    pub focus_range: Option<String>,
+    pub exposure_mode: Option<String>,
+    pub lens_type: Option<u16>,
+    pub max_focal_length: Option<f64>, // Millimeters
+    pub min_focal_length: Option<f64>, // Millimeters
+    pub focal_units: Option<u16>,      // Per millimeter
+    pub max_aperture: Option<f64>,
+    pub min_aperture: Option<f64>,
+    pub focus_continuous: Option<String>,
+    pub image_stabilization: Option<String>,
+}
+
+/// The FocalLength array (tag 0x0002)
+#[derive(Debug, PartialEq, Clone)]
+pub struct CanonFocalLength {
+    pub focal_type: String,              // "Fixed" or "Zoom"
+    pub focal_length: f64,               // Millimeters
+    pub focal_plane_x_size: Option<f64>, // Millimeters, only for some models
+    pub focal_plane_y_size: Option<f64>,
+}
+
+/// The ShotInfo array (tag 0x0004)
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct CanonShotInfo {
+    pub auto_iso: Option<f64>,
+    pub base_iso: Option<f64>,
+    pub measured_ev: Option<f64>,
+    pub target_aperture: Option<f64>,
+    pub target_exposure_time: Option<f64>, // Seconds
+    pub exposure_compensation: Option<f64>,
+    pub white_balance: Option<String>,
+    pub sequence_number: Option<i16>,
+    pub flash_exposure_compensation: Option<f64>,
+    pub focus_distance_upper: Option<f64>, // Meters, infinity if the focus is at infinity
+    pub focus_distance_lower: Option<f64>,
+    pub f_number: Option<f64>,
+    pub exposure_time: Option<f64>, // Seconds
+    pub camera_type: Option<String>,
+    pub auto_rotate: Option<String>,
+}
+
+/// The FileInfo array (tag 0x0093)
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct CanonFileInfo {
+    pub shutter_count: Option<u32>, // Only stored here by the EOS-1D Mark II models
+    pub bracket_mode: Option<String>,
+    pub bracket_shot_number: Option<i16>,
+    pub raw_jpg_quality: Option<String>,
+    pub raw_jpg_size: Option<String>,
+    pub live_view_shooting: Option<bool>,
+    pub focus_distance_upper: Option<f64>, // Meters
+    pub focus_distance_lower: Option<f64>,
+    pub shutter_mode: Option<String>,
+    pub rf_lens_type: Option<u16>,
+}
+
+/// The ColorData block (tag 0x4001), whose layout is identified by its length
+#[derive(Debug, PartialEq, Clone)]
+pub struct CanonColorData {
+    pub version: u8, // The ExifTool ColorData table number (1-11), 0 if the layout is unknown
+    pub wb_rggb_levels_as_shot: Option<[u16; 4]>,
+    pub color_temp_as_shot: Option<u16>, // Kelvin
+    pub data: Vec<u16>,
+}
+
+/// Tags of Canon makernotes, which are a bare IFD whose offsets are relative to the TIFF header
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub enum CanonTag {
+    Unknown(IFDEntry),
+
+    CameraSettings(CanonCameraSettings),
+    FocalLength(CanonFocalLength),
+    ShotInfo(CanonShotInfo),
+    ImageType(String),
+    FirmwareVersion(String),
+    FileNumber(String), // e.g. "100-1234"
+    OwnerName(String),
+    SerialNumber(String),
+    ModelId(u32),
+    FileInfo(CanonFileInfo),
+    LensModel(String),
+    InternalSerialNumber(String),
+    ColorSpace(String),
+    ColorData(CanonColorData),
+    ImageUniqueId(String),
+}
+
+/// Canon's EV encoding, in 1/32 EV steps where 0x0C and 0x14 mean 1/3 and 2/3
+fn canon_ev(value: i16) -> f64 {
+    let sign = if value < 0 { -1.0 } else { 1.0 };
+    let value = (value as i32).abs();
+    let fraction = value & 0x1F;
+    let fraction = match fraction {
+        0x0C => 32.0 / 3.0,
+        0x14 => 64.0 / 3.0,
+        f => f as f64,
+    };
+
+    sign * ((value & !0x1F) as f64 + fraction) / 32.0
+}
+
+fn canon_aperture(value: i16) -> f64 {
+    2_f64.powf(canon_ev(value) / 2.0)
+}
+
+fn canon_exposure_time(value: i16) -> f64 {
+    2_f64.powf(-canon_ev(value))
+}
+
+fn get_distance(value: i16) -> Option<f64> {
+    match value as u16 {
+        0 => None,
+        0xFFFF => Some(f64::INFINITY),
+        v => Some(v as f64 / 100.0),
+    }
+}
+
+fn get_quality(value: i16) -> String {
+    match value {
+        -1 => "n/a",
+        0 => "Unknown",
+        1 => "Economy",
+        2 => "Normal",
+        3 => "Fine",
+        4 => "RAW",
+        5 => "Superfine",
+        7 => "CRAW",
+        130 => "Light (RAW)",
+        131 => "Standard (RAW)",
+        _ => "Unknown",
+    }
+    .to_string()
+}
+
+fn get_image_size(value: i16) -> String {
+    match value {
+        -1 => "n/a",
+        0 => "Large",
+        1 => "Medium",
+        2 => "Small",
+        5 => "Medium 1",
+        6 => "Medium 2",
+        7 => "Medium 3",
+        8 => "Postcard",
+        9 => "Widescreen",
+        10 => "Medium Widescreen",
+        14 => "Small 1",
+        15 => "Small 2",
+        16 => "Small 3",
+        128 => "640x480 Movie",
+        129 => "Medium Movie",
+        130 => "Small Movie",
+        137 => "1280x720 Movie",
+        142 => "1920x1080 Movie",
+        143 => "4096x2160 Movie",
+        _ => "Unknown",
+    }
+    .to_string()
+}
+
+fn get_camera_settings(values: &[i16]) -> CanonCameraSettings {
+    let get = |index: usize| values.get(index).copied();
+    let focal_units = get(25).map(|v| v as u16).filter(|v| *v > 0);
+    let focal_length = |value: i16| value as u16 as f64 / focal_units.unwrap_or(1) as f64;
+
+    CanonCameraSettings {
+        macro_mode: get(1).map(|v| {
+            match v {
+                1 => "Macro",
+                2 => "Normal",
+                _ => "Unknown",
+            }
+            .to_string()
+        }),
+        self_timer: get(2).map(|v| match v {
+            0 => "Off".to_string(),
+            // 0x4000 is set for a custom self-timer
+            v => format!("{} s", (v & 0x0FFF) as f64 / 10.0),
+        }),
+        quality: get(3).map(get_quality),
+        flash_mode: get(4).map(|v| {
+            match v {
+                -1 => "n/a",
+                0 => "Off",
+                1 => "Auto",
+                2 => "On",
+                3 => "Red-eye reduction",
+                4 => "Slow-sync",
+                5 => "Red-eye reduction (Auto)",
+                6 => "Red-eye reduction (On)",
+                16 => "External flash",
+                _ => "Unknown",
+            }
+            .to_string()
+        }),
+        continuous_drive: get(5).map(|v| {
+            match v {
+                0 => "Single",
+                1 => "Continuous",
+                2 => "Movie",
+                3 => "Continuous, Speed Priority",
+                4 => "Continuous, Low",
+                5 => "Continuous, High",
+                6 => "Silent Single",
+                9 => "Single, Silent",
+                10 => "Continuous, Silent",
+                _ => "Unknown",
+            }
+            .to_string()
+        }),
+        focus_mode: get(7).map(|v| {
+            match v {
+                0 => "One-shot AF",
+                1 => "AI Servo AF",
+                2 => "AI Focus AF",
+                3 | 6 => "Manual Focus",
+                4 => "Single",
+                5 => "Continuous",
+                16 => "Pan Focus",
+                256 => "One-shot AF (Live View)",
+                257 => "AI Servo AF (Live View)",
+                258 => "AI Focus AF (Live View)",
+                512 => "Movie Snap Focus",
+                519 => "Movie Servo AF",
+                _ => "Unknown",
+            }
+            .to_string()
+        }),
+        record_mode: get(9).map(|v| {
+            match v {
+                1 => "JPEG",
+                2 => "CRW+THM",
+                3 => "AVI+THM",
+                4 => "TIF",
+                5 => "TIF+JPEG",
+                6 => "CR2",
+                7 => "CR2+JPEG",
+                9 => "MOV",
+                10 => "MP4",
+                11 => "CRM",
+                12 => "CR3",
+                13 => "CR3+JPEG",
+                14 => "HIF",
+                15 => "CR3+HIF",
+                _ => "Unknown",
+            }
+            .to_string()
+        }),
+        image_size: get(10).map(get_image_size),
+        easy_mode: get(11).map(|v| {
+            match v {
+                0 => "Full auto",
+                1 => "Manual",
+                2 => "Landscape",
+                3 => "Fast shutter",
+                4 => "Slow shutter",
+                5 => "Night",
+                6 => "Gray Scale",
+                7 => "Sepia",
+                8 => "Portrait",
+                9 => "Sports",
+                10 => "Macro",
+                11 => "Black & White",
+                12 => "Pan focus",
+                13 => "Vivid",
+                14 => "Neutral",
+                15 => "Flash Off",
+                16 => "Long Shutter",
+                17 => "Super Macro",
+                18 => "Foliage",
+                19 => "Indoor",
+                20 => "Fireworks",
+                21 => "Beach",
+                22 => "Underwater",
+                23 => "Snow",
+                24 => "Kids & Pets",
+                25 => "Night Snapshot",
+                26 => "Digital Macro",
+                27 => "My Colors",
+                28 => "Movie Snap",
+                29 => "Super Macro 2",
+                30 => "Color Accent",
+                31 => "Color Swap",
+                32 => "Aquarium",
+                33 => "ISO 3200",
+                _ => "Unknown",
+            }
+            .to_string()
+        }),
+        digital_zoom: get(12).map(|v| {
+            match v {
+                0 => "None",
+                1 => "2x",
+                2 => "4x",
+                3 => "Other",
+                _ => "Unknown",
+            }
+            .to_string()
+        }),
+        contrast: get(13),
+        saturation: get(14),
+        sharpness: get(15),
+        metering_mode: get(17).map(|v| {
+            match v {
+                0 => "Default",
+                1 => "Spot",
+                2 => "Average",
+                3 => "Evaluative",
+                4 => "Partial",
+                5 => "Center-weighted average",
+                _ => "Unknown",
+            }
+            .to_string()
+        }),
+        focus_range: get(18).map(|v| {
+            match v {
+                0 => "Manual",
+                1 => "Auto",
+                2 => "Not Known",
+                3 => "Macro",
+                4 => "Very Close",
+                5 => "Close",
+                6 => "Middle Range",
+                7 => "Far Range",
+                8 => "Pan Focus",
+                9 => "Super Macro",
+                10 => "Infinity",
+                _ => "Unknown",
+            }
+            .to_string()
+        }),
+        exposure_mode: get(20).map(|v| {
+            match v {
+                0 => "Easy",
+                1 => "Program AE",
+                2 => "Shutter speed priority AE",
+                3 => "Aperture-priority AE",
+                4 => "Manual",
+                5 => "Depth-of-field AE",
+                6 => "M-Dep",
+                7 => "Bulb",
+                8 => "Flexible-priority AE",
+                _ => "Unknown",
+            }
+            .to_string()
+        }),
+        lens_type: get(22)
+            .map(|v| v as u16)
+            .filter(|v| *v != 0 && *v != 0xFFFF),
+        max_focal_length: get(23).filter(|v| *v != 0).map(focal_length),
+        min_focal_length: get(24).filter(|v| *v != 0).map(focal_length),
+        focal_units,
+        max_aperture: get(26).filter(|v| *v > 0).map(canon_aperture),
+        min_aperture: get(27).filter(|v| *v > 0).map(canon_aperture),
+        focus_continuous: get(32).map(|v| {
+            match v {
+                0 => "Single",
+                1 => "Continuous",
+                8 => "Manual",
+                _ => "Unknown",
+            }
+            .to_string()
+        }),
+        image_stabilization: get(34).map(|v| {
+            match v {
+                0 => "Off",
+                1 => "On",
+                2 => "Shoot Only",
+                3 => "Panning",
+                4 => "Dynamic",
+                256 => "Off (2)",
+                257 => "On (2)",
+                258 => "Shoot Only (2)",
+                259 => "Panning (2)",
+                260 => "Dynamic (2)",
+                _ => "Unknown",
+            }
+            .to_string()
+        }),
+    }
+}
+
+fn get_shot_info(values: &[i16]) -> CanonShotInfo {
+    let get = |index: usize| values.get(index).copied();
+
+    CanonShotInfo {
+        auto_iso: get(1).map(|v| 100.0 * 2_f64.powf(v as f64 / 32.0)),
+        base_iso: get(2).map(|v| 100.0 * 2_f64.powf(v as f64 / 32.0) / 32.0),
+        measured_ev: get(3).map(|v| v as f64 / 32.0 + 5.0),
+        target_aperture: get(4).filter(|v| *v > 0).map(canon_aperture),
+        target_exposure_time: get(5).filter(|v| *v > -1000).map(canon_exposure_time),
+        exposure_compensation: get(6).map(canon_ev),
+        white_balance: get(7).map(|v| {
+            match v {
+                0 => "Auto",
+                1 => "Daylight",
+                2 => "Cloudy",
+                3 => "Tungsten",
+                4 => "Fluorescent",
+                5 => "Flash",
+                6 => "Custom",
+                7 => "Black & White",
+                8 => "Shade",
+                9 => "Manual Temperature (Kelvin)",
+                10 => "PC Set1",
+                11 => "PC Set2",
+                12 => "PC Set3",
+                14 => "Daylight Fluorescent",
+                15 => "Custom 1",
+                16 => "Custom 2",
+                17 => "Underwater",
+                18 => "Custom 3",
+                19 => "Custom 4",
+                20 => "PC Set4",
+                21 => "PC Set5",
+                23 => "Auto (ambience priority)",
+                _ => "Unknown",
+            }
+            .to_string()
+        }),
+        sequence_number: get(9),
+        flash_exposure_compensation: get(15).map(canon_ev),
+        focus_distance_upper: get(19).and_then(get_distance),
+        focus_distance_lower: get(20).and_then(get_distance),
+        f_number: get(21).filter(|v| *v > 0).map(canon_aperture),
+        exposure_time: get(22).filter(|v| *v != 0).map(canon_exposure_time),
+        camera_type: get(26).map(|v| {
+            match v {
+                248 => "EOS High-end",
+                250 => "Compact",
+                252 => "EOS Mid-range",
+                255 => "DV Camera",
+                _ => "Unknown",
+            }
+            .to_string()
+        }),
+        auto_rotate: get(27).map(|v| {
+            match v {
+                -1 => "n/a",
+                0 => "None",
+                1 => "Rotate 90 CW",
+                2 => "Rotate 180",
+                3 => "Rotate 270 CW",
+                _ => "Unknown",
+            }
+            .to_string()
+        }),
+    }
+}
+
+fn get_file_info(values: &[i16], model: &str) -> CanonFileInfo {
+    let get = |index: usize| values.get(index).copied();
+
+    // The EOS-1D Mark II models store the shutter count as a word-swapped LONG. Other models use
+    // these values for the file number or leave them unused, so the model has to match exactly.
+    let is_1d_mark_ii = matches!(
+        model.trim(),
+        "Canon EOS-1D Mark II" | "Canon EOS-1Ds Mark II" | "Canon EOS-1D Mark II N"
+    );
+    let shutter_count = match (get(1), get(2)) {
+        (Some(high), Some(low)) if is_1d_mark_ii => {
+            Some(((high as u16 as u32) << 16) | low as u16 as u32)
+        }
+        _ => None,
+    };
+
+    CanonFileInfo {
+        shutter_count,
+        bracket_mode: get(3).map(|v| {
+            match v {
+                0 => "Off",
+                1 => "AEB",
+                2 => "FEB",
+                3 => "ISO",
+                4 => "WB",
+                _ => "Unknown",
+            }
+            .to_string()
+        }),
+        bracket_shot_number: get(5),
+        raw_jpg_quality: get(6).map(get_quality),
+        raw_jpg_size: get(7).map(get_image_size),
+        live_view_shooting: get(19).map(|v| v == 1),
+        focus_distance_upper: get(20).and_then(get_distance),
+        focus_distance_lower: get(21).and_then(get_distance),
+        shutter_mode: get(23).map(|v| {
+            match v {
+                0 => "Mechanical",
+                1 => "Electronic First Curtain",
+                2 => "Electronic",
+                _ => "Unknown",
+            }
+            .to_string()
+        }),
+        rf_lens_type: get(61).map(|v| v as u16).filter(|v| *v != 0),
+    }
+}
+
+fn get_color_data(values: Vec<u16>) -> CanonColorData {
+    // The layout is identified by the number of values, the white balance as shot is followed
+    // by the color temperature as shot
+    let (version, wb_offset) = match values.len() {
+        582 => (1, Some(0x19)),
+        653 => (2, Some(0x18)),
+        796 => (3, Some(0x3F)),
+        674 | 692 | 702 | 1227 | 1250 | 1251 | 1337 | 1338 | 1346 => (4, Some(0x3F)),
+        5120 => (5, Some(0x47)),
+        1273 | 1275 => (6, Some(0x3F)),
+        1312 | 1313 | 1316 | 1506 => (7, Some(0x3F)),
+        1353 | 1560 | 1592 | 1602 => (8, Some(0x3F)),
+        1816 | 1820 | 1824 => (9, Some(0x47)),
+        2024 | 3656 => (10, Some(0x55)),
+        3778 | 3973 => (11, Some(0x69)),
+        _ => (0, None),
+    };
+
+    let wb_rggb_levels_as_shot =
+        wb_offset.and_then(|offset| values.get(offset..offset + 4)?.try_into().ok());
+    let color_temp_as_shot = wb_offset.and_then(|offset| values.get(offset + 4).copied());
+
+    CanonColorData {
+        version,
+        wb_rggb_levels_as_shot,
+        color_temp_as_shot,
+        data: values,
+    }
+}
+
+fn get_shorts(entry: &IFDEntry) -> Result<Vec<i16>, TiffError> {
+    entry
+        .values
+        .iter()
+        .map(|v| match v {
+            IFDEntryValue::SHORT(v) => Ok(*v as i16),
+            IFDEntryValue::SSHORT(v) => Ok(*v),
+            _ => Err(TiffError(format!(
+                "[Canon tag {}] Expected SHORT/SSHORT values (got {:?})",
+                entry.tag, v
+            ))),
+        })
+        .collect()
+}
+
+fn decode_canon_entry(
+    entry: &IFDEntry,
+    model: &str,
+    focal_units: u16,
+) -> Result<CanonTag, TiffError> {
+    let tag = match entry.tag {
+        0x0001 => CanonTag::CameraSettings(get_camera_settings(&get_shorts(entry)?)),
+        0x0002 => {
+            let values = get_shorts(entry)?;
+            if values.len() < 2 {
+                return Err(TiffError(format!(
+                    "[Canon tag 2] Expected at least 2 values (got {})",
+                    values.len()
+                )));
+            }
+            // The focal plane size is in 1/1000 inch
+            let focal_plane_size = |index: usize| {
+                values
+                    .get(index)
+                    .filter(|v| **v != 0)
+                    .map(|v| *v as u16 as f64 * 25.4 / 1000.0)
+            };
+
+            CanonTag::FocalLength(CanonFocalLength {
+                focal_type: match values[0] {
+                    1 => "Fixed",
+                    2 => "Zoom",
+                    _ => "Unknown",
+                }
+                .to_string(),
+                focal_length: values[1] as u16 as f64 / focal_units as f64,
+                focal_plane_x_size: focal_plane_size(2),
+                focal_plane_y_size: focal_plane_size(3),
+            })
+        }
+        0x0004 => CanonTag::ShotInfo(get_shot_info(&get_shorts(entry)?)),
+        0x0006 => CanonTag::ImageType(get_string(entry)?),
+        0x0007 => CanonTag::FirmwareVersion(get_string(entry)?),
+        0x0008 => {
+            let value: u32 = entry.clone().try_into()?;
+            CanonTag::FileNumber(format!("{:03}-{:04}", value / 10000, value % 10000))
+        }
+        0x0009 => CanonTag::OwnerName(get_string(entry)?),
+        0x000C => {
+            let value: u32 = entry.clone().try_into()?;
+            CanonTag::SerialNumber(match model.contains("EOS-1D") {
+                true => format!("{:06}", value),
+                false => format!("{:010}", value),
+            })
+        }
+        0x0010 => CanonTag::ModelId(entry.clone().try_into()?),
+        0x0028 => {
+            let value: Vec<u8> = entry.clone().try_into()?;
+            CanonTag::ImageUniqueId(value.iter().map(|b| format!("{:02x}", b)).collect())
+        }
+        0x0093 => CanonTag::FileInfo(get_file_info(&get_shorts(entry)?, model)),
+        0x0095 => CanonTag::LensModel(get_string(entry)?),
+        0x0096 => CanonTag::InternalSerialNumber(get_string(entry)?),
+        0x00B4 => CanonTag::ColorSpace(
+            match <IFDEntry as TryInto<u16>>::try_into(entry.clone())? {
+                1 => "sRGB",
+                2 => "Adobe RGB",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        0x4001 => CanonTag::ColorData(get_color_data(entry.clone().try_into()?)),
+        _ => CanonTag::Unknown(entry.clone()),
+    };
+
+    Ok(tag)
+}
+
+/// Decodes the entries of a Canon makernote.
+pub(crate) fn decode_canon_tags(entries: &[IFDEntry], model: &str) -> Vec<CanonTag> {
+    // The focal lengths are in FocalUnits per millimeter, from the CameraSettings
+    let focal_units = entries
+        .iter()
+        .find(|entry| entry.tag == 0x0001)
+        .and_then(|entry| get_shorts(entry).ok())
+        .and_then(|values| values.get(25).map(|v| *v as u16))
+        .filter(|v| *v > 0)
+        .unwrap_or(1);
+
+    decode_entries(entries, |entry| {
+        decode_canon_entry(entry, model, focal_units)
+    })
+}
+
+/// The name of a Canon lens from the LensType in the CameraSettings. Some IDs are shared with
+/// third-party lenses, in which case this is the Canon lens.
+///
+/// This is a partial port of ExifTool's table: it only has Canon's own EF, EF-S, EF-M, TS-E,
+/// MP-E and CN-E lenses, and leaves out the third-party lenses and the decimal IDs ExifTool uses
+/// to tell lenses sharing an ID apart. Lenses which aren't listed are described by their focal
+/// length range by [`canon_lens`].
+pub fn canon_lens_name(lens_type: u16) -> Option<&'static str> {
+    let name = match lens_type {
+        1 => "Canon EF 50mm f/1.8",
+        2 => "Canon EF 28mm f/2.8",
+        3 => "Canon EF 135mm f/2.8 Soft",
+        4 => "Canon EF 35-105mm f/3.5-4.5",
+        5 => "Canon EF 35-70mm f/3.5-4.5",
+        6 => "Canon EF 28-70mm f/3.5-4.5",
+        7 => "Canon EF 100-300mm f/5.6L",
+        9 => "Canon EF 70-210mm f/4",
+        10 => "Canon EF 50mm f/2.5 Macro",
+        11 => "Canon EF 35mm f/2",
+        13 => "Canon EF 15mm f/2.8 Fisheye",
+        14 => "Canon EF 50-200mm f/3.5-4.5L",
+        15 => "Canon EF 50-200mm f/3.5-4.5",
+        16 => "Canon EF 35-135mm f/3.5-4.5",
+        17 => "Canon EF 35-70mm f/3.5-4.5A",
+        18 => "Canon EF 28-70mm f/3.5-4.5",
+        20 => "Canon EF 100-200mm f/4.5A",
+        21 => "Canon EF 80-200mm f/2.8L",
+        22 => "Canon EF 20-35mm f/2.8L",
+        23 => "Canon EF 35-105mm f/3.5-4.5",
+        24 | 25 => "Canon EF 35-80mm f/4-5.6 Power Zoom",
+        26 => "Canon EF 100mm f/2.8 Macro",
+        28 => "Canon EF 80-200mm f/4.5-5.6",
+        29 => "Canon EF 50mm f/1.8 II",
+        30 => "Canon EF 35-105mm f/4.5-5.6",
+        31 => "Canon EF 75-300mm f/4-5.6",
+        32 => "Canon EF 24mm f/2.8",
+        35 => "Canon EF 35-80mm f/4-5.6",
+        36 => "Canon EF 38-76mm f/4.5-5.6",
+        37 => "Canon EF 35-80mm f/4-5.6",
+        38 => "Canon EF 80-200mm f/4.5-5.6 II",
+        39 => "Canon EF 75-300mm f/4-5.6",
+        40 => "Canon EF 28-80mm f/3.5-5.6",
+        41 => "Canon EF 28-90mm f/4-5.6",
+        42 => "Canon EF 28-200mm f/3.5-5.6",
+        43 => "Canon EF 28-105mm f/4-5.6",
+        44 => "Canon EF 90-300mm f/4.5-5.6",
+        45 => "Canon EF-S 18-55mm f/3.5-5.6",
+        46 => "Canon EF 28-90mm f/4-5.6",
+        48 => "Canon EF-S 18-55mm f/3.5-5.6 IS",
+        49 => "Canon EF-S 55-250mm f/4-5.6 IS",
+        50 => "Canon EF-S 18-200mm f/3.5-5.6 IS",
+        51 => "Canon EF-S 18-135mm f/3.5-5.6 IS",
+        52 => "Canon EF-S 18-55mm f/3.5-5.6 IS II",
+        53 => "Canon EF-S 18-55mm f/3.5-5.6 III",
+        54 => "Canon EF-S 55-250mm f/4-5.6 IS II",
+        94 => "Canon TS-E 17mm f/4L",
+        95 => "Canon TS-E 24mm f/3.5L II",
+        124 => "Canon MP-E 65mm f/2.8 1-5x Macro Photo",
+        125 => "Canon TS-E 24mm f/3.5L",
+        126 => "Canon TS-E 45mm f/2.8",
+        127 => "Canon TS-E 90mm f/2.8",
+        129 => "Canon EF 300mm f/2.8L USM",
+        130 => "Canon EF 50mm f/1.0L USM",
+        131 => "Canon EF 28-80mm f/2.8-4L USM",
+        132 => "Canon EF 1200mm f/5.6L USM",
+        134 => "Canon EF 600mm f/4L IS USM",
+        135 => "Canon EF 200mm f/1.8L USM",
+        136 => "Canon EF 300mm f/2.8L USM",
+        137 => "Canon EF 85mm f/1.2L USM",
+        138 => "Canon EF 28-80mm f/2.8-4L",
+        139 => "Canon EF 400mm f/2.8L USM",
+        140 | 141 => "Canon EF 500mm f/4.5L USM",
+        142 => "Canon EF 300mm f/2.8L IS USM",
+        143 => "Canon EF 500mm f/4L IS USM",
+        144 => "Canon EF 35-135mm f/4-5.6 USM",
+        145 => "Canon EF 100-300mm f/4.5-5.6 USM",
+        146 => "Canon EF 70-210mm f/3.5-4.5 USM",
+        147 => "Canon EF 35-135mm f/4-5.6 USM",
+        148 => "Canon EF 28-80mm f/3.5-5.6 USM",
+        149 => "Canon EF 100mm f/2 USM",
+        150 => "Canon EF 14mm f/2.8L USM",
+        151 => "Canon EF 200mm f/2.8L USM",
+        152 => "Canon EF 300mm f/4L IS USM",
+        153 => "Canon EF 35-350mm f/3.5-5.6L USM",
+        154 => "Canon EF 20mm f/2.8 USM",
+        155 => "Canon EF 85mm f/1.8 USM",
+        156 => "Canon EF 28-105mm f/3.5-4.5 USM",
+        160 => "Canon EF 20-35mm f/3.5-4.5 USM",
+        161 => "Canon EF 28-70mm f/2.8L USM",
+        162 => "Canon EF 200mm f/2.8L USM",
+        163 => "Canon EF 300mm f/4L",
+        164 => "Canon EF 400mm f/5.6L",
+        165 => "Canon EF 70-200mm f/2.8L USM",
+        166 => "Canon EF 70-200mm f/2.8L USM + 1.4x",
+        167 => "Canon EF 70-200mm f/2.8L USM + 2x",
+        168 => "Canon EF 28mm f/1.8 USM",
+        169 => "Canon EF 17-35mm f/2.8L USM",
+        170 => "Canon EF 200mm f/2.8L II USM",
+        171 => "Canon EF 300mm f/4L USM",
+        172 => "Canon EF 400mm f/5.6L USM",
+        173 => "Canon EF 180mm Macro f/3.5L USM",
+        174 => "Canon EF 135mm f/2L USM",
+        175 => "Canon EF 400mm f/2.8L USM",
+        176 => "Canon EF 24-85mm f/3.5-4.5 USM",
+        177 => "Canon EF 300mm f/4L IS USM",
+        178 => "Canon EF 28-135mm f/3.5-5.6 IS",
+        179 => "Canon EF 24mm f/1.4L USM",
+        180 => "Canon EF 35mm f/1.4L USM",
+        181 => "Canon EF 100-400mm f/4.5-5.6L IS USM + 1.4x",
+        182 => "Canon EF 100-400mm f/4.5-5.6L IS USM + 2x",
+        183 => "Canon EF 100-400mm f/4.5-5.6L IS USM",
+        184 => "Canon EF 400mm f/2.8L USM + 2x",
+        185 => "Canon EF 600mm f/4L IS USM",
+        186 => "Canon EF 70-200mm f/4L USM",
+        187 => "Canon EF 70-200mm f/4L USM + 1.4x",
+        188 => "Canon EF 70-200mm f/4L USM + 2x",
+        189 => "Canon EF 70-200mm f/4L USM + 2.8x",
+        190 => "Canon EF 100mm f/2.8 Macro USM",
+        191 => "Canon EF 400mm f/4 DO IS",
+        193 => "Canon EF 35-80mm f/4-5.6 USM",
+        194 => "Canon EF 80-200mm f/4.5-5.6 USM",
+        195 => "Canon EF 35-105mm f/4.5-5.6 USM",
+        196 | 200 => "Canon EF 75-300mm f/4-5.6 USM",
+        197 => "Canon EF 75-300mm f/4-5.6 IS USM",
+        198 => "Canon EF 50mm f/1.4 USM",
+        199 | 201 => "Canon EF 28-80mm f/3.5-5.6 USM",
+        202 => "Canon EF 28-80mm f/3.5-5.6 USM IV",
+        208 => "Canon EF 22-55mm f/4-5.6 USM",
+        209 => "Canon EF 55-200mm f/4.5-5.6",
+        210 => "Canon EF 28-90mm f/4-5.6 USM",
+        211 => "Canon EF 28-200mm f/3.5-5.6 USM",
+        212 => "Canon EF 28-105mm f/4-5.6 USM",
+        213 => "Canon EF 90-300mm f/4.5-5.6 USM",
+        214 => "Canon EF-S 18-55mm f/3.5-5.6 USM",
+        215 => "Canon EF 55-200mm f/4.5-5.6 II USM",
+        224 => "Canon EF 70-200mm f/2.8L IS USM",
+        225 => "Canon EF 70-200mm f/2.8L IS USM + 1.4x",
+        226 => "Canon EF 70-200mm f/2.8L IS USM + 2x",
+        227 => "Canon EF 70-200mm f/2.8L IS USM + 2.8x",
+        228 => "Canon EF 28-105mm f/3.5-4.5 USM",
+        229 => "Canon EF 16-35mm f/2.8L USM",
+        230 => "Canon EF 24-70mm f/2.8L USM",
+        231 => "Canon EF 17-40mm f/4L USM",
+        232 => "Canon EF 70-300mm f/4.5-5.6 DO IS USM",
+        233 => "Canon EF 28-300mm f/3.5-5.6L IS USM",
+        234 => "Canon EF-S 17-85mm f/4-5.6 IS USM",
+        235 => "Canon EF-S 10-22mm f/3.5-4.5 USM",
+        236 => "Canon EF-S 60mm f/2.8 Macro USM",
+        237 => "Canon EF 24-105mm f/4L IS USM",
+        238 => "Canon EF 70-300mm f/4-5.6 IS USM",
+        239 => "Canon EF 85mm f/1.2L II USM",
+        240 => "Canon EF-S 17-55mm f/2.8 IS USM",
+        241 => "Canon EF 50mm f/1.2L USM",
+        242 => "Canon EF 70-200mm f/4L IS USM",
+        243 => "Canon EF 70-200mm f/4L IS USM + 1.4x",
+        244 => "Canon EF 70-200mm f/4L IS USM + 2x",
+        245 => "Canon EF 70-200mm f/4L IS USM + 2.8x",
+        246 => "Canon EF 16-35mm f/2.8L II USM",
+        247 => "Canon EF 14mm f/2.8L II USM",
+        248 => "Canon EF 200mm f/2L IS USM",
+        249 => "Canon EF 800mm f/5.6L IS USM",
+        250 => "Canon EF 24mm f/1.4L II USM",
+        251 => "Canon EF 70-200mm f/2.8L IS II USM",
+        252 => "Canon EF 70-200mm f/2.8L IS II USM + 1.4x",
+        253 => "Canon EF 70-200mm f/2.8L IS II USM + 2x",
+        254 => "Canon EF 100mm f/2.8L Macro IS USM",
+        488 => "Canon EF-S 15-85mm f/3.5-5.6 IS USM",
+        489 => "Canon EF 70-300mm f/4-5.6L IS USM",
+        490 => "Canon EF 8-15mm f/4L Fisheye USM",
+        491 => "Canon EF 300mm f/2.8L IS II USM",
+        492 => "Canon EF 400mm f/2.8L IS II USM",
+        493 => "Canon EF 500mm f/4L IS II USM",
+        494 => "Canon EF 600mm f/4L IS II USM",
+        495 => "Canon EF 24-70mm f/2.8L II USM",
+        496 => "Canon EF 200-400mm f/4L IS USM",
+        499 => "Canon EF 200-400mm f/4L IS USM + 1.4x",
+        502 => "Canon EF 28mm f/2.8 IS USM",
+        503 => "Canon EF 24mm f/2.8 IS USM",
+        504 => "Canon EF 24-70mm f/4L IS USM",
+        505 => "Canon EF 35mm f/2 IS USM",
+        506 => "Canon EF 400mm f/4 DO IS II USM",
+        507 => "Canon EF 16-35mm f/4L IS USM",
+        508 => "Canon EF 11-24mm f/4L USM",
+        747 => "Canon EF 100-400mm f/4.5-5.6L IS II USM",
+        748 => "Canon EF 100-400mm f/4.5-5.6L IS II USM + 1.4x",
+        750 => "Canon EF 35mm f/1.4L II USM",
+        751 => "Canon EF 16-35mm f/2.8L III USM",
+        752 => "Canon EF 24-105mm f/4L IS II USM",
+        753 => "Canon EF 85mm f/1.4L IS USM",
+        754 => "Canon EF 70-200mm f/4L IS II USM",
+        757 => "Canon EF 400mm f/2.8L IS III USM",
+        758 => "Canon EF 600mm f/4L IS III USM",
+        4142 => "Canon EF-S 18-135mm f/3.5-5.6 IS STM",
+        4143 => "Canon EF-M 18-55mm f/3.5-5.6 IS STM",
+        4144 => "Canon EF 40mm f/2.8 STM",
+        4145 => "Canon EF-M 22mm f/2 STM",
+        4146 => "Canon EF-S 18-55mm f/3.5-5.6 IS STM",
+        4147 => "Canon EF-M 11-22mm f/4-5.6 IS STM",
+        4148 => "Canon EF-S 55-250mm f/4-5.6 IS STM",
+        4149 => "Canon EF-M 55-200mm f/4.5-6.3 IS STM",
+        4150 => "Canon EF-S 10-18mm f/4.5-5.6 IS STM",
+        4152 => "Canon EF 24-105mm f/3.5-5.6 IS STM",
+        4153 => "Canon EF-M 15-45mm f/3.5-6.3 IS STM",
+        4154 => "Canon EF-S 24mm f/2.8 STM",
+        4155 => "Canon EF-M 28mm f/3.5 Macro IS STM",
+        4156 => "Canon EF 50mm f/1.8 STM",
+        4157 => "Canon EF-M 18-150mm f/3.5-6.3 IS STM",
+        4158 => "Canon EF-S 18-55mm f/4-5.6 IS STM",
+        4159 => "Canon EF-M 32mm f/1.4 STM",
+        4160 => "Canon EF-S 35mm f/2.8 Macro IS STM",
+        36910 => "Canon EF 70-300mm f/4-5.6 IS II USM",
+        36912 => "Canon EF-S 18-135mm f/3.5-5.6 IS USM",
+        61491 => "Canon CN-E 14mm T3.1 L F",
+        61492 => "Canon CN-E 24mm T1.5 L F",
+        61494 => "Canon CN-E 85mm T1.3 L F",
+        61495 => "Canon CN-E 135mm T2.2 L F",
+        61496 => "Canon CN-E 35mm T1.5 L F",
+        _ => return None,
+    };
+
+    Some(name)
+}
+
+/// The name of an RF lens from the RFLensType in the FileInfo. This only covers the first RF
+/// lenses of ExifTool's table, newer ones are described by their focal length range.
+pub fn canon_rf_lens_name(rf_lens_type: u16) -> Option<&'static str> {
+    let name = match rf_lens_type {
+        257 => "Canon RF 50mm F1.2L USM",
+        258 => "Canon RF 24-105mm F4L IS USM",
+        259 => "Canon RF 28-70mm F2L USM",
+        260 => "Canon RF 35mm F1.8 MACRO IS STM",
+        261 => "Canon RF 85mm F1.2L USM",
+        262 => "Canon RF 85mm F1.2L USM DS",
+        263 => "Canon RF 24-70mm F2.8L IS USM",
+        264 => "Canon RF 15-35mm F2.8L IS USM",
+        265 => "Canon RF 24-240mm F4-6.3 IS USM",
+        266 => "Canon RF 70-200mm F2.8L IS USM",
+        267 => "Canon RF 85mm F2 MACRO IS STM",
+        268 => "Canon RF 600mm F11 IS STM",
+        269 => "Canon RF 600mm F11 IS STM + RF1.4x",
+        270 => "Canon RF 600mm F11 IS STM + RF2x",
+        271 => "Canon RF 800mm F11 IS STM",
+        272 => "Canon RF 800mm F11 IS STM + RF1.4x",
+        273 => "Canon RF 800mm F11 IS STM + RF2x",
+        274 => "Canon RF 24-105mm F4-7.1 IS STM",
+        275 => "Canon RF 100-500mm F4.5-7.1L IS USM",
+        276 => "Canon RF 100-500mm F4.5-7.1L IS USM + RF1.4x",
+        277 => "Canon RF 100-500mm F4.5-7.1L IS USM + RF2x",
+        278 => "Canon RF 70-200mm F4L IS USM",
+        279 => "Canon RF 100mm F2.8L MACRO IS USM",
+        280 => "Canon RF 50mm F1.8 STM",
+        281 => "Canon RF 14-35mm F4L IS USM",
+        282 => "Canon RF-S 18-45mm F4.5-6.3 IS STM",
+        283 => "Canon RF 100-400mm F5.6-8 IS USM",
+        284 => "Canon RF 100-400mm F5.6-8 IS USM + RF1.4x",
+        285 => "Canon RF 100-400mm F5.6-8 IS USM + RF2x",
+        286 => "Canon RF-S 18-150mm F3.5-6.3 IS STM",
+        287 => "Canon RF 24mm F1.8 MACRO IS STM",
+        288 => "Canon RF 16mm F2.8 STM",
+        _ => return None,
+    };
+
+    Some(name)
+}
+
+/// Identifies the lens from the decoded tags, preferring the LensModel written by newer models.
+/// Unknown lenses are described by their focal length range, e.g. "Unknown 18-55mm".
+pub fn canon_lens(tags: &[CanonTag]) -> Option<String> {
+    let mut settings: Option<&CanonCameraSettings> = None;
+    let mut rf_lens_type: Option<u16> = None;
+    for tag in tags {
+        match tag {
+            CanonTag::LensModel(model) if !model.is_empty() => return Some(model.clone()),
+            CanonTag::CameraSettings(s) => settings = Some(s),
+            CanonTag::FileInfo(info) => rf_lens_type = info.rf_lens_type,
+            _ => {}
+        }
+    }
+    let settings = settings?;
+
+    // 61182 means an RF lens, whose type is in the FileInfo
+    let name = match settings.lens_type {
+        Some(61182) => rf_lens_type.and_then(canon_rf_lens_name),
+        Some(lens_type) => canon_lens_name(lens_type),
+        None => None,
+    };
+    if let Some(name) = name {
+        return Some(name.to_string());
+    }
+
+    match (settings.min_focal_length, settings.max_focal_length) {
+        (Some(min), Some(max)) if (min - max).abs() < 0.5 => Some(format!("Unknown {:.0}mm", min)),
+        (Some(min), Some(max)) => Some(format!("Unknown {:.0}-{:.0}mm", min, max)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        canon_ev, canon_lens, canon_lens_name, canon_rf_lens_name, get_file_info, CanonTag,
+    };
+    use crate::{
+        get_tag_value,
+        makernote::{read_makernote, MakerNoteTags, MakerNoteVendor, TestIfd},
+        utils::Endianness,
+    };
+
+    #[test]
+    fn test_canon_ev() {
+        assert_eq!(canon_ev(0), 0.0);
+        assert_eq!(canon_ev(0x20), 1.0);
+        assert_eq!(canon_ev(0x2C), 1.0 + 1.0 / 3.0);
+        assert_eq!(canon_ev(-0x34), -(1.0 + 2.0 / 3.0));
+        assert_eq!(canon_ev(0x30), 1.5);
+    }
+
+    #[test]
+    fn test_canon_makernote() {
+        let mut camera_settings = [0_i16; 46];
+        camera_settings[0] = 92; // The size in bytes
+        camera_settings[1] = 2; // Normal
+        camera_settings[2] = 100; // 10 s
+        camera_settings[3] = 4; // RAW
+        camera_settings[7] = 1; // AI Servo AF
+        camera_settings[20] = 3; // Aperture-priority AE
+        camera_settings[22] = 237; // EF 24-105mm f/4L IS USM
+        camera_settings[23] = 105;
+        camera_settings[24] = 24;
+        camera_settings[25] = 1;
+        camera_settings[26] = 0x40; // f/2.0
+        camera_settings[34] = 1;
+
+        let mut shot_info = [0_i16; 34];
+        shot_info[1] = 64; // ISO 400
+        shot_info[7] = 1; // Daylight
+        shot_info[19] = 0x7FFF;
+        shot_info[20] = 350;
+        shot_info[21] = 0x60; // f/2.8
+        shot_info[22] = 0xC0; // 1/64 s
+
+        let mut file_info = [0_i16; 20];
+        file_info[1] = 1;
+        file_info[2] = 0x0002; // 65538 with the words swapped
+        file_info[3] = 1; // AEB
+
+        let mut color_data = vec![0_u16; 796];
+        color_data[0x3F..0x44].copy_from_slice(&[2048, 1024, 1024, 1500, 5200]);
+
+        let shorts = |values: &[i16]| values.iter().map(|v| *v as u16).collect::<Vec<u16>>();
+        let mut tiff_data = b"II*\0\x08\0\0\0".to_vec();
+        tiff_data.extend(
+            TestIfd::new(Endianness::Little)
+                .add_shorts(0x0001, &shorts(&camera_settings))
+                .add_shorts(0x0002, &[2, 50, 1417, 944])
+                .add_shorts(0x0004, &shorts(&shot_info))
+                .add_ascii(0x0006, "Canon EOS-1D Mark II")
+                .add_ascii(0x0007, "Firmware Version 1.2.4")
+                .add_longs(0x0008, &[1001234])
+                .add_longs(0x000C, &[123456])
+                .add_longs(0x0010, &[0x80000174])
+                .add_shorts(0x0093, &shorts(&file_info))
+                .add_shorts(0x4001, &color_data)
+                .add_shorts(0x9999, &[1])
+                .build(8),
+        );
+
+        let makernote = read_makernote(
+            &tiff_data,
+            8,
+            tiff_data[8..].to_vec(),
+            ("Canon", "Canon EOS-1D Mark II"),
+            Endianness::Little,
+        );
+        assert_eq!(makernote.vendor, MakerNoteVendor::Canon);
+        let tags = match &makernote.tags {
+            MakerNoteTags::Canon(tags) => tags,
+            other => panic!("Expected Canon tags (got {:?})", other),
+        };
+
+        let settings = get_tag_value!(tags, CanonTag::CameraSettings).unwrap();
+        assert_eq!(settings.macro_mode.as_deref(), Some("Normal"));
+        assert_eq!(settings.self_timer.as_deref(), Some("10 s"));
+        assert_eq!(settings.quality.as_deref(), Some("RAW"));
+        assert_eq!(settings.focus_mode.as_deref(), Some("AI Servo AF"));
+        assert_eq!(
+            settings.exposure_mode.as_deref(),
+            Some("Aperture-priority AE")
+        );
+        assert_eq!(settings.max_aperture, Some(2.0));
+        assert_eq!(settings.image_stabilization.as_deref(), Some("On"));
+        assert_eq!(canon_lens(tags).unwrap(), "Canon EF 24-105mm f/4L IS USM");
+
+        let focal_length = get_tag_value!(tags, CanonTag::FocalLength).unwrap();
+        assert_eq!(focal_length.focal_type, "Zoom");
+        assert_eq!(focal_length.focal_length, 50.0);
+        assert_eq!(
+            focal_length.focal_plane_x_size.map(|v| v.round()),
+            Some(36.0)
+        );
+
+        let shot_info = get_tag_value!(tags, CanonTag::ShotInfo).unwrap();
+        assert_eq!(shot_info.auto_iso, Some(400.0));
+        assert_eq!(shot_info.white_balance.as_deref(), Some("Daylight"));
+        assert_eq!(shot_info.focus_distance_upper, Some(327.67));
+        assert_eq!(shot_info.focus_distance_lower, Some(3.5));
+        assert_eq!(shot_info.f_number.map(|v| (v * 10.0).round()), Some(28.0));
+        assert_eq!(shot_info.exposure_time, Some(1.0 / 64.0));
+
+        assert_eq!(
+            get_tag_value!(tags, CanonTag::FileNumber).unwrap(),
+            "100-1234"
+        );
+        assert_eq!(
+            get_tag_value!(tags, CanonTag::SerialNumber).unwrap(),
+            "123456"
+        );
+        assert_eq!(get_tag_value!(tags, CanonTag::ModelId), Some(&0x80000174));
+        assert_eq!(
+            get_tag_value!(tags, CanonTag::FirmwareVersion).unwrap(),
+            "Firmware Version 1.2.4"
+        );
+
+        let file_info = get_tag_value!(tags, CanonTag::FileInfo).unwrap();
+        assert_eq!(file_info.shutter_count, Some(65538));
+        assert_eq!(file_info.bracket_mode.as_deref(), Some("AEB"));
+
+        // The Mark III models don't store a shutter count in FileInfo
+        let file_info = get_file_info(&[0, 1, 2, 1], "Canon EOS-1D Mark III");
+        assert_eq!(file_info.shutter_count, None);
+        let file_info = get_file_info(&[0, 1, 2, 1], "Canon EOS-1Ds Mark II");
+        assert_eq!(file_info.shutter_count, Some(65538));
+
+        let color_data = get_tag_value!(tags, CanonTag::ColorData).unwrap();
+        assert_eq!(color_data.version, 3);
+        assert_eq!(
+            color_data.wb_rggb_levels_as_shot,
+            Some([2048, 1024, 1024, 1500])
+        );
+        assert_eq!(color_data.color_temp_as_shot, Some(5200));
+
+        let unknown = makernote.unknown_entries();
+        assert_eq!(unknown.len(), 1);
+        assert_eq!(unknown[0].tag, 0x9999);
+    }
+
+    #[test]
+    fn test_unknown_lens() {
+        let mut camera_settings = [0_i16; 30];
+        camera_settings[22] = 0xFFFF_u16 as i16;
+        camera_settings[23] = 600;
+        camera_settings[24] = 150;
+        camera_settings[25] = 1;
+
+        let tags = vec![CanonTag::CameraSettings(super::get_camera_settings(
+            &camera_settings,
+        ))];
+        assert_eq!(canon_lens(&tags).unwrap(), "Unknown 150-600mm");
+
+        let tags = vec![
+            CanonTag::CameraSettings(super::get_camera_settings(&camera_settings)),
+            CanonTag::LensModel("RF24-105mm F4 L IS USM".to_string()),
+        ];
+        assert_eq!(canon_lens(&tags).unwrap(), "RF24-105mm F4 L IS USM");
+    }
+
+    #[test]
+    fn test_lens_names() {
+        assert_eq!(canon_lens_name(495), Some("Canon EF 24-70mm f/2.8L II USM"));
+        assert_eq!(
+            canon_lens_name(36910),
+            Some("Canon EF 70-300mm f/4-5.6 IS II USM")
+        );
+        assert_eq!(canon_lens_name(255), None);
+        assert_eq!(canon_rf_lens_name(280), Some("Canon RF 50mm F1.8 STM"));
+        assert_eq!(canon_rf_lens_name(61182), None);
+    }
+}
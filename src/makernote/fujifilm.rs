@@ -0,0 +1,365 @@
+// See <https://exiftool.org/TagNames/FujiFilm.html>
+
+use crate::{
+    get_tag_value,
+    tiff::{IFDEntry, IFDEntryValue, Tiff, TiffError},
+};
+
+use super::{decode_entries, get_integer, get_string, get_u16, get_version, MakerNoteTags};
+
+/// Tags of Fujifilm makernotes
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub enum FujifilmTag {
+    Unknown(IFDEntry),
+
+    Version(String),
+    InternalSerialNumber(String),
+    Quality(String),
+    Sharpness(String),
+    WhiteBalance(String),
+    Saturation(String), // Also holds the monochrome film simulations
+    Contrast(String),
+    ColorTemperature(u16),
+    WhiteBalanceFineTune(String), // e.g. "Red +1, Blue -2"
+    FocusMode(String),
+    GrainEffectRoughness(String),
+    ColorChromeEffect(String),
+    GrainEffectSize(String),
+    ColorChromeFxBlue(String),
+    DynamicRange(String),
+    FilmMode(String),
+    DynamicRangeSetting(String),
+    DevelopmentDynamicRange(u16), // Percent
+    AutoDynamicRange(u16),        // Percent
+}
+
+/// Weak or strong, shared by the grain and color chrome effects
+fn get_effect_strength(entry: &IFDEntry) -> Result<String, TiffError> {
+    let strength = match get_integer(entry)? {
+        0 => "Off",
+        32 => "Weak",
+        64 => "Strong",
+        _ => "Unknown",
+    };
+
+    Ok(strength.to_string())
+}
+
+/// Newer models store the fine-tune in steps of 20
+fn get_white_balance_fine_tune(entry: &IFDEntry) -> Result<String, TiffError> {
+    let values: Vec<i64> = entry
+        .values
+        .iter()
+        .filter_map(|v| match v {
+            IFDEntryValue::SLONG(v) => Some(*v as i64),
+            IFDEntryValue::LONG(v) => Some(*v as i64),
+            _ => None,
+        })
+        .collect();
+
+    match values.as_slice() {
+        [red, blue] => Ok(format!("Red {:+}, Blue {:+}", red / 20, blue / 20)),
+        _ => Err(TiffError(format!(
+            "[Fujifilm tag {}] Expected 2 integers",
+            entry.tag
+        ))),
+    }
+}
+
+fn decode_fujifilm_entry(entry: &IFDEntry) -> Result<FujifilmTag, TiffError> {
+    let tag = match entry.tag {
+        0x0000 => FujifilmTag::Version(get_version(entry)?),
+        0x0010 => FujifilmTag::InternalSerialNumber(get_string(entry)?),
+        0x1000 => FujifilmTag::Quality(get_string(entry)?),
+        0x1001 => FujifilmTag::Sharpness(
+            match get_u16(entry)? {
+                0x00 => "-4 (softest)",
+                0x01 => "-3 (very soft)",
+                0x02 => "-2 (soft)",
+                0x03 => "0 (normal)",
+                0x04 => "+2 (hard)",
+                0x05 => "+3 (very hard)",
+                0x06 => "+4 (hardest)",
+                0x82 => "-1 (medium soft)",
+                0x84 => "+1 (medium hard)",
+                0x8000 => "Film Simulation",
+                0xFFFF => "n/a",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        0x1002 => FujifilmTag::WhiteBalance(
+            match get_u16(entry)? {
+                0x000 => "Auto",
+                0x001 => "Auto (white priority)",
+                0x002 => "Auto (ambiance priority)",
+                0x100 => "Daylight",
+                0x200 => "Cloudy",
+                0x300 => "Daylight Fluorescent",
+                0x301 => "Day White Fluorescent",
+                0x302 => "White Fluorescent",
+                0x303 => "Warm White Fluorescent",
+                0x304 => "Living Room Warm White Fluorescent",
+                0x400 => "Incandescent",
+                0x500 => "Flash",
+                0x600 => "Underwater",
+                0xF00 => "Custom",
+                0xF01 => "Custom2",
+                0xF02 => "Custom3",
+                0xF03 => "Custom4",
+                0xF04 => "Custom5",
+                0xFF0 => "Kelvin",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        0x1003 => FujifilmTag::Saturation(
+            match get_u16(entry)? {
+                0x000 => "0 (normal)",
+                0x080 => "+1 (medium high)",
+                0x0C0 => "+3 (very high)",
+                0x0E0 => "+4 (highest)",
+                0x100 => "+2 (high)",
+                0x180 => "-1 (medium low)",
+                0x200 => "Low",
+                0x300 => "None (B&W)",
+                0x301 => "B&W Red Filter",
+                0x302 => "B&W Yellow Filter",
+                0x303 => "B&W Green Filter",
+                0x310 => "B&W Sepia",
+                0x400 => "-2 (low)",
+                0x4C0 => "-3 (very low)",
+                0x4E0 => "-4 (lowest)",
+                0x500 => "Acros",
+                0x501 => "Acros Red Filter",
+                0x502 => "Acros Yellow Filter",
+                0x503 => "Acros Green Filter",
+                0x8000 => "Film Simulation",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        0x1004 => FujifilmTag::Contrast(
+            match get_u16(entry)? {
+                0x000 => "0 (normal)",
+                0x080 => "+1 (medium high)",
+                0x100 => "+2 (high)",
+                0x180 => "-1 (medium low)",
+                0x200 => "Low",
+                0x8000 => "Film Simulation",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        0x1005 => FujifilmTag::ColorTemperature(get_u16(entry)?),
+        0x100A => FujifilmTag::WhiteBalanceFineTune(get_white_balance_fine_tune(entry)?),
+        0x1021 => FujifilmTag::FocusMode(
+            match get_u16(entry)? {
+                0 => "Auto",
+                1 => "Manual",
+                65535 => "Movie",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        0x1047 => FujifilmTag::GrainEffectRoughness(get_effect_strength(entry)?),
+        0x1048 => FujifilmTag::ColorChromeEffect(get_effect_strength(entry)?),
+        0x104C => FujifilmTag::GrainEffectSize(
+            match get_integer(entry)? {
+                0 => "Off",
+                16 => "Small",
+                32 => "Large",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        0x104E => FujifilmTag::ColorChromeFxBlue(get_effect_strength(entry)?),
+        0x1400 => FujifilmTag::DynamicRange(
+            match get_u16(entry)? {
+                1 => "Standard",
+                3 => "Wide",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        0x1401 => FujifilmTag::FilmMode(
+            match get_u16(entry)? {
+                0x000 => "F0/Standard (Provia)",
+                0x100 => "F1/Studio Portrait",
+                0x110 => "F1a/Studio Portrait Enhanced Saturation",
+                0x120 => "F1b/Studio Portrait Smooth Skin Tone (Astia)",
+                0x130 => "F1c/Studio Portrait Increased Sharpness",
+                0x200 => "F2/Fujichrome (Velvia)",
+                0x300 => "F3/Studio Portrait Ex",
+                0x400 => "F4/Velvia",
+                0x500 => "Pro Neg. Std",
+                0x501 => "Pro Neg. Hi",
+                0x600 => "Classic Chrome",
+                0x700 => "Eterna",
+                0x800 => "Classic Negative",
+                0x900 => "Bleach Bypass",
+                0xA00 => "Nostalgic Neg",
+                0xB00 => "Reala ACE",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        0x1402 => FujifilmTag::DynamicRangeSetting(
+            match get_u16(entry)? {
+                0x000 => "Auto",
+                0x001 => "Manual",
+                0x100 => "Standard (100%)",
+                0x200 => "Wide1 (230%)",
+                0x201 => "Wide2 (400%)",
+                0x8000 => "Film Simulation",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        0x1403 => FujifilmTag::DevelopmentDynamicRange(get_u16(entry)?),
+        0x140B => FujifilmTag::AutoDynamicRange(get_u16(entry)?),
+        _ => FujifilmTag::Unknown(entry.clone()),
+    };
+
+    Ok(tag)
+}
+
+/// Decodes the entries of a Fujifilm makernote.
+pub(crate) fn decode_fujifilm_tags(entries: &[IFDEntry]) -> Vec<FujifilmTag> {
+    decode_entries(entries, decode_fujifilm_entry)
+}
+
+/// The film simulation, which is the FilmMode for color images and is stored in the Saturation
+/// for monochrome ones
+pub fn fujifilm_film_simulation(tags: &[FujifilmTag]) -> Option<String> {
+    let saturation = get_tag_value!(tags, FujifilmTag::Saturation);
+    if let Some(saturation) = saturation {
+        if saturation.starts_with("B&W") || saturation.starts_with("Acros") {
+            return Some(saturation.clone());
+        }
+        if saturation == "None (B&W)" {
+            return Some("Monochrome".to_string());
+        }
+    }
+
+    get_tag_value!(tags, FujifilmTag::FilmMode)
+        .filter(|v| *v != "Unknown")
+        .cloned()
+}
+
+impl Tiff {
+    /// The film simulation of a Fujifilm image, e.g. "Classic Chrome" or "Acros Red Filter"
+    pub fn film_simulation(&self) -> Option<String> {
+        match &self.makernote()?.tags {
+            MakerNoteTags::Fujifilm(tags) => fujifilm_film_simulation(tags),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fujifilm_film_simulation, FujifilmTag};
+    use crate::{
+        get_tag_value,
+        makernote::{read_makernote, MakerNoteTags, MakerNoteVendor, TestIfd},
+        utils::Endianness,
+    };
+
+    #[test]
+    fn test_fujifilm_makernote() {
+        // "FUJIFILM" and the offset of the IFD, whose values are relative to the makernote. The
+        // makernote is always little-endian, even in big-endian EXIF data.
+        let mut data = b"FUJIFILM\x0c\0\0\0".to_vec();
+        data.extend(
+            TestIfd::new(Endianness::Little)
+                .add_undefined(0x0000, b"0130")
+                .add_ascii(0x0010, "FF02B1234567     5934313030343135")
+                .add_ascii(0x1000, "NORMAL ")
+                .add_shorts(0x1001, &[0x84])
+                .add_shorts(0x1002, &[0x200])
+                .add_slongs(0x100A, &[20, -40])
+                .add_shorts(0x1021, &[0])
+                .add_slongs(0x1047, &[32])
+                .add_slongs(0x1048, &[64])
+                .add_shorts(0x104C, &[16])
+                .add_shorts(0x1400, &[1])
+                .add_shorts(0x1401, &[0x600])
+                .add_shorts(0x1402, &[0x100])
+                .build(12),
+        );
+
+        let makernote = read_makernote(&[], 0, data, ("FUJIFILM", "X-T5"), Endianness::Big);
+        assert_eq!(makernote.vendor, MakerNoteVendor::Fujifilm);
+        let tags = match &makernote.tags {
+            MakerNoteTags::Fujifilm(tags) => tags,
+            other => panic!("Expected Fujifilm tags (got {:?})", other),
+        };
+
+        assert_eq!(get_tag_value!(tags, FujifilmTag::Version).unwrap(), "0130");
+        assert_eq!(
+            get_tag_value!(tags, FujifilmTag::InternalSerialNumber).unwrap(),
+            "FF02B1234567     5934313030343135"
+        );
+        assert_eq!(
+            get_tag_value!(tags, FujifilmTag::Quality).unwrap(),
+            "NORMAL"
+        );
+        assert_eq!(
+            get_tag_value!(tags, FujifilmTag::Sharpness).unwrap(),
+            "+1 (medium hard)"
+        );
+        assert_eq!(
+            get_tag_value!(tags, FujifilmTag::WhiteBalance).unwrap(),
+            "Cloudy"
+        );
+        assert_eq!(
+            get_tag_value!(tags, FujifilmTag::WhiteBalanceFineTune).unwrap(),
+            "Red +1, Blue -2"
+        );
+        assert_eq!(
+            get_tag_value!(tags, FujifilmTag::FocusMode).unwrap(),
+            "Auto"
+        );
+        assert_eq!(
+            get_tag_value!(tags, FujifilmTag::GrainEffectRoughness).unwrap(),
+            "Weak"
+        );
+        assert_eq!(
+            get_tag_value!(tags, FujifilmTag::ColorChromeEffect).unwrap(),
+            "Strong"
+        );
+        assert_eq!(
+            get_tag_value!(tags, FujifilmTag::GrainEffectSize).unwrap(),
+            "Small"
+        );
+        assert_eq!(
+            get_tag_value!(tags, FujifilmTag::DynamicRange).unwrap(),
+            "Standard"
+        );
+        assert_eq!(
+            get_tag_value!(tags, FujifilmTag::DynamicRangeSetting).unwrap(),
+            "Standard (100%)"
+        );
+        assert_eq!(fujifilm_film_simulation(tags).unwrap(), "Classic Chrome");
+        assert!(makernote.unknown_entries().is_empty());
+    }
+
+    #[test]
+    fn test_monochrome_film_simulation() {
+        let mut data = b"FUJIFILM\x0c\0\0\0".to_vec();
+        data.extend(
+            TestIfd::new(Endianness::Little)
+                .add_shorts(0x1003, &[0x501])
+                .build(12),
+        );
+
+        let makernote = read_makernote(&[], 0, data, ("FUJIFILM", "X100V"), Endianness::Little);
+        let tags = match &makernote.tags {
+            MakerNoteTags::Fujifilm(tags) => tags,
+            other => panic!("Expected Fujifilm tags (got {:?})", other),
+        };
+        assert_eq!(fujifilm_film_simulation(tags).unwrap(), "Acros Red Filter");
+    }
+}
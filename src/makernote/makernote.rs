@@ -0,0 +1,825 @@
+use std::io::Cursor;
+
+use crate::{
+    tiff::{get_tiff_value_type_size, read_ifd_entry, IFDEntry, IFDEntryValue, Tiff, TiffError},
+    utils::Endianness,
+};
+
+use super::{
+    decode_apple_tags, decode_canon_tags, decode_fujifilm_tags, decode_leica_tags,
+    decode_nikon_tags, decode_olympus_tags, decode_panasonic_tags, decode_pentax_tags,
+    decode_ricoh_tags, decode_ricoh_text, decode_sony_tags, get_leica_variant, is_ricoh_text,
+    AppleTag, CanonTag, FujifilmTag, LeicaTag, LeicaVariant, NikonTag, OlympusTag, PanasonicTag,
+    PentaxTag, RicohTag, SonyTag,
+};
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum MakerNoteVendor {
+    Apple,
+    Canon,
+    Fujifilm,
+    Leica,
+    Nikon,
+    Olympus,
+    Panasonic,
+    Pentax,
+    Ricoh,
+    Sony,
+    Unknown,
+}
+
+/// What the offsets of the values in a makernote's IFD are relative to
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum OffsetBase {
+    /// The start of the makernote (e.g. Fujifilm and Apple)
+    MakerNote,
+    /// The TIFF header of the EXIF data, which for TIFF based files is the start of the file (e.g.
+    /// Canon and Sony)
+    TiffHeader,
+    /// A TIFF header embedded in the makernote at the given offset (e.g. Nikon type 3)
+    EmbeddedTiff(usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct MakerNote {
+    pub vendor: MakerNoteVendor,
+    pub endianness: Endianness,
+    pub offset_base: Option<OffsetBase>, // None if the makernote isn't IFD based
+    pub entries: Vec<IFDEntry>,          // The entries of the vendor's IFD
+    pub tags: MakerNoteTags,             // The entries decoded with the vendor's tags
+    pub data: Vec<u8>,                   // The complete makernote
+}
+
+/// The decoded tags of a makernote, for vendors whose tags are supported
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub enum MakerNoteTags {
+    Undecoded,
+    Apple(Vec<AppleTag>),
+    Canon(Vec<CanonTag>),
+    Fujifilm(Vec<FujifilmTag>),
+    Leica(LeicaVariant, Vec<LeicaTag>), // Models built by Panasonic use Panasonic's tags instead
+    Nikon(Vec<NikonTag>),
+    Olympus(Vec<OlympusTag>),
+    Panasonic(Vec<PanasonicTag>),
+    Pentax(Vec<PentaxTag>),
+    Ricoh(Vec<RicohTag>),
+    Sony(Vec<SonyTag>),
+}
+
+/// A vendor's makernote tags, which keep the entries they can't decode as an `Unknown` variant
+pub(crate) trait VendorTag {
+    /// The tag for an entry which wasn't decoded
+    fn unknown(entry: IFDEntry) -> Self;
+
+    /// The entry of a tag which wasn't decoded
+    fn as_unknown(&self) -> Option<&IFDEntry>;
+}
+
+macro_rules! impl_vendor_tag {
+    ($($tag:ident),*) => {
+        $(
+            impl VendorTag for $tag {
+                fn unknown(entry: IFDEntry) -> Self {
+                    $tag::Unknown(entry)
+                }
+
+                fn as_unknown(&self) -> Option<&IFDEntry> {
+                    match self {
+                        $tag::Unknown(entry) => Some(entry),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_vendor_tag!(
+    AppleTag,
+    CanonTag,
+    FujifilmTag,
+    LeicaTag,
+    NikonTag,
+    OlympusTag,
+    PanasonicTag,
+    PentaxTag,
+    RicohTag,
+    SonyTag
+);
+
+fn unknown_entries<T: VendorTag>(tags: &[T]) -> Vec<IFDEntry> {
+    tags.iter()
+        .filter_map(|tag| tag.as_unknown())
+        .cloned()
+        .collect()
+}
+
+/// Decodes an entry with `decode`, keeping it as the vendor's `Unknown` tag if that fails
+pub(crate) fn decode_or_unknown<T: VendorTag>(
+    entry: &IFDEntry,
+    decode: impl FnOnce(&IFDEntry) -> Result<T, TiffError>,
+) -> T {
+    decode(entry).unwrap_or_else(|_| T::unknown(entry.clone()))
+}
+
+/// Decodes the entries of a makernote with `decode`. Entries which can't be decoded are kept as
+/// the vendor's `Unknown` tag.
+pub(crate) fn decode_entries<T: VendorTag>(
+    entries: &[IFDEntry],
+    decode: impl Fn(&IFDEntry) -> Result<T, TiffError>,
+) -> Vec<T> {
+    entries
+        .iter()
+        .map(|entry| decode_or_unknown(entry, &decode))
+        .collect()
+}
+
+/// A string value, without the padding vendors often add
+pub(crate) fn get_string(entry: &IFDEntry) -> Result<String, TiffError> {
+    let value: String = entry.clone().try_into()?;
+
+    Ok(value.trim().to_string())
+}
+
+pub(crate) fn get_bytes(entry: &IFDEntry) -> Result<Vec<u8>, TiffError> {
+    entry.clone().try_into()
+}
+
+/// The first value of a SHORT entry, as some tags have extra values which aren't decoded
+pub(crate) fn get_u16(entry: &IFDEntry) -> Result<u16, TiffError> {
+    match entry.values.first() {
+        Some(IFDEntryValue::SHORT(v)) => Ok(*v),
+        v => Err(TiffError(format!(
+            "[MakerNote tag {}] Expected a SHORT (got {:?})",
+            entry.tag, v
+        ))),
+    }
+}
+
+pub(crate) fn get_u32(entry: &IFDEntry) -> Result<u32, TiffError> {
+    match entry.get_single_value()? {
+        IFDEntryValue::SHORT(v) => Ok(v as u32),
+        IFDEntryValue::LONG(v) => Ok(v),
+        v => Err(TiffError(format!(
+            "[MakerNote tag {}] Expected a SHORT or LONG (got {:?})",
+            entry.tag, v
+        ))),
+    }
+}
+
+/// A single value of any integer type
+pub(crate) fn get_integer(entry: &IFDEntry) -> Result<i64, TiffError> {
+    match entry.get_single_value()? {
+        IFDEntryValue::SLONG(v) => Ok(v as i64),
+        IFDEntryValue::LONG(v) => Ok(v as i64),
+        IFDEntryValue::SSHORT(v) => Ok(v as i64),
+        IFDEntryValue::SHORT(v) => Ok(v as i64),
+        v => Err(TiffError(format!(
+            "[MakerNote tag {}] Expected an integer (got {:?})",
+            entry.tag, v
+        ))),
+    }
+}
+
+/// Versions are ASCII digits stored as UNDEFINED, e.g. "0100"
+pub(crate) fn get_version(entry: &IFDEntry) -> Result<String, TiffError> {
+    let bytes = get_bytes(entry)?;
+
+    Ok(String::from_utf8_lossy(&bytes)
+        .trim_end_matches('\0')
+        .trim()
+        .to_string())
+}
+
+impl MakerNote {
+    /// The entries which weren't decoded as one of the vendor's tags
+    pub(crate) fn unknown_entries(&self) -> Vec<IFDEntry> {
+        match &self.tags {
+            MakerNoteTags::Undecoded => self.entries.clone(),
+            MakerNoteTags::Apple(tags) => unknown_entries(tags),
+            MakerNoteTags::Canon(tags) => unknown_entries(tags),
+            MakerNoteTags::Fujifilm(tags) => unknown_entries(tags),
+            MakerNoteTags::Leica(_, tags) => unknown_entries(tags),
+            MakerNoteTags::Nikon(tags) => unknown_entries(tags),
+            MakerNoteTags::Olympus(tags) => unknown_entries(tags),
+            MakerNoteTags::Panasonic(tags) => unknown_entries(tags),
+            MakerNoteTags::Pentax(tags) => unknown_entries(tags),
+            MakerNoteTags::Ricoh(tags) => unknown_entries(tags),
+            MakerNoteTags::Sony(tags) => unknown_entries(tags),
+        }
+    }
+}
+
+/// Where a makernote's IFD is and how to read it
+struct Layout {
+    vendor: MakerNoteVendor,
+    endianness: Endianness,
+    offset_base: OffsetBase,
+    ifd_offset: usize, // Relative to the offset base
+}
+
+fn read_endianness(data: &[u8], position: usize) -> Option<Endianness> {
+    match data.get(position..position + 2)? {
+        b"II" => Some(Endianness::Little),
+        b"MM" => Some(Endianness::Big),
+        _ => None,
+    }
+}
+
+pub(crate) fn read_u32(data: &[u8], position: usize, endianness: Endianness) -> Option<u32> {
+    let bytes: [u8; 4] = data.get(position..position + 4)?.try_into().ok()?;
+
+    Some(match endianness {
+        Endianness::Little => u32::from_le_bytes(bytes),
+        Endianness::Big => u32::from_be_bytes(bytes),
+    })
+}
+
+pub(crate) fn read_u16(data: &[u8], position: usize, endianness: Endianness) -> Option<u16> {
+    let bytes: [u8; 2] = data.get(position..position + 2)?.try_into().ok()?;
+
+    Some(match endianness {
+        Endianness::Little => u16::from_le_bytes(bytes),
+        Endianness::Big => u16::from_be_bytes(bytes),
+    })
+}
+
+/// Works out the vendor and layout of a makernote from its header and the camera's Make.
+/// `offset` is the position of the makernote relative to the EXIF data's TIFF header.
+fn get_layout(data: &[u8], offset: usize, make: &str, endianness: Endianness) -> Option<Layout> {
+    let make = make.trim().to_ascii_uppercase();
+    let layout = |vendor, endianness, offset_base, ifd_offset| {
+        Some(Layout {
+            vendor,
+            endianness,
+            offset_base,
+            ifd_offset,
+        })
+    };
+
+    // Nikon type 3 has its own TIFF header after "Nikon\0", a 2 byte version and 2 unused bytes
+    if data.starts_with(b"Nikon\0\x02") {
+        let endianness = read_endianness(data, 10)?;
+        let ifd_offset = read_u32(data, 14, endianness)? as usize;
+        return layout(
+            MakerNoteVendor::Nikon,
+            endianness,
+            OffsetBase::EmbeddedTiff(10),
+            ifd_offset,
+        );
+    }
+
+    if data.starts_with(b"Nikon\0\x01") {
+        return layout(
+            MakerNoteVendor::Nikon,
+            endianness,
+            OffsetBase::TiffHeader,
+            offset + 8,
+        );
+    }
+
+    // The newer Olympus formats use offsets relative to the makernote and have their own byte order
+    if data.starts_with(b"OLYMPUS\0") {
+        let endianness = read_endianness(data, 8)?;
+        return layout(
+            MakerNoteVendor::Olympus,
+            endianness,
+            OffsetBase::MakerNote,
+            12,
+        );
+    }
+
+    if data.starts_with(b"OM SYSTEM\0") {
+        let endianness = read_endianness(data, 12)?;
+        return layout(
+            MakerNoteVendor::Olympus,
+            endianness,
+            OffsetBase::MakerNote,
+            16,
+        );
+    }
+
+    if data.starts_with(b"OLYMP\0") {
+        return layout(
+            MakerNoteVendor::Olympus,
+            endianness,
+            OffsetBase::TiffHeader,
+            offset + 8,
+        );
+    }
+
+    // Fujifilm makernotes are always little-endian, whatever the EXIF data's byte order is
+    if data.starts_with(b"FUJIFILM") {
+        let ifd_offset = read_u32(data, 8, Endianness::Little)? as usize;
+        return layout(
+            MakerNoteVendor::Fujifilm,
+            Endianness::Little,
+            OffsetBase::MakerNote,
+            ifd_offset,
+        );
+    }
+
+    // "Apple iOS\0", a 2 byte version and the byte order
+    if data.starts_with(b"Apple iOS\0") {
+        let endianness = read_endianness(data, 12)?;
+        return layout(
+            MakerNoteVendor::Apple,
+            endianness,
+            OffsetBase::MakerNote,
+            14,
+        );
+    }
+
+    if data.starts_with(b"SONY DSC \0\0\0") || data.starts_with(b"SONY CAM \0\0\0") {
+        return layout(
+            MakerNoteVendor::Sony,
+            endianness,
+            OffsetBase::TiffHeader,
+            offset + 12,
+        );
+    }
+
+    if data.starts_with(b"Panasonic\0\0\0") {
+        return layout(
+            MakerNoteVendor::Panasonic,
+            endianness,
+            OffsetBase::TiffHeader,
+            offset + 12,
+        );
+    }
+
+    // Leica's own formats use offsets relative to the makernote, unlike the Panasonic built models
+    if let Some((variant, ifd_offset)) = get_leica_variant(data, &make) {
+        return match variant {
+            LeicaVariant::Panasonic => layout(
+                MakerNoteVendor::Leica,
+                endianness,
+                OffsetBase::TiffHeader,
+                offset + ifd_offset,
+            ),
+            _ => layout(
+                MakerNoteVendor::Leica,
+                endianness,
+                OffsetBase::MakerNote,
+                ifd_offset,
+            ),
+        };
+    }
+
+    // "AOC\0" is followed by the byte order, or by two spaces for the EXIF data's byte order
+    if data.starts_with(b"AOC\0") {
+        let endianness = read_endianness(data, 4).unwrap_or(endianness);
+        return layout(
+            MakerNoteVendor::Pentax,
+            endianness,
+            OffsetBase::TiffHeader,
+            offset + 6,
+        );
+    }
+
+    if data.starts_with(b"PENTAX \0") {
+        let endianness = read_endianness(data, 8)?;
+        return layout(
+            MakerNoteVendor::Pentax,
+            endianness,
+            OffsetBase::MakerNote,
+            10,
+        );
+    }
+
+    if data.starts_with(b"Ricoh\0") || data.starts_with(b"RICOH\0") {
+        return layout(
+            MakerNoteVendor::Ricoh,
+            endianness,
+            OffsetBase::TiffHeader,
+            offset + 8,
+        );
+    }
+
+    // Makernotes without a header are a bare IFD in the EXIF data's byte order
+    let vendor = match make.as_str() {
+        m if m.starts_with("CANON") => MakerNoteVendor::Canon,
+        m if m.starts_with("NIKON") => MakerNoteVendor::Nikon,
+        m if m.starts_with("SONY") => MakerNoteVendor::Sony,
+        m if m.starts_with("PENTAX") || m.starts_with("ASAHI") => MakerNoteVendor::Pentax,
+        _ => return None,
+    };
+
+    layout(vendor, endianness, OffsetBase::TiffHeader, offset)
+}
+
+/// Reads an IFD, first checking that it and its values are within `data` so that a makernote with
+/// unexpected offsets gives an error instead of garbage. Entries whose values are out of bounds
+/// or of an unknown type are skipped.
+pub(crate) fn read_ifd_checked(
+    data: &[u8],
+    offset: usize,
+    endianness: Endianness,
+) -> Result<Vec<IFDEntry>, TiffError> {
+    let out_of_bounds =
+        |what: &str| TiffError(format!("The makernote's {} is out of bounds", what));
+
+    let entry_count = read_u16(data, offset, endianness).ok_or(out_of_bounds("IFD"))? as usize;
+    if entry_count == 0 || offset + 2 + entry_count * 12 > data.len() {
+        return Err(out_of_bounds("IFD"));
+    }
+
+    // Some cameras write values past the end of the makernote or use types of their own, so those
+    // entries are dropped
+    let mut entries: Vec<IFDEntry> = vec![];
+    let mut cursor = Cursor::new(data);
+    for i in 0..entry_count {
+        let position = offset + 2 + i * 12;
+        let value_type = read_u16(data, position + 2, endianness).unwrap_or_default();
+        let value_count = read_u32(data, position + 4, endianness).unwrap_or_default() as usize;
+
+        let size = match get_tiff_value_type_size(value_type)
+            .ok()
+            .and_then(|size| size.checked_mul(value_count))
+        {
+            Some(size) => size,
+            None => continue,
+        };
+        if size > 4 {
+            let value_offset =
+                read_u32(data, position + 8, endianness).unwrap_or_default() as usize;
+            if value_offset.saturating_add(size) > data.len() {
+                continue;
+            }
+        }
+
+        cursor.set_position(position as u64);
+        entries.push(read_ifd_entry(&mut cursor, &endianness)?);
+    }
+    if entries.is_empty() {
+        return Err(out_of_bounds("value offset"));
+    }
+
+    Ok(entries)
+}
+
+/// Finds the offset of a tag's value in an IFD, which [`IFDEntry`] doesn't keep
+pub(crate) fn find_value_offset(
+    data: &[u8],
+    ifd_offset: usize,
+    tag: u16,
+    endianness: Endianness,
+) -> Option<usize> {
+    let entry_count = read_u16(data, ifd_offset, endianness)? as usize;
+
+    (0..entry_count)
+        .map(|i| ifd_offset + 2 + i * 12)
+        .find(|position| read_u16(data, *position, endianness) == Some(tag))
+        .and_then(|position| read_u32(data, position + 8, endianness))
+        .map(|offset| offset as usize)
+}
+
+/// Decodes a makernote's entries with its vendor's tags. `data` is the complete makernote, and
+/// `base` is the data the entries' offsets are relative to, for tags which point to sub-IFDs.
+fn decode_tags(
+    layout: &Layout,
+    data: &[u8],
+    base: &[u8],
+    entries: &[IFDEntry],
+    (make, model): (&str, &str),
+) -> MakerNoteTags {
+    match layout.vendor {
+        MakerNoteVendor::Apple => MakerNoteTags::Apple(decode_apple_tags(entries)),
+        MakerNoteVendor::Canon => MakerNoteTags::Canon(decode_canon_tags(entries, model)),
+        MakerNoteVendor::Fujifilm => MakerNoteTags::Fujifilm(decode_fujifilm_tags(entries)),
+        MakerNoteVendor::Leica => {
+            match get_leica_variant(data, &make.trim().to_ascii_uppercase()) {
+                Some((LeicaVariant::Panasonic, _)) => {
+                    MakerNoteTags::Panasonic(decode_panasonic_tags(entries, layout.endianness))
+                }
+                Some((variant, _)) => {
+                    MakerNoteTags::Leica(variant, decode_leica_tags(entries, variant))
+                }
+                None => MakerNoteTags::Undecoded,
+            }
+        }
+        // Type 1 makernotes (early Coolpix models) use their own tag IDs, which aren't supported
+        MakerNoteVendor::Nikon if data.starts_with(b"Nikon\0\x01") => MakerNoteTags::Undecoded,
+        MakerNoteVendor::Nikon => MakerNoteTags::Nikon(decode_nikon_tags(entries, model)),
+        MakerNoteVendor::Olympus => MakerNoteTags::Olympus(decode_olympus_tags(
+            entries,
+            base,
+            layout.ifd_offset,
+            layout.endianness,
+        )),
+        MakerNoteVendor::Panasonic => {
+            MakerNoteTags::Panasonic(decode_panasonic_tags(entries, layout.endianness))
+        }
+        MakerNoteVendor::Pentax => MakerNoteTags::Pentax(decode_pentax_tags(entries, model)),
+        MakerNoteVendor::Ricoh => {
+            MakerNoteTags::Ricoh(decode_ricoh_tags(entries, base, layout.endianness))
+        }
+        MakerNoteVendor::Sony => MakerNoteTags::Sony(decode_sony_tags(entries, model)),
+        _ => MakerNoteTags::Undecoded,
+    }
+}
+
+/// Reads a makernote, whose data starts at `offset` in `tiff_data` (the EXIF data starting from
+/// its TIFF header). Makernotes of unknown vendors, or whose IFD can't be read, are kept as the
+/// raw bytes.
+pub(crate) fn read_makernote(
+    tiff_data: &[u8],
+    offset: usize,
+    data: Vec<u8>,
+    (make, model): (&str, &str),
+    endianness: Endianness,
+) -> MakerNote {
+    let unknown = |data| MakerNote {
+        vendor: MakerNoteVendor::Unknown,
+        endianness,
+        offset_base: None,
+        entries: vec![],
+        tags: MakerNoteTags::Undecoded,
+        data,
+    };
+
+    // Older Ricoh models write the makernote as text
+    if make.trim().to_ascii_uppercase().starts_with("RICOH") && is_ricoh_text(&data) {
+        return MakerNote {
+            vendor: MakerNoteVendor::Ricoh,
+            endianness,
+            offset_base: None,
+            entries: vec![],
+            tags: MakerNoteTags::Ricoh(decode_ricoh_text(&data)),
+            data,
+        };
+    }
+
+    let layout = match get_layout(&data, offset, make, endianness) {
+        Some(layout) => layout,
+        None => return unknown(data),
+    };
+
+    let base = match layout.offset_base {
+        OffsetBase::MakerNote => &data[..],
+        OffsetBase::TiffHeader => tiff_data,
+        OffsetBase::EmbeddedTiff(header_offset) => &data[header_offset..],
+    };
+
+    match read_ifd_checked(base, layout.ifd_offset, layout.endianness) {
+        Ok(entries) => MakerNote {
+            vendor: layout.vendor,
+            endianness: layout.endianness,
+            offset_base: Some(layout.offset_base),
+            tags: decode_tags(&layout, &data, base, &entries, (make, model)),
+            entries,
+            data,
+        },
+        Err(_) => unknown(data),
+    }
+}
+
+impl Tiff {
+    /// The makernote from the EXIF IFD, decoded if its vendor is known
+    pub fn makernote(&self) -> Option<&MakerNote> {
+        self.makernote.as_ref()
+    }
+}
+
+/// Builds IFDs for makernote tests, with the values after the entries
+#[cfg(test)]
+pub(crate) struct TestIfd {
+    endianness: Endianness,
+    entries: Vec<(u16, u16, u32, Vec<u8>)>,
+}
+
+#[cfg(test)]
+impl TestIfd {
+    pub(crate) fn new(endianness: Endianness) -> TestIfd {
+        TestIfd {
+            endianness,
+            entries: vec![],
+        }
+    }
+
+    fn bytes<const N: usize>(&self, le: [u8; N], be: [u8; N]) -> [u8; N] {
+        match self.endianness {
+            Endianness::Little => le,
+            Endianness::Big => be,
+        }
+    }
+
+    /// Adds an entry whose value is already in the IFD's byte order
+    pub(crate) fn add(&mut self, tag: u16, value_type: u16, count: u32, value: &[u8]) -> &mut Self {
+        self.entries.push((tag, value_type, count, value.to_vec()));
+        self
+    }
+
+    pub(crate) fn add_ascii(&mut self, tag: u16, value: &str) -> &mut Self {
+        let value = format!("{}\0", value);
+        self.add(tag, 2, value.len() as u32, value.as_bytes())
+    }
+
+    pub(crate) fn add_undefined(&mut self, tag: u16, value: &[u8]) -> &mut Self {
+        self.add(tag, 7, value.len() as u32, value)
+    }
+
+    pub(crate) fn add_shorts(&mut self, tag: u16, values: &[u16]) -> &mut Self {
+        let value: Vec<u8> = values
+            .iter()
+            .flat_map(|v| self.bytes(v.to_le_bytes(), v.to_be_bytes()))
+            .collect();
+        self.add(tag, 3, values.len() as u32, &value)
+    }
+
+    pub(crate) fn add_sshorts(&mut self, tag: u16, values: &[i16]) -> &mut Self {
+        let value: Vec<u8> = values
+            .iter()
+            .flat_map(|v| self.bytes(v.to_le_bytes(), v.to_be_bytes()))
+            .collect();
+        self.add(tag, 8, values.len() as u32, &value)
+    }
+
+    pub(crate) fn add_longs(&mut self, tag: u16, values: &[u32]) -> &mut Self {
+        let value: Vec<u8> = values
+            .iter()
+            .flat_map(|v| self.bytes(v.to_le_bytes(), v.to_be_bytes()))
+            .collect();
+        self.add(tag, 4, values.len() as u32, &value)
+    }
+
+    pub(crate) fn add_slongs(&mut self, tag: u16, values: &[i32]) -> &mut Self {
+        let value: Vec<u8> = values
+            .iter()
+            .flat_map(|v| self.bytes(v.to_le_bytes(), v.to_be_bytes()))
+            .collect();
+        self.add(tag, 9, values.len() as u32, &value)
+    }
+
+    pub(crate) fn add_rationals(&mut self, tag: u16, values: &[(u32, u32)]) -> &mut Self {
+        let value: Vec<u8> = values
+            .iter()
+            .flat_map(|(n, d)| {
+                let mut bytes = self.bytes(n.to_le_bytes(), n.to_be_bytes()).to_vec();
+                bytes.extend(self.bytes(d.to_le_bytes(), d.to_be_bytes()));
+                bytes
+            })
+            .collect();
+        self.add(tag, 5, values.len() as u32, &value)
+    }
+
+    pub(crate) fn add_srationals(&mut self, tag: u16, values: &[(i32, i32)]) -> &mut Self {
+        let value: Vec<u8> = values
+            .iter()
+            .flat_map(|(n, d)| {
+                let mut bytes = self.bytes(n.to_le_bytes(), n.to_be_bytes()).to_vec();
+                bytes.extend(self.bytes(d.to_le_bytes(), d.to_be_bytes()));
+                bytes
+            })
+            .collect();
+        self.add(tag, 10, values.len() as u32, &value)
+    }
+
+    /// The IFD and its values, for an IFD at `offset` of the data its offsets are relative to
+    pub(crate) fn build(&self, offset: usize) -> Vec<u8> {
+        let mut entries: Vec<u8> = self
+            .bytes(
+                (self.entries.len() as u16).to_le_bytes(),
+                (self.entries.len() as u16).to_be_bytes(),
+            )
+            .to_vec();
+        let mut values: Vec<u8> = vec![];
+        let values_offset = offset + 2 + self.entries.len() * 12 + 4;
+
+        for (tag, value_type, count, value) in &self.entries {
+            entries.extend(self.bytes(tag.to_le_bytes(), tag.to_be_bytes()));
+            entries.extend(self.bytes(value_type.to_le_bytes(), value_type.to_be_bytes()));
+            entries.extend(self.bytes(count.to_le_bytes(), count.to_be_bytes()));
+            if value.len() <= 4 {
+                entries.extend(value);
+                entries.extend(vec![0; 4 - value.len()]);
+            } else {
+                let value_offset = (values_offset + values.len()) as u32;
+                entries.extend(self.bytes(value_offset.to_le_bytes(), value_offset.to_be_bytes()));
+                values.extend(value);
+            }
+        }
+
+        entries.extend([0; 4]);
+        entries.extend(values);
+        entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{
+        read_ifd_checked, read_makernote, MakerNoteTags, MakerNoteVendor, OffsetBase, TestIfd,
+    };
+    use crate::{jpeg::read_jpeg, tiff::IfdKind, utils::Endianness};
+
+    #[test]
+    fn test_nikon_type_3_layout() {
+        let data = fs::read("test_images/test9.jpeg").unwrap();
+        let exif = read_jpeg(&data).unwrap().exif.unwrap();
+        let makernote = exif.makernote().unwrap();
+
+        assert_eq!(makernote.vendor, MakerNoteVendor::Nikon);
+        assert_eq!(makernote.endianness, Endianness::Little);
+        assert_eq!(makernote.offset_base, Some(OffsetBase::EmbeddedTiff(10)));
+        assert_eq!(makernote.entries.len(), 46);
+        assert_eq!(makernote.data.len(), 2612);
+
+        // The entries which aren't decoded as Nikon tags can be decoded with the tag registry
+        let is_registered = |tag| {
+            exif.unknown_entries.iter().any(|(ifd, entry)| {
+                *ifd == IfdKind::MakerNote(MakerNoteVendor::Nikon) && entry.tag == tag
+            })
+        };
+        assert!(is_registered(0xA8));
+        assert!(!is_registered(0x01));
+    }
+
+    #[test]
+    fn test_nikon_type_1_is_undecoded() {
+        let mut data = b"Nikon\0\x01\0".to_vec();
+        data.extend(
+            TestIfd::new(Endianness::Little)
+                .add_shorts(3, &[2])
+                .build(16),
+        );
+        let mut tiff_data = b"II*\0\x08\0\0\0".to_vec();
+        tiff_data.extend(&data);
+
+        let makernote = read_makernote(&tiff_data, 8, data, ("NIKON", "E990"), Endianness::Little);
+        assert_eq!(makernote.vendor, MakerNoteVendor::Nikon);
+        assert!(matches!(makernote.tags, MakerNoteTags::Undecoded));
+        assert_eq!(makernote.unknown_entries()[0].tag, 3);
+    }
+
+    #[test]
+    fn test_read_ifd_checked_skips_entries() {
+        // An entry of an unknown type (99) between two SHORTs
+        let data = TestIfd::new(Endianness::Little)
+            .add_shorts(1, &[2])
+            .add(2, 99, 1, &[1, 2, 3, 4])
+            .add_shorts(3, &[4])
+            .build(0);
+
+        let entries = read_ifd_checked(&data, 0, Endianness::Little).unwrap();
+        let tags: Vec<u16> = entries.iter().map(|entry| entry.tag).collect();
+        assert_eq!(tags, vec![1, 3]);
+    }
+
+    #[test]
+    fn test_unknown_and_text_makernotes() {
+        let data = fs::read("test_images/ricoh_sample.jpeg").unwrap();
+        let exif = read_jpeg(&data).unwrap().exif.unwrap();
+        let makernote = exif.makernote().unwrap();
+
+        assert_eq!(makernote.vendor, MakerNoteVendor::Ricoh);
+        assert_eq!(makernote.offset_base, None);
+        assert!(makernote.data.starts_with(b"Rv0207;"));
+
+        // Text from a camera which isn't a Ricoh
+        let data = b"Rv0207;Rg76;".to_vec();
+        let makernote = read_makernote(&[], 0, data, ("Acme", ""), Endianness::Big);
+        assert_eq!(makernote.vendor, MakerNoteVendor::Unknown);
+
+        // A Fujifilm makernote whose IFD offset is past the end of the data
+        let data = b"FUJIFILM\xFF\0\0\0".to_vec();
+        let makernote = read_makernote(&[], 0, data, ("FUJIFILM", ""), Endianness::Big);
+        assert_eq!(makernote.vendor, MakerNoteVendor::Unknown);
+        assert_eq!(makernote.offset_base, None);
+    }
+
+    #[test]
+    fn test_offset_bases() {
+        // A bare Canon IFD at offset 8 of the EXIF data, with a value at offset 26
+        let mut tiff_data = b"MM\0\x2a\0\0\0\x08".to_vec();
+        tiff_data.extend(b"\0\x01\0\x06\0\x03\0\0\0\x03\0\0\0\x1a\0\0\0\0");
+        tiff_data.extend(b"\0\x01\0\x02\0\x03");
+        let makernote = read_makernote(
+            &tiff_data,
+            8,
+            tiff_data[8..].to_vec(),
+            ("Canon", ""),
+            Endianness::Big,
+        );
+        assert_eq!(makernote.vendor, MakerNoteVendor::Canon);
+        assert_eq!(makernote.offset_base, Some(OffsetBase::TiffHeader));
+        assert_eq!(makernote.entries[0].tag, 6);
+        assert_eq!(makernote.entries[0].values.len(), 3);
+
+        // A little-endian Fujifilm IFD in big-endian EXIF data, with a value relative to the
+        // makernote
+        let mut data = b"FUJIFILM\x0c\0\0\0".to_vec();
+        data.extend(b"\x01\0\x10\0\x02\0\x05\0\0\0\x1e\0\0\0\0\0\0\0");
+        data.extend(b"Test\0");
+        let makernote = read_makernote(&[], 100, data, ("FUJIFILM", ""), Endianness::Big);
+        assert_eq!(makernote.vendor, MakerNoteVendor::Fujifilm);
+        assert_eq!(makernote.endianness, Endianness::Little);
+        assert_eq!(makernote.offset_base, Some(OffsetBase::MakerNote));
+        assert_eq!(makernote.entries[0].tag, 0x10);
+        let value: String = makernote.entries[0].clone().try_into().unwrap();
+        assert_eq!(value, "Test");
+    }
+}
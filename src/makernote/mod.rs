@@ -0,0 +1,22 @@
+mod apple;
+mod canon;
+mod fujifilm;
+#[allow(clippy::module_inception)]
+mod makernote;
+mod nikon;
+mod olympus;
+mod panasonic;
+mod pentax;
+mod ricoh;
+mod sony;
+
+pub use apple::*;
+pub use canon::*;
+pub use fujifilm::*;
+pub use makernote::*;
+pub use nikon::*;
+pub use olympus::*;
+pub use panasonic::*;
+pub use pentax::*;
+pub use ricoh::*;
+pub use sony::*;
@@ -0,0 +1,536 @@
+// See <https://exiftool.org/TagNames/Nikon.html>
+
+use crate::tiff::{IFDEntry, IFDEntryValue, TiffError};
+
+use super::{decode_entries, get_bytes, get_string};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct NikonVrInfo {
+    pub version: String,
+    pub vibration_reduction: String, // "On", "Off" or "n/a"
+    pub mode: String,                // e.g. "Normal" or "Sport"
+}
+
+/// A block which is XOR-encrypted after its 4 byte version, keyed by the serial number and
+/// shutter count. `data` is decrypted if `decrypted` is true, which needs the shutter count.
+#[derive(Debug, PartialEq, Clone)]
+pub struct NikonEncryptedBlock {
+    pub version: String,
+    pub decrypted: bool,
+    pub data: Vec<u8>, // The complete block, including the version
+}
+
+/// The lens information from LensData, with the raw bytes converted to physical values
+#[derive(Debug, PartialEq, Clone)]
+pub struct NikonLensData {
+    pub version: String,
+    pub exit_pupil_position: Option<f64>, // Millimeters
+    pub af_aperture: Option<f64>,
+    pub focus_position: Option<u8>,
+    pub focus_distance: Option<f64>, // Meters
+    pub focal_length: Option<f64>,   // Millimeters
+    pub lens_id_number: u8,
+    pub lens_f_stops: f64,
+    pub min_focal_length: f64, // Millimeters
+    pub max_focal_length: f64, // Millimeters
+    pub max_aperture_at_min_focal: f64,
+    pub max_aperture_at_max_focal: f64,
+    pub mcu_version: u8,
+    pub effective_max_aperture: Option<f64>,
+    /// The 7 bytes (LensIDNumber, LensFStops, MinFocalLength, MaxFocalLength,
+    /// MaxApertureAtMinFocal, MaxApertureAtMaxFocal, MCUVersion) that together with the LensType
+    /// identify a lens
+    pub lens_id_bytes: [u8; 7],
+}
+
+/// Tags of Nikon type 3 makernotes (those starting with "Nikon\0\x02"), used by the DSLRs and
+/// newer COOLPIX cameras
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub enum NikonTag {
+    Unknown(IFDEntry),
+
+    MakerNoteVersion(String),
+    Iso(u16),
+    ColorMode(String),
+    Quality(String),
+    WhiteBalance(String),
+    Sharpness(String),
+    FocusMode(String),
+    FlashSetting(String),
+    FlashType(String),
+    WhiteBalanceFineTune(Vec<i16>),
+    IsoSelection(String),
+    IsoSetting(u16),
+    SerialNumber(String),
+    ColorSpace(String),
+    VrInfo(NikonVrInfo),
+    ActiveDLighting(String),
+    LensType(Vec<String>), // e.g. ["D", "G", "VR"], empty for a plain AF lens
+    Lens([f64; 4]),        // Min and max focal length, then the max aperture at each
+    ShotInfo(NikonEncryptedBlock),
+    LensData(NikonLensData),
+    ShutterCount(u32),
+    ImageStabilization(String),
+}
+
+// The tables used by Nikon's XOR encryption, indexed by the serial number and shutter count
+#[rustfmt::skip]
+const XLAT: [[u8; 256]; 2] = [
+    [
+        0xc1, 0xbf, 0x6d, 0x0d, 0x59, 0xc5, 0x13, 0x9d, 0x83, 0x61, 0x6b, 0x4f, 0xc7, 0x7f, 0x3d, 0x3d,
+        0x53, 0x59, 0xe3, 0xc7, 0xe9, 0x2f, 0x95, 0xa7, 0x95, 0x1f, 0xdf, 0x7f, 0x2b, 0x29, 0xc7, 0x0d,
+        0xdf, 0x07, 0xef, 0x71, 0x89, 0x3d, 0x13, 0x3d, 0x3b, 0x13, 0xfb, 0x0d, 0x89, 0xc1, 0x65, 0x1f,
+        0xb3, 0x0d, 0x6b, 0x29, 0xe3, 0xfb, 0xef, 0xa3, 0x6b, 0x47, 0x7f, 0x95, 0x35, 0xa7, 0x47, 0x4f,
+        0xc7, 0xf1, 0x59, 0x95, 0x35, 0x11, 0x29, 0x61, 0xf1, 0x3d, 0xb3, 0x2b, 0x0d, 0x43, 0x89, 0xc1,
+        0x9d, 0x9d, 0x89, 0x65, 0xf1, 0xe9, 0xdf, 0xbf, 0x3d, 0x7f, 0x53, 0x97, 0xe5, 0xe9, 0x95, 0x17,
+        0x1d, 0x3d, 0x8b, 0xfb, 0xc7, 0xe3, 0x67, 0xa7, 0x07, 0xf1, 0x71, 0xa7, 0x53, 0xb5, 0x29, 0x89,
+        0xe5, 0x2b, 0xa7, 0x17, 0x29, 0xe9, 0x4f, 0xc5, 0x65, 0x6d, 0x6b, 0xef, 0x0d, 0x89, 0x49, 0x2f,
+        0xb3, 0x43, 0x53, 0x65, 0x1d, 0x49, 0xa3, 0x13, 0x89, 0x59, 0xef, 0x6b, 0xef, 0x65, 0x1d, 0x0b,
+        0x59, 0x13, 0xe3, 0x4f, 0x9d, 0xb3, 0x29, 0x43, 0x2b, 0x07, 0x1d, 0x95, 0x59, 0x59, 0x47, 0xfb,
+        0xe5, 0xe9, 0x61, 0x47, 0x2f, 0x35, 0x7f, 0x17, 0x7f, 0xef, 0x7f, 0x95, 0x95, 0x71, 0xd3, 0xa3,
+        0x0b, 0x71, 0xa3, 0xad, 0x0b, 0x3b, 0xb5, 0xfb, 0xa3, 0xbf, 0x4f, 0x83, 0x1d, 0xad, 0xe9, 0x2f,
+        0x71, 0x65, 0xa3, 0xe5, 0x07, 0x35, 0x3d, 0x0d, 0xb5, 0xe9, 0xe5, 0x47, 0x3b, 0x9d, 0xef, 0x35,
+        0xa3, 0xbf, 0xb3, 0xdf, 0x53, 0xd3, 0x97, 0x53, 0x49, 0x71, 0x07, 0x35, 0x61, 0x71, 0x2f, 0x43,
+        0x2f, 0x11, 0xdf, 0x17, 0x97, 0xfb, 0x95, 0x3b, 0x7f, 0x6b, 0xd3, 0x25, 0xbf, 0xad, 0xc7, 0xc5,
+        0xc5, 0xb5, 0x8b, 0xef, 0x2f, 0xd3, 0x07, 0x6b, 0x25, 0x49, 0x95, 0x25, 0x49, 0x6d, 0x71, 0xc7,
+    ],
+    [
+        0xa7, 0xbc, 0xc9, 0xad, 0x91, 0xdf, 0x85, 0xe5, 0xd4, 0x78, 0xd5, 0x17, 0x46, 0x7c, 0x29, 0x4c,
+        0x4d, 0x03, 0xe9, 0x25, 0x68, 0x11, 0x86, 0xb3, 0xbd, 0xf7, 0x6f, 0x61, 0x22, 0xa2, 0x26, 0x34,
+        0x2a, 0xbe, 0x1e, 0x46, 0x14, 0x68, 0x9d, 0x44, 0x18, 0xc2, 0x40, 0xf4, 0x7e, 0x5f, 0x1b, 0xad,
+        0x0b, 0x94, 0xb6, 0x67, 0xb4, 0x0b, 0xe1, 0xea, 0x95, 0x9c, 0x66, 0xdc, 0xe7, 0x5d, 0x6c, 0x05,
+        0xda, 0xd5, 0xdf, 0x7a, 0xef, 0xf6, 0xdb, 0x1f, 0x82, 0x4c, 0xc0, 0x68, 0x47, 0xa1, 0xbd, 0xee,
+        0x39, 0x50, 0x56, 0x4a, 0xdd, 0xdf, 0xa5, 0xf8, 0xc6, 0xda, 0xca, 0x90, 0xca, 0x01, 0x42, 0x9d,
+        0x8b, 0x0c, 0x73, 0x43, 0x75, 0x05, 0x94, 0xde, 0x24, 0xb3, 0x80, 0x34, 0xe5, 0x2c, 0xdc, 0x9b,
+        0x3f, 0xca, 0x33, 0x45, 0xd0, 0xdb, 0x5f, 0xf5, 0x52, 0xc3, 0x21, 0xda, 0xe2, 0x22, 0x72, 0x6b,
+        0x3e, 0xd0, 0x5b, 0xa8, 0x87, 0x8c, 0x06, 0x5d, 0x0f, 0xdd, 0x09, 0x19, 0x93, 0xd0, 0xb9, 0xfc,
+        0x8b, 0x0f, 0x84, 0x60, 0x33, 0x1c, 0x9b, 0x45, 0xf1, 0xf0, 0xa3, 0x94, 0x3a, 0x12, 0x77, 0x33,
+        0x4d, 0x44, 0x78, 0x28, 0x3c, 0x9e, 0xfd, 0x65, 0x57, 0x16, 0x94, 0x6b, 0xfb, 0x59, 0xd0, 0xc8,
+        0x22, 0x36, 0xdb, 0xd2, 0x63, 0x98, 0x43, 0xa1, 0x04, 0x87, 0x86, 0xf7, 0xa6, 0x26, 0xbb, 0xd6,
+        0x59, 0x4d, 0xbf, 0x6a, 0x2e, 0xaa, 0x2b, 0xef, 0xe6, 0x78, 0xb6, 0x4e, 0xe0, 0x2f, 0xdc, 0x7c,
+        0xbe, 0x57, 0x19, 0x32, 0x7e, 0x2a, 0xd0, 0xb8, 0xba, 0x29, 0x00, 0x3c, 0x52, 0x7d, 0xa8, 0x49,
+        0x3b, 0x2d, 0xeb, 0x25, 0x49, 0xfa, 0xa3, 0xaa, 0x39, 0xa7, 0xc5, 0xa7, 0x50, 0x11, 0x36, 0xfb,
+        0xc6, 0x67, 0x4a, 0xf5, 0xa5, 0x12, 0x65, 0x7e, 0xb0, 0xdf, 0xaf, 0x4e, 0xb3, 0x61, 0x7f, 0x2f,
+    ],
+];
+
+/// XORs `data` with Nikon's key stream. Since this is XOR, it both encrypts and decrypts.
+pub fn nikon_decrypt(data: &mut [u8], serial: u32, shutter_count: u32) {
+    let key = shutter_count
+        .to_le_bytes()
+        .iter()
+        .fold(0_u8, |key, b| key ^ b);
+
+    let ci = XLAT[0][(serial & 0xFF) as usize];
+    let mut cj = XLAT[1][key as usize];
+    let mut ck = 0x60_u8;
+
+    for byte in data {
+        cj = cj.wrapping_add(ci.wrapping_mul(ck));
+        ck = ck.wrapping_add(1);
+        *byte ^= cj;
+    }
+}
+
+/// The key Nikon derives from the serial number, which is only used if it's all digits
+fn get_serial_key(serial: Option<&str>, model: &str) -> u32 {
+    match serial.map(|s| s.trim().parse::<u32>()) {
+        Some(Ok(serial)) => serial,
+        _ if model.trim_end().ends_with("D50") => 0x22,
+        _ => 0x60,
+    }
+}
+
+/// Versions are stored as 4 ASCII digits, except on some COOLPIX models which use a byte per
+/// digit
+fn get_version(data: &[u8]) -> String {
+    match data.get(0..4) {
+        Some(version) if version.iter().all(|b| b.is_ascii_digit()) => {
+            String::from_utf8_lossy(version).to_string()
+        }
+        Some(version) => version.iter().map(|b| b.to_string()).collect(),
+        None => String::new(),
+    }
+}
+
+fn get_encrypted_block(data: Vec<u8>, key: Option<(u32, u32)>) -> NikonEncryptedBlock {
+    let version = get_version(&data);
+    let is_known_version = version.len() == 4 && version.bytes().all(|b| b.is_ascii_digit());
+
+    // Every version from 0200 on is encrypted, including the 04xx and 08xx versions of the Z
+    // series. Since an unknown version can't be told apart from encrypted data it's never
+    // reported as decrypted.
+    let mut data = data;
+    let decrypted = match key {
+        _ if !is_known_version => false,
+        _ if version.as_str() < "0200" => true,
+        Some((serial, shutter_count)) if data.len() > 4 => {
+            nikon_decrypt(&mut data[4..], serial, shutter_count);
+            true
+        }
+        _ => false,
+    };
+
+    NikonEncryptedBlock {
+        version,
+        decrypted,
+        data,
+    }
+}
+
+/// Decodes LensData, decrypting it first if needed. Returns None for unknown versions and for
+/// encrypted data without a key.
+fn get_lens_data(data: Vec<u8>, key: Option<(u32, u32)>) -> Option<NikonLensData> {
+    let block = get_encrypted_block(data, key);
+    if !block.decrypted {
+        return None;
+    }
+
+    // The offset of LensIDNumber, which the 7 bytes identifying the lens start at
+    let lens_id_offset = match block.version.as_str() {
+        "0100" => 6,
+        "0101" | "0201" | "0202" | "0203" => 11,
+        "0204" => 12,
+        _ => return None,
+    };
+    let data = &block.data;
+    let lens_id_bytes: [u8; 7] = data
+        .get(lens_id_offset..lens_id_offset + 7)?
+        .try_into()
+        .ok()?;
+
+    let aperture = |v: u8| 2_f64.powf(v as f64 / 24.0);
+    let focal_length = |v: u8| 5.0 * 2_f64.powf(v as f64 / 24.0);
+    let optional = |offset: usize| match lens_id_offset {
+        6 => None,
+        _ => data.get(offset).copied(),
+    };
+
+    // Version 0204 has an extra byte before FocusDistance
+    let distance_offset = lens_id_offset - 2;
+
+    Some(NikonLensData {
+        version: block.version.clone(),
+        exit_pupil_position: optional(4).filter(|v| *v != 0).map(|v| 2048.0 / v as f64),
+        af_aperture: optional(5).map(aperture),
+        focus_position: optional(8),
+        focus_distance: optional(distance_offset).map(|v| 0.01 * 10_f64.powf(v as f64 / 40.0)),
+        focal_length: optional(distance_offset + 1).map(focal_length),
+        lens_id_number: lens_id_bytes[0],
+        lens_f_stops: lens_id_bytes[1] as f64 / 12.0,
+        min_focal_length: focal_length(lens_id_bytes[2]),
+        max_focal_length: focal_length(lens_id_bytes[3]),
+        max_aperture_at_min_focal: aperture(lens_id_bytes[4]),
+        max_aperture_at_max_focal: aperture(lens_id_bytes[5]),
+        mcu_version: lens_id_bytes[6],
+        effective_max_aperture: optional(lens_id_offset + 7).map(aperture),
+        lens_id_bytes,
+    })
+}
+
+fn get_lens_type(value: u8) -> Vec<String> {
+    ["MF", "D", "G", "VR", "1", "FT-1", "E", "AF-P"]
+        .iter()
+        .enumerate()
+        .filter(|(bit, _)| value & (1 << bit) != 0)
+        .map(|(_, name)| name.to_string())
+        .collect()
+}
+
+fn decode_nikon_entry(entry: &IFDEntry, key: Option<(u32, u32)>) -> Result<NikonTag, TiffError> {
+    let tag = match entry.tag {
+        0x0001 => NikonTag::MakerNoteVersion(get_version(&get_bytes(entry)?)),
+        // The first value is always 0
+        0x0002 => {
+            let values: Vec<u16> = entry.clone().try_into()?;
+            NikonTag::Iso(*values.last().unwrap_or(&0))
+        }
+        0x0003 => NikonTag::ColorMode(get_string(entry)?),
+        0x0004 => NikonTag::Quality(get_string(entry)?),
+        0x0005 => NikonTag::WhiteBalance(get_string(entry)?),
+        0x0006 => NikonTag::Sharpness(get_string(entry)?),
+        0x0007 => NikonTag::FocusMode(get_string(entry)?),
+        0x0008 => NikonTag::FlashSetting(get_string(entry)?),
+        0x0009 => NikonTag::FlashType(get_string(entry)?),
+        0x000B => NikonTag::WhiteBalanceFineTune(
+            entry
+                .values
+                .iter()
+                .map(|v| match v {
+                    IFDEntryValue::SSHORT(v) => Ok(*v),
+                    IFDEntryValue::SHORT(v) => Ok(*v as i16),
+                    _ => Err(TiffError(format!(
+                        "[Nikon tag 11] Expected SSHORT values (got {:?})",
+                        v
+                    ))),
+                })
+                .collect::<Result<Vec<i16>, TiffError>>()?,
+        ),
+        0x000F => NikonTag::IsoSelection(get_string(entry)?),
+        0x0013 => {
+            let values: Vec<u16> = entry.clone().try_into()?;
+            NikonTag::IsoSetting(*values.last().unwrap_or(&0))
+        }
+        0x001D => NikonTag::SerialNumber(get_string(entry)?),
+        0x001E => NikonTag::ColorSpace(
+            match <IFDEntry as TryInto<u16>>::try_into(entry.clone())? {
+                1 => "sRGB",
+                2 => "Adobe RGB",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        0x001F => {
+            let data = get_bytes(entry)?;
+            if data.len() < 7 {
+                return Err(TiffError(format!(
+                    "[Nikon tag 31] Expected at least 7 bytes of VRInfo (got {})",
+                    data.len()
+                )));
+            }
+
+            NikonTag::VrInfo(NikonVrInfo {
+                version: get_version(&data),
+                vibration_reduction: match data[4] {
+                    1 => "On",
+                    2 => "Off",
+                    _ => "n/a",
+                }
+                .to_string(),
+                mode: match data[6] {
+                    0 => "Normal",
+                    1 => "On (1)",
+                    2 => "Active",
+                    3 => "Sport",
+                    _ => "Unknown",
+                }
+                .to_string(),
+            })
+        }
+        0x0022 => NikonTag::ActiveDLighting(
+            match <IFDEntry as TryInto<u16>>::try_into(entry.clone())? {
+                0 => "Off",
+                1 => "Low",
+                3 => "Normal",
+                5 => "High",
+                7 => "Extra High",
+                8 => "Extra High 1",
+                9 => "Extra High 2",
+                10 => "Extra High 3",
+                11 => "Extra High 4",
+                0xFFFF => "Auto",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        0x0083 => NikonTag::LensType(get_lens_type(entry.clone().try_into()?)),
+        0x0084 => {
+            let values: Vec<f64> = entry.clone().try_into()?;
+            match values.try_into() {
+                Ok(values) => NikonTag::Lens(values),
+                Err(values) => {
+                    return Err(TiffError(format!(
+                        "[Nikon tag 132] Expected 4 values (got {})",
+                        values.len()
+                    )))
+                }
+            }
+        }
+        0x0091 => NikonTag::ShotInfo(get_encrypted_block(get_bytes(entry)?, key)),
+        0x0098 => match get_lens_data(get_bytes(entry)?, key) {
+            Some(lens_data) => NikonTag::LensData(lens_data),
+            None => NikonTag::Unknown(entry.clone()),
+        },
+        0x00A7 => NikonTag::ShutterCount(entry.clone().try_into()?),
+        0x00AC => NikonTag::ImageStabilization(get_string(entry)?),
+        _ => NikonTag::Unknown(entry.clone()),
+    };
+
+    Ok(tag)
+}
+
+/// Decodes the entries of a Nikon type 3 makernote.
+pub(crate) fn decode_nikon_tags(entries: &[IFDEntry], model: &str) -> Vec<NikonTag> {
+    let find = |tag: u16| entries.iter().find(|e| e.tag == tag);
+
+    // The encrypted blocks are keyed by the serial number and shutter count
+    let serial = find(0x001D).and_then(|e| get_string(e).ok());
+    let shutter_count = find(0x00A7).and_then(|e| e.clone().try_into().ok());
+    let key = shutter_count.map(|count| (get_serial_key(serial.as_deref(), model), count));
+
+    decode_entries(entries, |entry| decode_nikon_entry(entry, key))
+}
+
+impl NikonLensData {
+    /// A description of the lens from its focal length and aperture range, e.g.
+    /// "18-55mm f/3.5-5.6 G VR"
+    pub fn description(&self, lens_type: &[String]) -> String {
+        let focal_length = match self.min_focal_length.round() == self.max_focal_length.round() {
+            true => format!("{:.0}mm", self.min_focal_length),
+            false => format!(
+                "{:.0}-{:.0}mm",
+                self.min_focal_length, self.max_focal_length
+            ),
+        };
+        let aperture = match (self.max_aperture_at_min_focal * 10.0).round()
+            == (self.max_aperture_at_max_focal * 10.0).round()
+        {
+            true => format!("f/{:.1}", self.max_aperture_at_min_focal),
+            false => format!(
+                "f/{:.1}-{:.1}",
+                self.max_aperture_at_min_focal, self.max_aperture_at_max_focal
+            ),
+        };
+
+        let mut parts = vec![focal_length, aperture];
+        parts.extend(lens_type.iter().filter(|t| *t != "MF").cloned());
+
+        parts.join(" ")
+    }
+
+    /// The key ExifTool and others use to identify the lens, as hex bytes (e.g.
+    /// "7A 40 2D 53 2C 3C 44 06"), with the LensType as the last byte
+    pub fn lens_id_key(&self, lens_type: u8) -> String {
+        self.lens_id_bytes
+            .iter()
+            .chain([lens_type].iter())
+            .map(|b| format!("{:02X}", b))
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{decode_nikon_tags, get_encrypted_block, nikon_decrypt, NikonTag};
+    use crate::{
+        get_tag_value,
+        jpeg::read_jpeg,
+        makernote::MakerNoteTags,
+        tiff::{IFDEntry, IFDEntryValue},
+    };
+
+    #[test]
+    fn test_coolpix_makernote() {
+        let data = fs::read("test_images/test9.jpeg").unwrap();
+        let exif = read_jpeg(&data).unwrap().exif.unwrap();
+        let tags = match &exif.makernote().unwrap().tags {
+            MakerNoteTags::Nikon(tags) => tags,
+            other => panic!("Expected Nikon tags (got {:?})", other),
+        };
+
+        assert_eq!(
+            get_tag_value!(tags, NikonTag::MakerNoteVersion).unwrap(),
+            "0200"
+        );
+        assert_eq!(get_tag_value!(tags, NikonTag::Quality).unwrap(), "NORMAL");
+        assert_eq!(
+            get_tag_value!(tags, NikonTag::WhiteBalance).unwrap(),
+            "AUTO1"
+        );
+        assert_eq!(get_tag_value!(tags, NikonTag::FocusMode).unwrap(), "AF-S");
+        assert_eq!(
+            get_tag_value!(tags, NikonTag::ActiveDLighting).unwrap(),
+            "Off"
+        );
+        assert_eq!(
+            get_tag_value!(tags, NikonTag::ImageStabilization).unwrap(),
+            "VR-ON"
+        );
+        assert_eq!(get_tag_value!(tags, NikonTag::SerialNumber), None);
+
+        // This ShotInfo's version isn't one of the known ones, so it isn't reported as decrypted
+        let shot_info = get_tag_value!(tags, NikonTag::ShotInfo).unwrap();
+        assert!(!shot_info.decrypted);
+        assert_eq!(shot_info.data.len(), 984);
+
+        let data = fs::read("test_images/gps.jpeg").unwrap();
+        let exif = read_jpeg(&data).unwrap().exif.unwrap();
+        let makernote = exif.makernote().unwrap();
+        assert!(matches!(&makernote.tags, MakerNoteTags::Nikon(tags)
+            if get_tag_value!(tags, NikonTag::Quality).unwrap() == "FINE"));
+    }
+
+    #[test]
+    fn test_nikon_decrypt() {
+        let plain = b"\x00\x01\x02\x03\xF0\xFF".to_vec();
+
+        let mut data = plain.clone();
+        nikon_decrypt(&mut data, 3001234, 12345);
+        assert_ne!(data, plain);
+        nikon_decrypt(&mut data, 3001234, 12345);
+        assert_eq!(data, plain);
+
+        // The first byte of the key stream is xlat[1][key] + xlat[0][serial] * 0x60
+        let mut data = vec![0_u8];
+        nikon_decrypt(&mut data, 0, 0);
+        assert_eq!(data, vec![0xa7_u8.wrapping_add(0xc1_u8.wrapping_mul(0x60))]);
+    }
+
+    #[test]
+    fn test_encrypted_lens_data() {
+        // LensData 0204 for an 18-55mm lens at 18mm, with the lens ID bytes at offset 12
+        let mut lens_data = b"0204".to_vec();
+        lens_data.extend([
+            0x58, 0x24, 0, 0, 0x0A, 0, 0x3A, 0x2C, 0x8A, 0x40, 0x2D, 0x53,
+        ]);
+        lens_data.extend([0x2C, 0x3C, 0x62, 0x36]);
+        let mut encrypted = lens_data.clone();
+        nikon_decrypt(&mut encrypted[4..], 3001234, 4321);
+
+        let entries = vec![
+            IFDEntry {
+                tag: 0x001D,
+                values: b"3001234\0"
+                    .iter()
+                    .map(|b| IFDEntryValue::ASCII(*b))
+                    .collect(),
+            },
+            IFDEntry {
+                tag: 0x0083,
+                values: vec![IFDEntryValue::BYTE(0x0E)],
+            },
+            IFDEntry {
+                tag: 0x0098,
+                values: encrypted
+                    .iter()
+                    .map(|b| IFDEntryValue::UNDEFINED(*b))
+                    .collect(),
+            },
+            IFDEntry {
+                tag: 0x00A7,
+                values: vec![IFDEntryValue::LONG(4321)],
+            },
+        ];
+        let tags = decode_nikon_tags(&entries, "NIKON D5300");
+
+        assert_eq!(get_tag_value!(tags, NikonTag::ShutterCount), Some(&4321));
+        let lens_type = get_tag_value!(tags, NikonTag::LensType).unwrap();
+        assert_eq!(lens_type, &vec!["D", "G", "VR"]);
+
+        let lens_data = get_tag_value!(tags, NikonTag::LensData).unwrap();
+        assert_eq!(lens_data.lens_id_number, 0x8A);
+        assert_eq!(lens_data.focal_length.map(|f| f.round()), Some(18.0));
+        assert_eq!(lens_data.description(lens_type), "18-55mm f/3.6-5.7 D G VR");
+        assert_eq!(lens_data.lens_id_key(0x0E), "8A 40 2D 53 2C 3C 62 0E");
+
+        // The same data can't be decoded without the shutter count
+        let tags = decode_nikon_tags(&entries[0..3], "NIKON D5300");
+        assert!(get_tag_value!(tags, NikonTag::LensData).is_none());
+    }
+
+    #[test]
+    fn test_encrypted_block_versions() {
+        assert!(get_encrypted_block(b"0100\x01".to_vec(), None).decrypted);
+        assert!(!get_encrypted_block(b"0402\x01".to_vec(), None).decrypted);
+        assert!(!get_encrypted_block(b"0800\x01".to_vec(), None).decrypted);
+        assert!(!get_encrypted_block(b"\xFF\x01\x02\x03\x01".to_vec(), None).decrypted);
+
+        let block = get_encrypted_block(b"0800\x01".to_vec(), Some((1, 2)));
+        assert!(block.decrypted);
+        assert_ne!(block.data, b"0800\x01");
+    }
+}
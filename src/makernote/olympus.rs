@@ -0,0 +1,700 @@
+// See <https://exiftool.org/TagNames/Olympus.html>
+
+use crate::{
+    tiff::{IFDEntry, IFDEntryValue, TiffError},
+    utils::Endianness,
+};
+
+use super::{
+    decode_entries, decode_or_unknown, find_value_offset, get_string, get_u16, get_u32,
+    get_version, read_ifd_checked,
+};
+
+/// Tags of Olympus and OM System makernotes, including those of the Equipment, CameraSettings,
+/// RawDevelopment, ImageProcessing and FocusInfo sub-IFDs
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub enum OlympusTag {
+    Unknown(IFDEntry),
+
+    MakerNoteVersion(String),
+    CameraType(String),
+
+    // Equipment
+    EquipmentVersion(String),
+    CameraType2(String),
+    SerialNumber(String),
+    InternalSerialNumber(String),
+    LensType(String),
+    LensSerialNumber(String),
+    LensModel(String),
+    MaxApertureAtMinFocal(f64),
+    MaxApertureAtMaxFocal(f64),
+    MinFocalLength(u16), // mm
+    MaxFocalLength(u16), // mm
+
+    // CameraSettings
+    CameraSettingsVersion(String),
+    ExposureMode(String),
+    MeteringMode(String),
+    FocusMode(String),
+    ColorSpace(String),
+    PictureMode(String),
+    ArtFilter(String),
+    ImageStabilization(String),
+
+    // RawDevelopment
+    RawDevVersion(String),
+    RawDevExposureBiasValue(f64),
+    RawDevColorSpace(String),
+    RawDevEngine(String),
+
+    // ImageProcessing
+    ImageProcessingVersion(String),
+    WbRbLevels(Vec<u16>),
+
+    // FocusInfo
+    FocusInfoVersion(String),
+    AutoFocus(String),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum SubIfd {
+    Equipment,
+    CameraSettings,
+    RawDevelopment,
+    ImageProcessing,
+    FocusInfo,
+}
+
+fn get_sub_ifd(tag: u16) -> Option<SubIfd> {
+    match tag {
+        0x2010 => Some(SubIfd::Equipment),
+        0x2020 => Some(SubIfd::CameraSettings),
+        0x2030 => Some(SubIfd::RawDevelopment),
+        0x2040 => Some(SubIfd::ImageProcessing),
+        0x2050 => Some(SubIfd::FocusInfo),
+        _ => None,
+    }
+}
+
+/// Apertures are stored in APEX units scaled by 256
+fn get_aperture(entry: &IFDEntry) -> Result<f64, TiffError> {
+    let value = get_u16(entry)? as f64;
+
+    Ok((2_f64.sqrt().powf(value / 256.0) * 10.0).round() / 10.0)
+}
+
+fn get_color_space(entry: &IFDEntry) -> Result<String, TiffError> {
+    let color_space = match get_u16(entry)? {
+        0 => "sRGB",
+        1 => "Adobe RGB",
+        2 => "Pro Photo RGB",
+        _ => "Unknown",
+    };
+
+    Ok(color_space.to_string())
+}
+
+/// The lens type is 6 bytes, of which the make, model and sub-model identify the lens
+fn get_lens_type(entry: &IFDEntry) -> Result<String, TiffError> {
+    let bytes: Vec<u8> = entry.clone().try_into()?;
+    if bytes.len() < 4 {
+        return Err(TiffError(format!(
+            "[Olympus tag {}] Expected 6 bytes (got {})",
+            entry.tag,
+            bytes.len()
+        )));
+    }
+
+    let key = format!("{:x} {:02x} {:02x}", bytes[0], bytes[2], bytes[3]);
+    Ok(match olympus_lens_name(&key) {
+        Some(name) => name.to_string(),
+        None => format!("Unknown ({})", key),
+    })
+}
+
+/// The name of a lens from its make, model and sub-model, e.g. "0 19 10". The make is 0 for
+/// Olympus and OM System, 1 for Sigma, 2 for Panasonic, 3 for Leica and 5 for Tamron.
+/// Lenses which aren't listed, such as the newest ones, are decoded as "Unknown (key)".
+pub fn olympus_lens_name(key: &str) -> Option<&'static str> {
+    let name = match key {
+        "0 00 00" => "None",
+        "0 01 00" => "Olympus Zuiko Digital ED 50mm F2.0 Macro",
+        "0 01 01" => "Olympus Zuiko Digital 40-150mm F3.5-4.5",
+        "0 01 10" => "Olympus M.Zuiko Digital ED 14-42mm F3.5-5.6",
+        "0 02 00" => "Olympus Zuiko Digital ED 150mm F2.0",
+        "0 02 10" => "Olympus M.Zuiko Digital 17mm F2.8 Pancake",
+        "0 03 00" => "Olympus Zuiko Digital ED 300mm F2.8",
+        "0 03 10" => "Olympus M.Zuiko Digital ED 14-150mm F4.0-5.6 [II]",
+        "0 04 10" => "Olympus M.Zuiko Digital ED 9-18mm F4.0-5.6",
+        "0 05 00" => "Olympus Zuiko Digital 14-54mm F2.8-3.5",
+        "0 05 01" => "Olympus Zuiko Digital Pro ED 90-250mm F2.8",
+        "0 05 10" => "Olympus M.Zuiko Digital ED 14-42mm F3.5-5.6 L",
+        "0 06 00" => "Olympus Zuiko Digital ED 50-200mm F2.8-3.5",
+        "0 06 01" => "Olympus Zuiko Digital ED 8mm F3.5 Fisheye",
+        "0 06 10" => "Olympus M.Zuiko Digital ED 40-150mm F4.0-5.6",
+        "0 07 00" => "Olympus Zuiko Digital 11-22mm F2.8-3.5",
+        "0 07 01" => "Olympus Zuiko Digital 18-180mm F3.5-6.3",
+        "0 07 10" => "Olympus M.Zuiko Digital ED 12mm F2.0",
+        "0 08 01" => "Olympus Zuiko Digital 70-300mm F4.0-5.6",
+        "0 08 10" => "Olympus M.Zuiko Digital ED 75-300mm F4.8-6.7",
+        "0 09 10" => "Olympus M.Zuiko Digital 14-42mm F3.5-5.6 II",
+        "0 10 10" => "Olympus M.Zuiko Digital ED 12-50mm F3.5-6.3 EZ",
+        "0 11 10" => "Olympus M.Zuiko Digital 45mm F1.8",
+        "0 12 10" => "Olympus M.Zuiko Digital ED 60mm F2.8 Macro",
+        "0 13 10" => "Olympus M.Zuiko Digital 14-42mm F3.5-5.6 II R",
+        "0 14 10" => "Olympus M.Zuiko Digital ED 40-150mm F4.0-5.6 R",
+        "0 15 00" => "Olympus Zuiko Digital ED 7-14mm F4.0",
+        "0 15 10" => "Olympus M.Zuiko Digital ED 75mm F1.8",
+        "0 16 10" => "Olympus M.Zuiko Digital 17mm F1.8",
+        "0 17 00" => "Olympus Zuiko Digital Pro ED 35-100mm F2.0",
+        "0 18 00" => "Olympus Zuiko Digital 14-45mm F3.5-5.6",
+        "0 18 10" => "Olympus M.Zuiko Digital ED 75-300mm F4.8-6.7 II",
+        "0 19 10" => "Olympus M.Zuiko Digital ED 12-40mm F2.8 Pro",
+        "0 20 00" => "Olympus Zuiko Digital 35mm F3.5 Macro",
+        "0 20 10" => "Olympus M.Zuiko Digital ED 40-150mm F2.8 Pro",
+        "0 21 10" => "Olympus M.Zuiko Digital ED 14-42mm F3.5-5.6 EZ",
+        "0 22 00" => "Olympus Zuiko Digital 17.5-45mm F3.5-5.6",
+        "0 22 10" => "Olympus M.Zuiko Digital 25mm F1.8",
+        "0 23 00" => "Olympus Zuiko Digital ED 14-42mm F3.5-5.6",
+        "0 23 10" => "Olympus M.Zuiko Digital ED 7-14mm F2.8 Pro",
+        "0 24 00" => "Olympus Zuiko Digital ED 40-150mm F4.0-5.6",
+        "0 24 10" => "Olympus M.Zuiko Digital ED 300mm F4.0 IS Pro",
+        "0 25 10" => "Olympus M.Zuiko Digital ED 8mm F1.8 Fisheye Pro",
+        "0 26 10" => "Olympus M.Zuiko Digital ED 12-100mm F4.0 IS Pro",
+        "0 27 10" => "Olympus M.Zuiko Digital ED 30mm F3.5 Macro",
+        "0 28 10" => "Olympus M.Zuiko Digital ED 25mm F1.2 Pro",
+        "0 29 10" => "Olympus M.Zuiko Digital ED 17mm F1.2 Pro",
+        "0 30 00" => "Olympus Zuiko Digital ED 70-300mm F4.0-5.6",
+        "0 30 10" => "Olympus M.Zuiko Digital ED 45mm F1.2 Pro",
+        "0 31 00" => "Olympus Zuiko Digital ED 12-60mm F2.8-4.0 SWD",
+        "0 32 00" => "Olympus Zuiko Digital ED 14-35mm F2.0 SWD",
+        "0 32 10" => "Olympus M.Zuiko Digital ED 12-200mm F3.5-6.3",
+        "0 33 00" => "Olympus Zuiko Digital 25mm F2.8",
+        "0 33 10" => "Olympus M.Zuiko Digital 150-400mm F4.5 TC1.25x IS Pro",
+        "0 34 00" => "Olympus Zuiko Digital ED 9-18mm F4.0-5.6",
+        "0 34 10" => "Olympus M.Zuiko Digital ED 12-45mm F4.0 Pro",
+        "0 35 00" => "Olympus Zuiko Digital 14-54mm F2.8-3.5 II",
+        "0 35 10" => "Olympus M.Zuiko 100-400mm F5.0-6.3",
+        "0 36 10" => "Olympus M.Zuiko Digital ED 8-25mm F4 Pro",
+        "0 37 10" => "OM System M.Zuiko Digital ED 40-150mm F4.0 Pro",
+        "0 39 10" => "OM System M.Zuiko Digital ED 90mm F3.5 Macro IS Pro",
+        "1 01 00" => "Sigma 18-50mm F3.5-5.6 DC",
+        "1 01 10" => "Sigma 30mm F2.8 EX DN",
+        "1 02 00" => "Sigma 55-200mm F4.0-5.6 DC",
+        "1 02 10" => "Sigma 19mm F2.8 EX DN",
+        "1 03 00" => "Sigma 18-125mm F3.5-5.6 DC",
+        "1 03 10" => "Sigma 30mm F2.8 DN | A",
+        "1 04 00" => "Sigma 18-125mm F3.5-5.6 DC",
+        "1 04 10" => "Sigma 19mm F2.8 DN | A",
+        "1 05 00" => "Sigma 30mm F1.4 EX DC HSM",
+        "1 05 10" => "Sigma 60mm F2.8 DN | A",
+        "1 06 00" => "Sigma APO 50-500mm F4.0-6.3 EX DG HSM",
+        "1 06 10" => "Sigma 30mm F1.4 DC DN | C",
+        "1 07 00" => "Sigma Macro 105mm F2.8 EX DG",
+        "1 07 10" => "Sigma 16mm F1.4 DC DN | C (017)",
+        "1 08 00" => "Sigma APO Macro 150mm F2.8 EX DG HSM",
+        "1 09 00" => "Sigma 18-50mm F2.8 EX DC Macro",
+        "1 10 00" => "Sigma 24mm F1.8 EX DG Aspherical Macro",
+        "1 11 00" => "Sigma APO 135-400mm F4.5-5.6 DG",
+        "1 12 00" => "Sigma APO 300-800mm F5.6 EX DG HSM",
+        "1 13 00" => "Sigma 30mm F1.4 EX DC HSM",
+        "1 14 00" => "Sigma APO 50-500mm F4.0-6.3 EX DG HSM",
+        "1 15 00" => "Sigma 10-20mm F4.0-5.6 EX DC HSM",
+        "1 16 00" => "Sigma APO 70-200mm F2.8 II EX DG Macro HSM",
+        "1 17 00" => "Sigma 50mm F1.4 EX DG HSM",
+        "2 01 00" => "Leica D Vario Elmarit 14-50mm F2.8-3.5 Asph.",
+        "2 01 10" => "Lumix G Vario 14-45mm F3.5-5.6 Asph. Mega OIS",
+        "2 02 00" => "Leica D Summilux 25mm F1.4 Asph.",
+        "2 02 10" => "Lumix G Vario 45-200mm F4.0-5.6 Mega OIS",
+        "2 03 00" => "Leica D Vario Elmar 14-50mm F3.8-5.6 Asph. Mega OIS",
+        "2 03 01" => "Leica D Vario Elmar 14-50mm F3.8-5.6 Asph.",
+        "2 03 10" => "Lumix G Vario HD 14-140mm F4.0-5.8 Asph. Mega OIS",
+        "2 04 00" => "Leica D Vario Elmar 14-150mm F3.5-5.6",
+        "2 04 10" => "Lumix G Vario 7-14mm F4.0 Asph.",
+        "2 05 10" => "Lumix G 20mm F1.7 Asph.",
+        "2 06 10" => "Leica DG Macro-Elmarit 45mm F2.8 Asph. Mega OIS",
+        "2 07 10" => "Lumix G Vario 14-42mm F3.5-5.6 Asph. Mega OIS",
+        "2 08 10" => "Lumix G Fisheye 8mm F3.5",
+        "2 09 10" => "Lumix G Vario 100-300mm F4.0-5.6 Mega OIS",
+        "2 10 10" => "Lumix G 14mm F2.5 Asph.",
+        "2 11 10" => "Lumix G 12.5mm F12 3D",
+        "2 12 10" => "Leica DG Summilux 25mm F1.4 Asph.",
+        "2 13 10" => "Lumix G X Vario PZ 45-175mm F4.0-5.6 Asph. Power OIS",
+        "2 14 10" => "Lumix G X Vario PZ 14-42mm F3.5-5.6 Asph. Power OIS",
+        "2 15 10" => "Lumix G X Vario 12-35mm F2.8 Asph. Power OIS",
+        "2 16 10" => "Lumix G Vario 45-150mm F4.0-5.6 Asph. Mega OIS",
+        "2 17 10" => "Lumix G X Vario 35-100mm F2.8 Power OIS",
+        "2 18 10" => "Lumix G Vario 14-42mm F3.5-5.6 II Asph. Mega OIS",
+        "2 19 10" => "Lumix G Vario 14-140mm F3.5-5.6 Asph. Power OIS",
+        "2 20 10" => "Lumix G Vario 12-32mm F3.5-5.6 Asph. Mega OIS",
+        "2 21 10" => "Leica DG Nocticron 42.5mm F1.2 Asph. Power OIS",
+        "2 22 10" => "Leica DG Summilux 15mm F1.7 Asph.",
+        "2 23 10" => "Lumix G Vario 35-100mm F4.0-5.6 Asph. Mega OIS",
+        "2 24 10" => "Lumix G Macro 30mm F2.8 Asph. Mega OIS",
+        "2 25 10" => "Lumix G 42.5mm F1.7 Asph. Power OIS",
+        "2 26 10" => "Lumix G 25mm F1.7 Asph.",
+        "2 27 10" => "Leica DG Vario-Elmar 100-400mm F4.0-6.3 Asph. Power OIS",
+        "2 28 10" => "Lumix G Vario 12-60mm F3.5-5.6 Asph. Power OIS",
+        "2 29 10" => "Leica DG Summilux 12mm F1.4 Asph.",
+        "2 30 10" => "Leica DG Vario-Elmarit 12-60mm F2.8-4 Asph. Power OIS",
+        "2 31 10" => "Lumix G Vario 45-200mm F4.0-5.6 II",
+        "2 32 10" => "Lumix G Vario 100-300mm F4.0-5.6 II",
+        "2 33 10" => "Lumix G X Vario 12-35mm F2.8 II Asph. Power OIS",
+        "2 34 10" => "Lumix G Vario 35-100mm F2.8 II",
+        "2 35 10" => "Leica DG Vario-Elmarit 8-18mm F2.8-4 Asph.",
+        "2 36 10" => "Leica DG Elmarit 200mm F2.8 Power OIS",
+        "2 37 10" => "Leica DG Vario-Elmarit 50-200mm F2.8-4 Asph. Power OIS",
+        "2 38 10" => "Leica DG Vario-Summilux 10-25mm F1.7 Asph.",
+        "3 01 00" => "Leica D Vario Elmarit 14-50mm F2.8-3.5 Asph.",
+        "3 02 00" => "Leica D Summilux 25mm F1.4 Asph.",
+        "5 01 10" => "Tamron 14-150mm F3.5-5.8 Di III",
+        _ => return None,
+    };
+
+    Some(name)
+}
+
+fn decode_equipment_entry(entry: &IFDEntry) -> Result<OlympusTag, TiffError> {
+    let tag = match entry.tag {
+        0x0000 => OlympusTag::EquipmentVersion(get_version(entry)?),
+        0x0100 => OlympusTag::CameraType2(get_string(entry)?),
+        0x0101 => OlympusTag::SerialNumber(get_string(entry)?),
+        0x0102 => OlympusTag::InternalSerialNumber(get_string(entry)?),
+        0x0201 => OlympusTag::LensType(get_lens_type(entry)?),
+        0x0202 => OlympusTag::LensSerialNumber(get_string(entry)?),
+        0x0203 => OlympusTag::LensModel(get_string(entry)?),
+        0x0205 => OlympusTag::MaxApertureAtMinFocal(get_aperture(entry)?),
+        0x0206 => OlympusTag::MaxApertureAtMaxFocal(get_aperture(entry)?),
+        0x0207 => OlympusTag::MinFocalLength(get_u16(entry)?),
+        0x0208 => OlympusTag::MaxFocalLength(get_u16(entry)?),
+        _ => OlympusTag::Unknown(entry.clone()),
+    };
+
+    Ok(tag)
+}
+
+fn decode_camera_settings_entry(entry: &IFDEntry) -> Result<OlympusTag, TiffError> {
+    let tag = match entry.tag {
+        0x0000 => OlympusTag::CameraSettingsVersion(get_version(entry)?),
+        0x0200 => OlympusTag::ExposureMode(
+            match get_u16(entry)? {
+                1 => "Manual",
+                2 => "Program",
+                3 => "Aperture-priority AE",
+                4 => "Shutter speed priority AE",
+                5 => "Program-shift",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        0x0202 => OlympusTag::MeteringMode(
+            match get_u16(entry)? {
+                2 => "Center-weighted average",
+                3 => "Spot",
+                5 => "ESP",
+                261 => "Pattern+AF",
+                515 => "Spot+Highlight control",
+                1027 => "Spot+Shadow control",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        // The second value, if any, is a bit mask of extra settings
+        0x0301 => OlympusTag::FocusMode(
+            match get_u16(entry)? {
+                0 => "Single AF",
+                1 => "Sequential shooting AF",
+                2 => "Continuous AF",
+                3 => "Multi AF",
+                4 => "Face detect",
+                10 => "MF",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        0x0507 => OlympusTag::ColorSpace(get_color_space(entry)?),
+        0x0520 => OlympusTag::PictureMode(
+            match get_u16(entry)? {
+                1 => "Vivid",
+                2 => "Natural",
+                3 => "Muted",
+                4 => "Portrait",
+                5 => "i-Enhance",
+                6 => "e-Portrait",
+                7 => "Color Creator",
+                9 => "Color Profile 1",
+                10 => "Color Profile 2",
+                11 => "Color Profile 3",
+                12 => "Monochrome Profile 1",
+                13 => "Monochrome Profile 2",
+                14 => "Monochrome Profile 3",
+                17 => "Art Mode",
+                18 => "Monochrome Profile 4",
+                256 => "Monotone",
+                512 => "Sepia",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        // The other 3 values are the filter's variation and effects
+        0x0529 => OlympusTag::ArtFilter(
+            match get_u16(entry)? {
+                0 => "Off",
+                1 => "Soft Focus",
+                2 => "Pop Art",
+                3 => "Pale & Light Color",
+                4 => "Light Tone",
+                5 => "Pin Hole",
+                6 => "Grainy Film",
+                9 => "Diorama",
+                10 => "Cross Process",
+                12 => "Fish Eye",
+                13 => "Drawing",
+                14 => "Gentle Sepia",
+                15 => "Pale & Light Color II",
+                16 => "Pop Art II",
+                17 => "Pin Hole II",
+                18 => "Pin Hole III",
+                19 => "Grainy Film II",
+                20 => "Dramatic Tone",
+                21 => "Punk",
+                22 => "Soft Focus 2",
+                23 => "Sparkle",
+                24 => "Watercolor",
+                25 => "Key Line",
+                26 => "Key Line II",
+                27 => "Miniature",
+                28 => "Reflection",
+                29 => "Fragmented",
+                31 => "Cross Process II",
+                32 => "Dramatic Tone II",
+                33 => "Watercolor I",
+                34 => "Watercolor II",
+                35 => "Diorama II",
+                36 => "Vintage",
+                37 => "Vintage II",
+                38 => "Vintage III",
+                39 => "Partial Color",
+                40 => "Partial Color II",
+                41 => "Partial Color III",
+                42 => "Bleach Bypass",
+                43 => "Bleach Bypass II",
+                44 => "Instant Film",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        0x0604 => OlympusTag::ImageStabilization(
+            match get_u32(entry)? {
+                0 => "Off",
+                1 => "On, Mode 1",
+                2 => "On, Mode 2",
+                3 => "On, Mode 3",
+                4 => "On, Mode 4",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        _ => OlympusTag::Unknown(entry.clone()),
+    };
+
+    Ok(tag)
+}
+
+fn decode_raw_development_entry(entry: &IFDEntry) -> Result<OlympusTag, TiffError> {
+    let tag = match entry.tag {
+        0x0000 => OlympusTag::RawDevVersion(get_version(entry)?),
+        0x0100 => OlympusTag::RawDevExposureBiasValue(entry.clone().try_into()?),
+        0x0108 => OlympusTag::RawDevColorSpace(get_color_space(entry)?),
+        0x0109 => OlympusTag::RawDevEngine(
+            match get_u16(entry)? {
+                0 => "High Speed",
+                1 => "High Function",
+                2 => "Advanced High Speed",
+                3 => "Advanced High Function",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        _ => OlympusTag::Unknown(entry.clone()),
+    };
+
+    Ok(tag)
+}
+
+fn decode_image_processing_entry(entry: &IFDEntry) -> Result<OlympusTag, TiffError> {
+    let tag = match entry.tag {
+        0x0000 => OlympusTag::ImageProcessingVersion(get_version(entry)?),
+        0x0100 => OlympusTag::WbRbLevels(entry.clone().try_into()?),
+        _ => OlympusTag::Unknown(entry.clone()),
+    };
+
+    Ok(tag)
+}
+
+fn decode_focus_info_entry(entry: &IFDEntry) -> Result<OlympusTag, TiffError> {
+    let tag = match entry.tag {
+        0x0000 => OlympusTag::FocusInfoVersion(get_version(entry)?),
+        0x0209 => OlympusTag::AutoFocus(
+            match get_u16(entry)? {
+                0 => "Off",
+                1 => "On",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        _ => OlympusTag::Unknown(entry.clone()),
+    };
+
+    Ok(tag)
+}
+
+/// Decodes a sub-IFD, which newer models point to with an IFD or LONG entry. Older models store
+/// it in an UNDEFINED entry, so it's read from where that entry's value is.
+fn decode_sub_ifd(
+    entry: &IFDEntry,
+    sub_ifd: SubIfd,
+    base: &[u8],
+    ifd_offset: usize,
+    endianness: Endianness,
+) -> Result<Vec<OlympusTag>, TiffError> {
+    let offset = match entry.values.first() {
+        Some(IFDEntryValue::LONG(offset)) => *offset as usize,
+        Some(IFDEntryValue::UNDEFINED(_)) => {
+            find_value_offset(base, ifd_offset, entry.tag, endianness).ok_or(TiffError(format!(
+                "[Olympus tag {}] Couldn't find the sub-IFD",
+                entry.tag
+            )))?
+        }
+        v => {
+            return Err(TiffError(format!(
+                "[Olympus tag {}] Expected an IFD offset (got {:?})",
+                entry.tag, v
+            )))
+        }
+    };
+    let entries = read_ifd_checked(base, offset, endianness)?;
+
+    let decode = match sub_ifd {
+        SubIfd::Equipment => decode_equipment_entry,
+        SubIfd::CameraSettings => decode_camera_settings_entry,
+        SubIfd::RawDevelopment => decode_raw_development_entry,
+        SubIfd::ImageProcessing => decode_image_processing_entry,
+        SubIfd::FocusInfo => decode_focus_info_entry,
+    };
+
+    Ok(decode_entries(&entries, decode))
+}
+
+fn decode_olympus_entry(entry: &IFDEntry) -> Result<OlympusTag, TiffError> {
+    let tag = match entry.tag {
+        0x0000 => OlympusTag::MakerNoteVersion(get_version(entry)?),
+        0x0207 => OlympusTag::CameraType(get_string(entry)?),
+        _ => OlympusTag::Unknown(entry.clone()),
+    };
+
+    Ok(tag)
+}
+
+/// Decodes the entries of a makernote, including its sub-IFDs. `base` is the data the entries'
+/// offsets are relative to, and `ifd_offset` is where the makernote's IFD is in it.
+pub(crate) fn decode_olympus_tags(
+    entries: &[IFDEntry],
+    base: &[u8],
+    ifd_offset: usize,
+    endianness: Endianness,
+) -> Vec<OlympusTag> {
+    let mut tags: Vec<OlympusTag> = vec![];
+
+    for entry in entries {
+        match get_sub_ifd(entry.tag) {
+            Some(sub_ifd) => match decode_sub_ifd(entry, sub_ifd, base, ifd_offset, endianness) {
+                Ok(sub_tags) => tags.extend(sub_tags),
+                Err(_) => tags.push(OlympusTag::Unknown(entry.clone())),
+            },
+            None => tags.push(decode_or_unknown(entry, decode_olympus_entry)),
+        }
+    }
+
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{olympus_lens_name, OlympusTag};
+    use crate::{
+        get_tag_value,
+        makernote::{read_makernote, MakerNoteTags, MakerNoteVendor, TestIfd},
+        utils::Endianness,
+    };
+
+    #[test]
+    fn test_olympus_makernote() {
+        // "OLYMPUS\0II" and a version, then an IFD at 12 whose sub-IFDs follow it. All offsets are
+        // relative to the makernote.
+        let main_ifd = |equipment: u32, camera_settings: u32, focus_info: u32| {
+            TestIfd::new(Endianness::Little)
+                .add_undefined(0x0000, b"0100")
+                .add(0x2010, 13, 1, &equipment.to_le_bytes())
+                .add_longs(0x2020, &[camera_settings])
+                .add(0x2050, 13, 1, &focus_info.to_le_bytes())
+                .add_longs(0x2060, &[0])
+                .build(12)
+        };
+        let equipment_offset = 12 + main_ifd(0, 0, 0).len();
+        let equipment = TestIfd::new(Endianness::Little)
+            .add_undefined(0x0000, b"0100")
+            .add_ascii(0x0101, "BHQ123456")
+            .add_undefined(0x0201, &[0, 0, 0x19, 0x10, 0, 0])
+            .add_ascii(0x0203, "OLYMPUS M.12-40mm F2.8")
+            .add_shorts(0x0205, &[768])
+            .add_shorts(0x0207, &[12])
+            .add_shorts(0x0208, &[40])
+            .add_shorts(0x0209, &[0])
+            .build(equipment_offset);
+        let camera_settings_offset = equipment_offset + equipment.len();
+        let camera_settings = TestIfd::new(Endianness::Little)
+            .add_shorts(0x0200, &[3])
+            .add_shorts(0x0301, &[2, 0])
+            .add_shorts(0x0520, &[17])
+            .add_shorts(0x0529, &[23, 0, 0, 0])
+            .add_longs(0x0604, &[1])
+            .build(camera_settings_offset);
+        let focus_info_offset = camera_settings_offset + camera_settings.len();
+        let focus_info = TestIfd::new(Endianness::Little)
+            .add_longs(0x0000, &[100])
+            .add_shorts(0x0209, &[1, 0])
+            .build(focus_info_offset);
+
+        let mut data = b"OLYMPUS\0II\x03\0".to_vec();
+        data.extend(main_ifd(
+            equipment_offset as u32,
+            camera_settings_offset as u32,
+            focus_info_offset as u32,
+        ));
+        data.extend(equipment);
+        data.extend(camera_settings);
+        data.extend(focus_info);
+
+        let makernote = read_makernote(
+            &[],
+            0,
+            data,
+            ("OLYMPUS CORPORATION", "E-M1MarkII"),
+            Endianness::Big,
+        );
+        assert_eq!(makernote.vendor, MakerNoteVendor::Olympus);
+        let tags = match &makernote.tags {
+            MakerNoteTags::Olympus(tags) => tags,
+            other => panic!("Expected Olympus tags (got {:?})", other),
+        };
+
+        assert_eq!(
+            get_tag_value!(tags, OlympusTag::MakerNoteVersion).unwrap(),
+            "0100"
+        );
+        assert_eq!(
+            get_tag_value!(tags, OlympusTag::SerialNumber).unwrap(),
+            "BHQ123456"
+        );
+        assert_eq!(
+            get_tag_value!(tags, OlympusTag::LensType).unwrap(),
+            "Olympus M.Zuiko Digital ED 12-40mm F2.8 Pro"
+        );
+        assert_eq!(
+            get_tag_value!(tags, OlympusTag::MaxApertureAtMinFocal),
+            Some(&2.8)
+        );
+        assert_eq!(get_tag_value!(tags, OlympusTag::MinFocalLength), Some(&12));
+        assert_eq!(get_tag_value!(tags, OlympusTag::MaxFocalLength), Some(&40));
+        assert_eq!(
+            get_tag_value!(tags, OlympusTag::ExposureMode).unwrap(),
+            "Aperture-priority AE"
+        );
+        assert_eq!(
+            get_tag_value!(tags, OlympusTag::FocusMode).unwrap(),
+            "Continuous AF"
+        );
+        assert_eq!(
+            get_tag_value!(tags, OlympusTag::PictureMode).unwrap(),
+            "Art Mode"
+        );
+        assert_eq!(
+            get_tag_value!(tags, OlympusTag::ArtFilter).unwrap(),
+            "Sparkle"
+        );
+        assert_eq!(
+            get_tag_value!(tags, OlympusTag::ImageStabilization).unwrap(),
+            "On, Mode 1"
+        );
+        assert_eq!(get_tag_value!(tags, OlympusTag::AutoFocus).unwrap(), "On");
+
+        // The sub-IFD at offset 0 can't be read, and the sub-IFD entries which aren't decoded
+        // (0x0209 in Equipment) or can't be (the LONG version in FocusInfo) are kept
+        let unknown: Vec<u16> = makernote
+            .unknown_entries()
+            .iter()
+            .map(|entry| entry.tag)
+            .collect();
+        assert_eq!(unknown, vec![0x0209, 0x0000, 0x2060]);
+    }
+
+    #[test]
+    fn test_old_olympus_makernote() {
+        // An "OLYMP\0" makernote at offset 8 of the EXIF data, whose Equipment sub-IFD is stored
+        // in an UNDEFINED entry. Offsets are relative to the TIFF header, so the sub-IFD's values
+        // are relative to it as well.
+        let equipment = TestIfd::new(Endianness::Big)
+            .add_undefined(0x0201, &[0, 0, 0x11, 0x10, 0, 0])
+            .add_ascii(0x0101, "A12345678")
+            .build(34);
+
+        let mut tiff_data = b"MM\0\x2a\0\0\0\x08OLYMP\0\0\x01".to_vec();
+        tiff_data.extend(
+            TestIfd::new(Endianness::Big)
+                .add_undefined(0x2010, &equipment)
+                .build(16),
+        );
+
+        let makernote = read_makernote(
+            &tiff_data,
+            8,
+            tiff_data[8..].to_vec(),
+            ("OLYMPUS IMAGING CORP.", "E-300"),
+            Endianness::Big,
+        );
+        let tags = match &makernote.tags {
+            MakerNoteTags::Olympus(tags) => tags,
+            other => panic!("Expected Olympus tags (got {:?})", other),
+        };
+
+        assert_eq!(
+            get_tag_value!(tags, OlympusTag::LensType).unwrap(),
+            "Olympus M.Zuiko Digital 45mm F1.8"
+        );
+        assert_eq!(
+            get_tag_value!(tags, OlympusTag::SerialNumber).unwrap(),
+            "A12345678"
+        );
+        assert!(makernote.unknown_entries().is_empty());
+    }
+    #[test]
+    fn test_lens_names() {
+        assert_eq!(
+            olympus_lens_name("0 31 00"),
+            Some("Olympus Zuiko Digital ED 12-60mm F2.8-4.0 SWD")
+        );
+        assert_eq!(
+            olympus_lens_name("0 37 10"),
+            Some("OM System M.Zuiko Digital ED 40-150mm F4.0 Pro")
+        );
+        assert_eq!(olympus_lens_name("1 04 10"), Some("Sigma 19mm F2.8 DN | A"));
+        assert_eq!(
+            olympus_lens_name("2 20 10"),
+            Some("Lumix G Vario 12-32mm F3.5-5.6 Asph. Mega OIS")
+        );
+        assert_eq!(olympus_lens_name("4 01 00"), None);
+    }
+}
@@ -0,0 +1,527 @@
+// See <https://exiftool.org/TagNames/Panasonic.html>
+
+use crate::{
+    tiff::{IFDEntry, IFDEntryValue, TiffError},
+    utils::Endianness,
+};
+
+use super::{decode_entries, get_bytes, get_string, get_u16, get_u32};
+
+/// The faces detected when the photo was taken
+#[derive(Debug, PartialEq, Clone)]
+pub struct PanasonicFaceDetInfo {
+    pub faces_detected: u16,
+    pub positions: Vec<[u16; 4]>, // Center x and y, width and height of each face, at most 5
+}
+
+/// Tags of Panasonic makernotes, which Leica cameras built by Panasonic also use
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub enum PanasonicTag {
+    Unknown(IFDEntry),
+
+    ImageQuality(String),
+    FirmwareVersion(String),
+    ImageStabilization(String),
+    InternalSerialNumber(String), // e.g. "(F54) 2012:05:13 no. 0120"
+    TimeSincePowerOn(f64),        // Seconds
+    BurstMode(String),
+    SequenceNumber(u32),
+    WorldTimeLocation(String),
+    FaceDetInfo(PanasonicFaceDetInfo),
+    LensType(String),
+    LensSerialNumber(String),
+    AccessoryType(String),
+    PhotoStyle(String),
+    MakerNoteVersion(String),
+}
+
+/// The makernote formats of Leica cameras
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum LeicaVariant {
+    /// "LEICA\0\0\0" or "LEICA CAMERA AG\0" from models built by Panasonic (e.g. Digilux, D-Lux
+    /// and V-Lux), with Panasonic's tags
+    Panasonic,
+    /// "LEICA\0\0\0" from the M8
+    M8,
+    /// "LEICA0\x03\0" from the M9 and M Monochrom
+    M9,
+    /// "LEICA\0\x01\0" and "LEICA\0\x04\0" to "LEICA\0\x07\0" from the X, T and Q models
+    X,
+    /// "LEICA\0\x02\0" from the M10 and S models
+    M10,
+}
+
+/// Tags of the makernotes of Leica's own models
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub enum LeicaTag {
+    Unknown(IFDEntry),
+
+    Quality(String),
+    SerialNumber(u32),
+    LensType(String), // The lens ID and frame selector for the M8, otherwise the name
+    CameraTemperature(i32), // Celsius
+    OriginalFileName(String),
+    FilmMode(String),
+    InternalSerialNumber(String),
+}
+
+/// Works out which Leica format a makernote is from its header and the camera's Make, which must
+/// already be uppercase. Returns the variant and the offset of the IFD in the makernote.
+pub(crate) fn get_leica_variant(data: &[u8], make: &str) -> Option<(LeicaVariant, usize)> {
+    if data.starts_with(b"LEICA CAMERA AG\0") {
+        return Some((LeicaVariant::Panasonic, 18));
+    }
+
+    let variant = match data.get(..8)? {
+        b"LEICA\0\0\0" if make.starts_with("LEICA CAMERA") => LeicaVariant::M8,
+        b"LEICA\0\0\0" => LeicaVariant::Panasonic,
+        b"LEICA0\x03\0" => LeicaVariant::M9,
+        b"LEICA\0\x01\0" | b"LEICA\0\x04\0" | b"LEICA\0\x05\0" | b"LEICA\0\x06\0"
+        | b"LEICA\0\x07\0" => LeicaVariant::X,
+        b"LEICA\0\x02\0" => LeicaVariant::M10,
+        _ => return None,
+    };
+
+    Some((variant, 8))
+}
+
+/// UNDEFINED text, padded with NULs
+fn get_undefined_string(entry: &IFDEntry) -> Result<String, TiffError> {
+    let bytes: Vec<u8> = entry.clone().try_into()?;
+    let end = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+
+    Ok(String::from_utf8_lossy(&bytes[..end]).trim().to_string())
+}
+
+/// The firmware version is 4 bytes, e.g. 0 1 0 0 is "0.1.0.0"
+fn get_firmware_version(entry: &IFDEntry) -> Result<String, TiffError> {
+    let bytes = get_bytes(entry)?;
+
+    // Some models store text instead, which like exiftool we assume unless a byte is below '0'
+    match bytes.iter().any(|b| *b < b'0') {
+        true => Ok(bytes
+            .iter()
+            .map(|b| b.to_string())
+            .collect::<Vec<String>>()
+            .join(".")),
+        false => Ok(String::from_utf8_lossy(&bytes).to_string()),
+    }
+}
+
+/// The internal serial number holds the factory code and the date of manufacture
+fn get_internal_serial_number(entry: &IFDEntry) -> Result<String, TiffError> {
+    let serial = get_undefined_string(entry)?;
+    // `get` rather than indexing, as the serial number isn't necessarily ASCII
+    // The factory code is an uppercase letter then 2 uppercase letters or digits
+    let (factory, date) = match (serial.get(..3), serial.get(3..13)) {
+        (Some(factory), Some(date))
+            if factory.starts_with(|c: char| c.is_ascii_uppercase())
+                && factory
+                    .bytes()
+                    .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+                && date.bytes().all(|b| b.is_ascii_digit()) =>
+        {
+            (factory, date)
+        }
+        _ => return Ok(serial),
+    };
+
+    // Two digit years from 70 on are in the 1900s, like exiftool
+    let year: u32 = date[..2].parse().unwrap_or_default();
+    let year = year + if year < 70 { 2000 } else { 1900 };
+
+    Ok(format!(
+        "({}) {}:{}:{} no. {}",
+        factory,
+        year,
+        &date[2..4],
+        &date[4..6],
+        &date[6..]
+    ))
+}
+
+fn get_face_det_info(
+    entry: &IFDEntry,
+    endianness: Endianness,
+) -> Result<PanasonicFaceDetInfo, TiffError> {
+    let bytes: Vec<u8> = entry.clone().try_into()?;
+    let words: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|c| match endianness {
+            Endianness::Little => u16::from_le_bytes([c[0], c[1]]),
+            Endianness::Big => u16::from_be_bytes([c[0], c[1]]),
+        })
+        .collect();
+
+    let faces_detected = *words.first().ok_or(TiffError(format!(
+        "[Panasonic tag {}] FaceDetInfo is empty",
+        entry.tag
+    )))?;
+    let positions = words[1..]
+        .chunks_exact(4)
+        .take((faces_detected as usize).min(5))
+        .map(|c| [c[0], c[1], c[2], c[3]])
+        .collect();
+
+    Ok(PanasonicFaceDetInfo {
+        faces_detected,
+        positions,
+    })
+}
+
+fn decode_panasonic_entry(
+    entry: &IFDEntry,
+    endianness: Endianness,
+) -> Result<PanasonicTag, TiffError> {
+    let tag = match entry.tag {
+        0x0001 => PanasonicTag::ImageQuality(
+            match get_u16(entry)? {
+                1 => "TIFF",
+                2 => "High",
+                3 => "Normal",
+                6 => "Very High",
+                7 => "RAW",
+                9 => "Motion Picture",
+                11 => "Full HD Movie",
+                12 => "4k Movie",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        0x0002 => PanasonicTag::FirmwareVersion(get_firmware_version(entry)?),
+        0x001A => PanasonicTag::ImageStabilization(
+            match get_u16(entry)? {
+                2 => "On, Optical",
+                3 => "Off",
+                4 => "On, Mode 2",
+                5 => "On, Optical Panning",
+                6 => "On, Body-only",
+                7 => "On, Body-only Panning",
+                9 => "Dual IS",
+                10 => "Dual IS Panning",
+                11 => "Dual2 IS",
+                12 => "Dual2 IS Panning",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        0x0025 => PanasonicTag::InternalSerialNumber(get_internal_serial_number(entry)?),
+        0x0029 => PanasonicTag::TimeSincePowerOn(get_u32(entry)? as f64 / 100.0),
+        0x002A => PanasonicTag::BurstMode(
+            match get_u16(entry)? {
+                0 => "Off",
+                1 => "On",
+                2 => "Auto Exposure Bracketing (AEB)",
+                3 => "Focus Bracketing",
+                4 => "Unlimited",
+                8 => "White Balance Bracketing",
+                17 => "On (with flash)",
+                18 => "Aperture Bracketing",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        0x002B => PanasonicTag::SequenceNumber(get_u32(entry)?),
+        0x003A => PanasonicTag::WorldTimeLocation(
+            match get_u16(entry)? {
+                1 => "Home",
+                2 => "Destination",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        0x004E => PanasonicTag::FaceDetInfo(get_face_det_info(entry, endianness)?),
+        0x0051 => PanasonicTag::LensType(get_string(entry)?),
+        0x0052 => PanasonicTag::LensSerialNumber(get_string(entry)?),
+        0x0053 => PanasonicTag::AccessoryType(get_string(entry)?),
+        0x0089 => PanasonicTag::PhotoStyle(
+            match get_u16(entry)? {
+                0 => "Auto",
+                1 => "Standard or Custom",
+                2 => "Vivid",
+                3 => "Natural",
+                4 => "Monochrome",
+                5 => "Scenery",
+                6 => "Portrait",
+                8 => "Cinelike D",
+                9 => "Cinelike V",
+                11 => "L. Monochrome",
+                12 => "Like709",
+                15 => "L. Monochrome D",
+                17 => "V-Log",
+                18 => "Cinelike D2",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        0x8000 => PanasonicTag::MakerNoteVersion(get_undefined_string(entry)?),
+        _ => PanasonicTag::Unknown(entry.clone()),
+    };
+
+    Ok(tag)
+}
+
+/// Decodes the entries of a Panasonic makernote.
+pub(crate) fn decode_panasonic_tags(
+    entries: &[IFDEntry],
+    endianness: Endianness,
+) -> Vec<PanasonicTag> {
+    decode_entries(entries, |entry| decode_panasonic_entry(entry, endianness))
+}
+
+fn decode_leica_entry(entry: &IFDEntry, variant: LeicaVariant) -> Result<LeicaTag, TiffError> {
+    let tag = match (variant, entry.tag) {
+        (LeicaVariant::M8, 0x0300) => LeicaTag::Quality(
+            match get_u16(entry)? {
+                1 => "Fine",
+                2 => "Basic",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        (LeicaVariant::M8, 0x0303) | (LeicaVariant::X, 0x0305) => {
+            LeicaTag::SerialNumber(get_u32(entry)?)
+        }
+        // The lowest 2 bits are the position of the frame selector
+        (LeicaVariant::M8, 0x0310) => {
+            let value = get_u32(entry)?;
+            LeicaTag::LensType(format!("{} {}", value >> 2, value & 0x03))
+        }
+        (LeicaVariant::M8, 0x0320) => match entry.get_single_value()? {
+            IFDEntryValue::SLONG(v) => LeicaTag::CameraTemperature(v),
+            _ => LeicaTag::Unknown(entry.clone()),
+        },
+        (LeicaVariant::X, 0x0303) => LeicaTag::LensType(get_string(entry)?),
+        (LeicaVariant::X, 0x0407) => LeicaTag::OriginalFileName(get_string(entry)?),
+        (LeicaVariant::X, 0x0412) => LeicaTag::FilmMode(get_string(entry)?),
+        (LeicaVariant::X, 0x0500) => LeicaTag::InternalSerialNumber(get_undefined_string(entry)?),
+        _ => LeicaTag::Unknown(entry.clone()),
+    };
+
+    Ok(tag)
+}
+
+/// Decodes the entries of a makernote of one of Leica's own formats.
+pub(crate) fn decode_leica_tags(entries: &[IFDEntry], variant: LeicaVariant) -> Vec<LeicaTag> {
+    decode_entries(entries, |entry| decode_leica_entry(entry, variant))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        get_firmware_version, get_internal_serial_number, LeicaTag, LeicaVariant,
+        PanasonicFaceDetInfo, PanasonicTag,
+    };
+    use crate::{
+        get_tag_value,
+        makernote::{read_makernote, MakerNoteTags, MakerNoteVendor, TestIfd},
+        tiff::{IFDEntry, IFDEntryValue},
+        utils::Endianness,
+    };
+
+    #[test]
+    fn test_panasonic_makernote() {
+        let face_det_info: Vec<u8> = [1_u16, 320, 240, 100, 120, 0, 0, 0, 0]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+
+        // A "Panasonic\0\0\0" makernote at offset 8 of the EXIF data, with the IFD after the header
+        let mut tiff_data = b"II*\0\x08\0\0\0Panasonic\0\0\0".to_vec();
+        tiff_data.extend(
+            TestIfd::new(Endianness::Little)
+                .add_shorts(0x0001, &[2])
+                .add_undefined(0x0002, &[0, 1, 2, 0])
+                .add_shorts(0x001A, &[6])
+                .add_undefined(0x0025, b"F541205130120\0\0\0")
+                .add_longs(0x0029, &[12345])
+                .add_shorts(0x002A, &[1])
+                .add_longs(0x002B, &[3])
+                .add_shorts(0x003A, &[2])
+                .add_undefined(0x004E, &face_det_info)
+                .add_ascii(0x0051, "LUMIX G VARIO 12-32/F3.5-5.6")
+                .add_undefined(0x8000, b"0152")
+                .build(20),
+        );
+
+        let makernote = read_makernote(
+            &tiff_data,
+            8,
+            tiff_data[8..].to_vec(),
+            ("Panasonic", "DC-G9"),
+            Endianness::Little,
+        );
+        assert_eq!(makernote.vendor, MakerNoteVendor::Panasonic);
+        let tags = match &makernote.tags {
+            MakerNoteTags::Panasonic(tags) => tags,
+            other => panic!("Expected Panasonic tags (got {:?})", other),
+        };
+
+        assert_eq!(
+            get_tag_value!(tags, PanasonicTag::ImageQuality).unwrap(),
+            "High"
+        );
+        assert_eq!(
+            get_tag_value!(tags, PanasonicTag::FirmwareVersion).unwrap(),
+            "0.1.2.0"
+        );
+        assert_eq!(
+            get_tag_value!(tags, PanasonicTag::ImageStabilization).unwrap(),
+            "On, Body-only"
+        );
+        assert_eq!(
+            get_tag_value!(tags, PanasonicTag::InternalSerialNumber).unwrap(),
+            "(F54) 2012:05:13 no. 0120"
+        );
+        assert_eq!(
+            get_tag_value!(tags, PanasonicTag::TimeSincePowerOn),
+            Some(&123.45)
+        );
+        assert_eq!(get_tag_value!(tags, PanasonicTag::BurstMode).unwrap(), "On");
+        assert_eq!(get_tag_value!(tags, PanasonicTag::SequenceNumber), Some(&3));
+        assert_eq!(
+            get_tag_value!(tags, PanasonicTag::WorldTimeLocation).unwrap(),
+            "Destination"
+        );
+        assert_eq!(
+            get_tag_value!(tags, PanasonicTag::FaceDetInfo),
+            Some(&PanasonicFaceDetInfo {
+                faces_detected: 1,
+                positions: vec![[320, 240, 100, 120]],
+            })
+        );
+        assert_eq!(
+            get_tag_value!(tags, PanasonicTag::LensType).unwrap(),
+            "LUMIX G VARIO 12-32/F3.5-5.6"
+        );
+        assert_eq!(
+            get_tag_value!(tags, PanasonicTag::MakerNoteVersion).unwrap(),
+            "0152"
+        );
+        assert!(makernote.unknown_entries().is_empty());
+    }
+
+    #[test]
+    fn test_leica_makernotes() {
+        // A Panasonic built model, whose makernote is the same as Panasonic's after the header
+        let mut tiff_data = b"MM\0\x2a\0\0\0\x08LEICA\0\0\0".to_vec();
+        tiff_data.extend(
+            TestIfd::new(Endianness::Big)
+                .add_shorts(0x002A, &[2])
+                .build(16),
+        );
+        let makernote = read_makernote(
+            &tiff_data,
+            8,
+            tiff_data[8..].to_vec(),
+            ("LEICA", "D-LUX 3"),
+            Endianness::Big,
+        );
+        assert_eq!(makernote.vendor, MakerNoteVendor::Leica);
+        let tags = match &makernote.tags {
+            MakerNoteTags::Panasonic(tags) => tags,
+            other => panic!("Expected Panasonic tags (got {:?})", other),
+        };
+        assert_eq!(
+            get_tag_value!(tags, PanasonicTag::BurstMode).unwrap(),
+            "Auto Exposure Bracketing (AEB)"
+        );
+
+        // The same header from an M8, whose offsets are relative to the makernote
+        let mut data = b"LEICA\0\0\0".to_vec();
+        data.extend(
+            TestIfd::new(Endianness::Big)
+                .add_shorts(0x0300, &[1])
+                .add_longs(0x0303, &[3101234])
+                .add_longs(0x0310, &[(11 << 2) | 1])
+                .add_slongs(0x0320, &[24])
+                .add_longs(0x0399, &[0])
+                .build(8),
+        );
+        let makernote = read_makernote(&[], 0, data, ("Leica Camera AG", "M8"), Endianness::Big);
+        assert_eq!(makernote.vendor, MakerNoteVendor::Leica);
+        let tags = match &makernote.tags {
+            MakerNoteTags::Leica(LeicaVariant::M8, tags) => tags,
+            other => panic!("Expected M8 tags (got {:?})", other),
+        };
+        assert_eq!(get_tag_value!(tags, LeicaTag::Quality).unwrap(), "Fine");
+        assert_eq!(get_tag_value!(tags, LeicaTag::SerialNumber), Some(&3101234));
+        assert_eq!(get_tag_value!(tags, LeicaTag::LensType).unwrap(), "11 1");
+        assert_eq!(get_tag_value!(tags, LeicaTag::CameraTemperature), Some(&24));
+        assert_eq!(makernote.unknown_entries().len(), 1);
+
+        // An X model
+        let mut data = b"LEICA\0\x01\0".to_vec();
+        data.extend(
+            TestIfd::new(Endianness::Little)
+                .add_ascii(0x0303, "Elmarit 24mm f/2.8 ASPH.")
+                .add_ascii(0x0412, "Standard")
+                .build(8),
+        );
+        let makernote = read_makernote(
+            &[],
+            0,
+            data,
+            ("LEICA CAMERA AG", "LEICA X2"),
+            Endianness::Little,
+        );
+        let tags = match &makernote.tags {
+            MakerNoteTags::Leica(LeicaVariant::X, tags) => tags,
+            other => panic!("Expected X tags (got {:?})", other),
+        };
+        assert_eq!(
+            get_tag_value!(tags, LeicaTag::LensType).unwrap(),
+            "Elmarit 24mm f/2.8 ASPH."
+        );
+        assert_eq!(
+            get_tag_value!(tags, LeicaTag::FilmMode).unwrap(),
+            "Standard"
+        );
+    }
+
+    #[test]
+    fn test_internal_serial_number_not_ascii() {
+        let entry = |bytes: &[u8]| IFDEntry {
+            tag: 0x0025,
+            values: bytes.iter().map(|b| IFDEntryValue::UNDEFINED(*b)).collect(),
+        };
+
+        assert_eq!(
+            get_internal_serial_number(&entry(b"F541205130120")).unwrap(),
+            "(F54) 2012:05:13 no. 0120"
+        );
+        assert_eq!(
+            get_internal_serial_number(&entry(b"K0A9812310042")).unwrap(),
+            "(K0A) 1998:12:31 no. 0042"
+        );
+        assert_eq!(
+            get_internal_serial_number(&entry(b"Fa41205130120")).unwrap(),
+            "Fa41205130120"
+        );
+        // Invalid UTF-8 is replaced by a 3 byte character, which mustn't be split
+        assert_eq!(
+            get_internal_serial_number(&entry(b"F\xFF1205130120")).unwrap(),
+            "F\u{FFFD}1205130120"
+        );
+        assert_eq!(
+            get_internal_serial_number(&entry(b"F54120513012\xFF")).unwrap(),
+            "F54120513012\u{FFFD}"
+        );
+    }
+
+    #[test]
+    fn test_firmware_version() {
+        let entry = |bytes: &[u8]| IFDEntry {
+            tag: 0x0002,
+            values: bytes.iter().map(|b| IFDEntryValue::UNDEFINED(*b)).collect(),
+        };
+
+        assert_eq!(get_firmware_version(&entry(b"0152")).unwrap(), "0152");
+        assert_eq!(
+            get_firmware_version(&entry(&[0, 1, 0x35, 0])).unwrap(),
+            "0.1.53.0"
+        );
+    }
+}
@@ -0,0 +1,806 @@
+// See <https://exiftool.org/TagNames/Pentax.html>
+
+use crate::tiff::{IFDEntry, TiffError};
+
+use super::{decode_entries, get_bytes, get_string, get_u16};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PentaxShakeReduction {
+    pub stabilized: bool,
+    pub shake_reduction: String,
+}
+
+/// Tags of Pentax and Ricoh Imaging makernotes
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub enum PentaxTag {
+    Unknown(IFDEntry),
+
+    PentaxVersion(String),
+    PentaxModelId(u32),
+    Date(String), // "YYYY:MM:DD"
+    Time(String), // "HH:MM:SS"
+    Quality(String),
+    FocusMode(String),
+    AfPointSelected(String),
+    Iso(u32),
+    DriveMode(String),
+    LensType(String),
+    ShakeReduction(PentaxShakeReduction),
+    ShutterCount(u32),
+    SerialNumber(String),
+}
+
+fn get_date(bytes: &[u8]) -> Option<String> {
+    match bytes {
+        [year_hi, year_lo, month, day, ..] => Some(format!(
+            "{:04}:{:02}:{:02}",
+            u16::from_be_bytes([*year_hi, *year_lo]),
+            month,
+            day
+        )),
+        _ => None,
+    }
+}
+
+fn get_time(bytes: &[u8]) -> Option<String> {
+    match bytes {
+        [hour, minute, second, ..] => Some(format!("{:02}:{:02}:{:02}", hour, minute, second)),
+        _ => None,
+    }
+}
+
+/// The shutter count is XORed with the Date and the inverted Time, which must be the raw bytes of
+/// those tags. The same function obfuscates it.
+pub fn pentax_shutter_count(value: &[u8], date: &[u8], time: &[u8]) -> Option<u32> {
+    let value = u32::from_be_bytes(value.get(..4)?.try_into().ok()?);
+    let date = u32::from_be_bytes(date.get(..4)?.try_into().ok()?);
+    let time = u32::from_be_bytes([*time.first()?, *time.get(1)?, *time.get(2)?, 0]);
+
+    Some(value ^ date ^ !time)
+}
+
+/// ISO is stored as an index, except on some models which store the value itself
+fn get_iso(index: u16) -> Option<u32> {
+    let iso = match index {
+        3 => 50,
+        4 => 64,
+        5 => 80,
+        6 => 100,
+        7 => 125,
+        8 => 160,
+        9 => 200,
+        10 => 250,
+        11 => 320,
+        12 => 400,
+        13 => 500,
+        14 => 640,
+        15 => 800,
+        16 => 1000,
+        17 => 1250,
+        18 => 1600,
+        19 => 2000,
+        20 => 2500,
+        21 => 3200,
+        22 => 4000,
+        23 => 5000,
+        24 => 6400,
+        25 => 8000,
+        26 => 10000,
+        27 => 12800,
+        28 => 16000,
+        29 => 20000,
+        30 => 25600,
+        31 => 32000,
+        32 => 40000,
+        33 => 51200,
+        34 => 64000,
+        35 => 80000,
+        36 => 102400,
+        37 => 128000,
+        38 => 160000,
+        39 => 204800,
+        40 => 256000,
+        41 => 320000,
+        42 => 409600,
+        43 => 512000,
+        44 => 640000,
+        45 => 819200,
+        50 | 100 | 200 | 400 | 800 | 1600 | 3200 => index as u32,
+        // Half steps
+        258 => 50,
+        259 => 70,
+        260 => 100,
+        261 => 140,
+        262 => 200,
+        263 => 280,
+        264 => 400,
+        265 => 560,
+        266 => 800,
+        267 => 1100,
+        268 => 1600,
+        269 => 2200,
+        270 => 3200,
+        271 => 4500,
+        272 => 6400,
+        273 => 9000,
+        274 => 12800,
+        275 => 18000,
+        276 => 25600,
+        277 => 36000,
+        278 => 51200,
+        _ => return None,
+    };
+
+    Some(iso)
+}
+
+/// Only the first two of the 4 bytes are decoded, the others are the remote and exposure modes
+fn get_drive_mode(bytes: &[u8]) -> Option<String> {
+    let mode = match bytes.first()? {
+        0 => "Single-frame",
+        1 => "Continuous",
+        2 => "Continuous (Lo)",
+        3 => "Burst",
+        4 => "Continuous (Medium)",
+        5 => "Continuous (Low)",
+        255 => "Video",
+        _ => "Unknown",
+    };
+    let timer = match bytes.get(1) {
+        Some(1) => Some("Self-timer (12 s)"),
+        Some(2) => Some("Self-timer (2 s)"),
+        Some(16) => Some("Mirror Lock-up"),
+        _ => None,
+    };
+
+    Some(match timer {
+        Some(timer) => format!("{}, {}", mode, timer),
+        None => mode.to_string(),
+    })
+}
+
+fn get_shake_reduction(bytes: &[u8]) -> Option<PentaxShakeReduction> {
+    let shake_reduction = match bytes.get(1)? {
+        0 => "Off",
+        1 => "On",
+        4 => "Off (4)",
+        5 => "On but Disabled",
+        6 => "On (Video)",
+        7 => "On (7)",
+        15 => "On (15)",
+        39 => "On (mode 2)",
+        135 => "On (135)",
+        167 => "On (mode 1)",
+        _ => "Unknown",
+    };
+
+    Some(PentaxShakeReduction {
+        stabilized: bytes[0] & 0x01 != 0,
+        shake_reduction: shake_reduction.to_string(),
+    })
+}
+
+/// The models whose AF points are numbered like the 11 point SAFOX modules of the *ist D
+fn has_11_point_af(model: &str) -> bool {
+    const MODELS: [&str; 17] = [
+        "K10D",
+        "K20D",
+        "K100D",
+        "K100D Super",
+        "K110D",
+        "K200D",
+        "K-m",
+        "K2000",
+        "K-x",
+        "K-r",
+        "K-7",
+        "K-5",
+        "K-5 II",
+        "K-5 II s",
+        "K-30",
+        "K-50",
+        "K-500",
+    ];
+
+    let model = model.trim();
+    let model = model.strip_prefix("PENTAX ").unwrap_or(model);
+    model.starts_with("*ist") || MODELS.contains(&model)
+}
+
+/// The lens is identified by a series and an ID, which is 1 byte in LensRec (0x003F)
+fn get_lens_rec_type(bytes: &[u8]) -> Option<(u8, u16)> {
+    match bytes {
+        [series, id, ..] => Some((*series, *id as u16)),
+        _ => None,
+    }
+}
+
+/// LensInfo (0x0207) has a version for each length. The *ist, K100D and K110D models and the
+/// Samsung GX-1 store the same 2 bytes as LensRec. Newer models store the series in the low
+/// nibble of a byte, then a 2 byte ID, at offset 1 in the 80, 90, 91, 128 and 168 byte versions
+/// and at offset 0 in the others. The 80, 128 and 168 byte versions have another byte before the
+/// ID.
+fn get_lens_info_type(bytes: &[u8], model: &str) -> Option<(u8, u16)> {
+    let is_old_format = model.contains("*ist")
+        || model.contains("GX-1")
+        || model.ends_with("K100D")
+        || model.ends_with("K110D");
+    if is_old_format {
+        return get_lens_rec_type(bytes);
+    }
+
+    let (series, id_hi, id_lo) = match bytes.len() {
+        90 | 91 => (bytes[1], bytes[3], bytes[4]),
+        80 | 128 | 168 => (bytes[1], bytes[4], bytes[5]),
+        4.. => (bytes[0], bytes[2], bytes[3]),
+        _ => return None,
+    };
+
+    Some((series & 0x0F, u16::from_be_bytes([id_hi, id_lo])))
+}
+
+fn get_lens_type((series, id): (u8, u16)) -> String {
+    match pentax_lens_name(series, id) {
+        Some(name) => name.to_string(),
+        None => format!("Unknown ({} {})", series, id),
+    }
+}
+
+/// The name of a lens from its series and ID
+pub fn pentax_lens_name(series: u8, id: u16) -> Option<&'static str> {
+    let name = match (series, id) {
+        (0, 0) => "M-42 or No Lens",
+        (1, 0) => "K or M Lens",
+        (2, 0) => "A Series Lens",
+        (3, 0) => "Sigma",
+        (3, 17) => "smc PENTAX-FA SOFT 85mm F2.8",
+        (3, 18) => "smc PENTAX-F 1.7X AF ADAPTER",
+        (3, 19) => "smc PENTAX-F 24-50mm F4",
+        (3, 20) => "smc PENTAX-F 35-80mm F4-5.6",
+        (3, 21) => "smc PENTAX-F 80-200mm F4.7-5.6",
+        (3, 22) => "smc PENTAX-F FISH-EYE 17-28mm F3.5-4.5",
+        (3, 23) => "smc PENTAX-F 100-300mm F4.5-5.6 or Sigma Lens",
+        (3, 24) => "smc PENTAX-F 35-135mm F3.5-4.5",
+        (3, 25) => "smc PENTAX-F 35-105mm F4-5.6 or Sigma or Tokina Lens",
+        (3, 26) => "smc PENTAX-F* 250-600mm F5.6 ED[IF]",
+        (3, 27) => "smc PENTAX-F 28-80mm F3.5-4.5 or Tokina Lens",
+        (3, 28) => "smc PENTAX-F 35-70mm F3.5-4.5 or Tokina Lens",
+        (3, 29) => "PENTAX-F 28-80mm F3.5-4.5 or Sigma or Tokina Lens",
+        (3, 30) => "PENTAX-F 70-200mm F4-5.6",
+        (3, 31) => "smc PENTAX-F 70-210mm F4-5.6 or Tokina or Takumar Lens",
+        (3, 32) => "smc PENTAX-F 50mm F1.4",
+        (3, 33) => "smc PENTAX-F 50mm F1.7",
+        (3, 34) => "smc PENTAX-F 135mm F2.8 [IF]",
+        (3, 35) => "smc PENTAX-F 28mm F2.8",
+        (3, 36) => "Sigma 20mm F1.8 EX DG Aspherical RF",
+        (3, 38) => "smc PENTAX-F* 300mm F4.5 ED[IF]",
+        (3, 39) => "smc PENTAX-F* 600mm F4 ED[IF]",
+        (3, 40) => "smc PENTAX-F Macro 100mm F2.8",
+        (3, 41) => "smc PENTAX-F Macro 50mm F2.8 or Sigma Lens",
+        (3, 42) => "Sigma 300mm F2.8 EX DG APO IF",
+        (3, 44) => "Sigma or Tamron Lens (3 44)",
+        (3, 46) => "Sigma or Samsung Lens (3 46)",
+        (3, 50) => "smc PENTAX-FA 28-70mm F4 AL",
+        (3, 51) => "Sigma 28mm F1.8 EX DG Aspherical Macro",
+        (3, 52) => "smc PENTAX-FA 28-200mm F3.8-5.6 AL[IF] or Tamron Lens",
+        (3, 53) => "smc PENTAX-FA 28-80mm F3.5-5.6 AL",
+        (3, 247) => "smc PENTAX-DA FISH-EYE 10-17mm F3.5-4.5 ED[IF]",
+        (3, 248) => "smc PENTAX-DA 12-24mm F4 ED AL[IF]",
+        (3, 250) => "smc PENTAX-DA 50-200mm F4-5.6 ED",
+        (3, 251) => "smc PENTAX-DA 40mm F2.8 Limited",
+        (3, 252) => "smc PENTAX-DA 18-55mm F3.5-5.6 AL",
+        (3, 253) => "smc PENTAX-DA 14mm F2.8 ED[IF]",
+        (3, 254) => "smc PENTAX-DA 16-45mm F4 ED AL",
+        (3, 255) => "Sigma Lens (3 255)",
+        (4, 1) => "smc PENTAX-FA SOFT 28mm F2.8",
+        (4, 2) => "smc PENTAX-FA 80-320mm F4.5-5.6",
+        (4, 3) => "smc PENTAX-FA 43mm F1.9 Limited",
+        (4, 6) => "smc PENTAX-FA 35-80mm F4-5.6",
+        (4, 12) => "smc PENTAX-FA 50mm F1.4",
+        (4, 15) => "smc PENTAX-FA 28-105mm F4-5.6 [IF]",
+        (4, 16) => "Tamron AF 80-210mm F4-5.6 (178D)",
+        (4, 19) => "Tamron SP AF 90mm F2.8 (172E)",
+        (4, 20) => "smc PENTAX-FA 28-80mm F3.5-5.6",
+        (4, 21) => "Cosina AF 100-300mm F5.6-6.7",
+        (4, 22) => "Tokina 28-80mm F3.5-5.6",
+        (4, 23) => "smc PENTAX-FA 20-35mm F4 AL",
+        (4, 24) => "smc PENTAX-FA 77mm F1.8 Limited",
+        (4, 25) => "Tamron SP AF 14mm F2.8",
+        (4, 26) => "smc PENTAX-FA Macro 100mm F3.5 or Cosina Lens",
+        (4, 27) => "Tamron AF 28-300mm F3.5-6.3 LD Aspherical[IF] Macro (185D/285D)",
+        (4, 28) => "smc PENTAX-FA 35mm F2 AL",
+        (4, 29) => "Tamron AF 28-200mm F3.8-5.6 LD Super II Macro (371D)",
+        (4, 34) => "smc PENTAX-FA 24-90mm F3.5-4.5 AL[IF]",
+        (4, 35) => "smc PENTAX-FA 100-300mm F4.7-5.8",
+        (4, 36) => "Tamron AF 70-300mm F4-5.6 LD Macro 1:2",
+        (4, 37) => "Tamron SP AF 24-135mm F3.5-5.6 AD AL (190D)",
+        (4, 38) => "smc PENTAX-FA 28-105mm F3.2-4.5 AL[IF]",
+        (4, 39) => "smc PENTAX-FA 31mm F1.8 AL Limited",
+        (4, 41) => "Tamron AF 28-200mm Super Zoom F3.8-5.6 Aspherical XR [IF] Macro (A03)",
+        (4, 43) => "smc PENTAX-FA 28-90mm F3.5-5.6",
+        (4, 44) => "smc PENTAX-FA J 75-300mm F4.5-5.8 AL",
+        (4, 45) => "Tamron Lens (4 45)",
+        (4, 46) => "smc PENTAX-FA J 28-80mm F3.5-5.6 AL",
+        (4, 47) => "smc PENTAX-FA J 18-35mm F4-5.6 AL",
+        (4, 49) => "Tamron SP AF 28-75mm F2.8 XR Di LD Aspherical [IF] Macro",
+        (4, 51) => "smc PENTAX-D FA 50mm F2.8 Macro",
+        (4, 52) => "smc PENTAX-D FA 100mm F2.8 Macro",
+        (4, 55) => "Samsung/Schneider D-XENOGON 35mm F2",
+        (4, 56) => "Samsung/Schneider D-XENON 100mm F2.8 Macro",
+        (4, 75) => "Tamron SP AF 70-200mm F2.8 Di LD [IF] Macro (A001)",
+        (4, 214) => "smc PENTAX-DA 35mm F2.4 AL",
+        (4, 229) => "smc PENTAX-DA 18-55mm F3.5-5.6 AL II",
+        (4, 230) => "Tamron SP AF 17-50mm F2.8 XR Di II",
+        (4, 231) => "smc PENTAX-DA 18-250mm F3.5-6.3 ED AL [IF]",
+        (4, 237) => "Samsung/Schneider D-XENOGON 10-17mm F3.5-4.5",
+        (4, 239) => "Samsung/Schneider D-XENON 12-24mm F4 ED AL [IF]",
+        (4, 242) => "smc PENTAX-DA* 16-50mm F2.8 ED AL [IF] SDM (SDM unused)",
+        (4, 243) => "smc PENTAX-DA 70mm F2.4 Limited",
+        (4, 244) => "smc PENTAX-DA 21mm F3.2 AL Limited",
+        (4, 245) => "Samsung/Schneider D-XENON 50-200mm",
+        (4, 246) => "Samsung/Schneider D-XENON 18-55mm",
+        (4, 247) => "smc PENTAX-DA FISH-EYE 10-17mm F3.5-4.5 ED[IF]",
+        (4, 248) => "smc PENTAX-DA 12-24mm F4 ED AL [IF]",
+        (4, 249) => "Tamron XR DiII 18-200mm F3.5-6.3 (A14)",
+        (4, 250) => "smc PENTAX-DA 50-200mm F4-5.6 ED",
+        (4, 251) => "smc PENTAX-DA 40mm F2.8 Limited",
+        (4, 252) => "smc PENTAX-DA 18-55mm F3.5-5.6 AL",
+        (4, 253) => "smc PENTAX-DA 14mm F2.8 ED[IF]",
+        (4, 254) => "smc PENTAX-DA 16-45mm F4 ED AL",
+        (5, 1) => "smc PENTAX-FA* 24mm F2 AL[IF]",
+        (5, 2) => "smc PENTAX-FA 28mm F2.8 AL",
+        (5, 3) => "smc PENTAX-FA 50mm F1.7",
+        (5, 4) => "smc PENTAX-FA 50mm F1.4",
+        (5, 5) => "smc PENTAX-FA* 600mm F4 ED[IF]",
+        (5, 6) => "smc PENTAX-FA* 300mm F4.5 ED[IF]",
+        (5, 7) => "smc PENTAX-FA 135mm F2.8 [IF]",
+        (5, 8) => "smc PENTAX-FA Macro 50mm F2.8",
+        (5, 9) => "smc PENTAX-FA Macro 100mm F2.8",
+        (5, 10) => "smc PENTAX-FA* 85mm F1.4 [IF]",
+        (5, 11) => "smc PENTAX-FA* 200mm F2.8 ED[IF]",
+        (5, 12) => "smc PENTAX-FA 28-80mm F3.5-4.7",
+        (5, 13) => "smc PENTAX-FA 70-200mm F4-5.6",
+        (5, 14) => "smc PENTAX-FA* 250-600mm F5.6 ED[IF]",
+        (5, 15) => "smc PENTAX-FA 28-105mm F4-5.6",
+        (5, 16) => "smc PENTAX-FA 100-300mm F4.5-5.6",
+        (5, 98) => "smc PENTAX-FA 100-300mm F4.5-5.6",
+        (6, 1) => "smc PENTAX-FA* 85mm F1.4 [IF]",
+        (6, 2) => "smc PENTAX-FA* 200mm F4 Macro ED[IF]",
+        (6, 3) => "smc PENTAX-FA* 300mm F2.8 ED[IF]",
+        (6, 4) => "smc PENTAX-FA* 28-70mm F2.8 AL",
+        (6, 5) => "smc PENTAX-FA* 80-200mm F2.8 ED[IF]",
+        (6, 6) => "smc PENTAX-FA* 28-70mm F2.8 AL",
+        (6, 7) => "smc PENTAX-FA* 80-200mm F2.8 ED[IF]",
+        (6, 8) => "smc PENTAX-FA 28-70mm F4AL",
+        (6, 9) => "smc PENTAX-FA 20mm F2.8",
+        (6, 10) => "smc PENTAX-FA* 400mm F5.6 ED[IF]",
+        (6, 13) => "smc PENTAX-FA* 400mm F5.6 ED[IF]",
+        (6, 14) => "smc PENTAX-FA* Macro 200mm F4 ED[IF]",
+        (7, 0) => "smc PENTAX-DA 21mm F3.2 AL Limited",
+        (7, 58) => "smc PENTAX-D FA Macro 100mm F2.8 WR",
+        (7, 75) => "Tamron SP AF 70-200mm F2.8 Di LD [IF] Macro (A001)",
+        (7, 201) => "smc PENTAX-DA L 50-200mm F4-5.6 ED WR",
+        (7, 202) => "smc PENTAX-DA L 18-55mm F3.5-5.6 AL WR",
+        (7, 203) => "HD PENTAX-DA 55-300mm F4-5.8 ED WR",
+        (7, 204) => "HD PENTAX-DA 15mm F4 ED AL Limited",
+        (7, 205) => "HD PENTAX-DA 35mm F2.8 Macro Limited",
+        (7, 206) => "HD PENTAX-DA 70mm F2.4 Limited",
+        (7, 207) => "HD PENTAX-DA 21mm F3.2 ED AL Limited",
+        (7, 208) => "HD PENTAX-DA 40mm F2.8 Limited",
+        (7, 212) => "smc PENTAX-DA 50mm F1.8",
+        (7, 213) => "smc PENTAX-DA 40mm F2.8 XS",
+        (7, 214) => "smc PENTAX-DA 35mm F2.4 AL",
+        (7, 216) => "smc PENTAX-DA L 55-300mm F4-5.8 ED",
+        (7, 217) => "smc PENTAX-DA 50-200mm F4-5.6 ED WR",
+        (7, 218) => "smc PENTAX-DA 18-55mm F3.5-5.6 AL WR",
+        (7, 220) => "Tamron SP AF 10-24mm F3.5-4.5 Di II LD Aspherical [IF]",
+        (7, 221) => "smc PENTAX-DA L 50-200mm F4-5.6 ED",
+        (7, 222) => "smc PENTAX-DA L 18-55mm F3.5-5.6",
+        (7, 223) => "Samsung/Schneider D-XENON 18-55mm F3.5-5.6 II",
+        (7, 224) => "smc PENTAX-DA 15mm F4 ED AL Limited",
+        (7, 225) => "Samsung/Schneider D-XENON 18-250mm F3.5-6.3",
+        (7, 226) => "smc PENTAX-DA* 55mm F1.4 SDM (SDM unused)",
+        (7, 227) => "smc PENTAX-DA* 60-250mm F4 [IF] SDM (SDM unused)",
+        (7, 228) => "Samsung 16-45mm F4 ED",
+        (7, 229) => "smc PENTAX-DA 18-55mm F3.5-5.6 AL II",
+        (7, 230) => "Tamron AF 17-50mm F2.8 XR Di-II LD (Model A16)",
+        (7, 231) => "smc PENTAX-DA 18-250mm F3.5-6.3 ED AL [IF]",
+        (7, 233) => "smc PENTAX-DA 35mm F2.8 Macro Limited",
+        (7, 234) => "smc PENTAX-DA* 300mm F4 ED [IF] SDM (SDM unused)",
+        (7, 235) => "smc PENTAX-DA* 200mm F2.8 ED [IF] SDM (SDM unused)",
+        (7, 236) => "smc PENTAX-DA 55-300mm F4-5.8 ED",
+        (7, 238) => "Tamron AF 18-250mm F3.5-6.3 Di II LD Aspherical [IF] Macro",
+        (7, 241) => "smc PENTAX-DA* 50-135mm F2.8 ED [IF] SDM (SDM unused)",
+        (7, 242) => "smc PENTAX-DA* 16-50mm F2.8 ED AL [IF] SDM (SDM unused)",
+        (7, 243) => "smc PENTAX-DA 70mm F2.4 Limited",
+        (7, 244) => "smc PENTAX-DA 21mm F3.2 AL Limited",
+        (7, 245) => "Schneider D-XENON 50-200mm",
+        (7, 246) => "Schneider D-XENON 18-55mm",
+        (7, 247) => "smc PENTAX-DA 10-17mm F3.5-4.5 ED [IF] Fisheye zoom",
+        (7, 248) => "smc PENTAX-DA 12-24mm F4 ED AL [IF]",
+        (7, 249) => "Tamron XR DiII 18-200mm F3.5-6.3 (A14)",
+        (7, 250) => "smc PENTAX-DA 50-200mm F4-5.6 ED",
+        (7, 251) => "smc PENTAX-DA 40mm F2.8 Limited",
+        (7, 252) => "smc PENTAX-DA 18-55mm F3.5-5.6 AL",
+        (7, 253) => "smc PENTAX-DA 14mm F2.8 ED[IF]",
+        (7, 254) => "smc PENTAX-DA 16-45mm F4 ED AL",
+        (7, 255) => "Sigma Lens (7 255)",
+        (8, 0) => "Sigma Lens (8 0)",
+        (8, 3) => "Sigma 18-125mm F3.8-5.6 DC HSM",
+        (8, 4) => "Sigma 50mm F1.4 EX DG HSM",
+        (8, 7) => "Sigma 24-70mm F2.8 IF EX DG HSM",
+        (8, 8) => "Sigma 18-250mm F3.5-6.3 DC OS HSM",
+        (8, 11) => "Sigma 10-20mm F3.5 EX DC HSM",
+        (8, 12) => "Sigma 70-300mm F4-5.6 DG OS",
+        (8, 13) => "Sigma 120-400mm F4.5-5.6 APO DG OS HSM",
+        (8, 14) => "Sigma 17-70mm F2.8-4.0 DC Macro OS HSM",
+        (8, 15) => "Sigma 150-500mm F5-6.3 APO DG OS HSM",
+        (8, 16) => "Sigma 70-200mm F2.8 EX DG Macro HSM II",
+        (8, 17) => "Sigma 50-500mm F4.5-6.3 DG OS HSM",
+        (8, 18) => "Sigma 8-16mm F4.5-5.6 DC HSM",
+        (8, 21) => "Sigma 17-50mm F2.8 EX DC OS HSM",
+        (8, 22) => "Sigma 85mm F1.4 EX DG HSM",
+        (8, 23) => "Sigma 70-200mm F2.8 APO EX DG OS HSM",
+        (8, 25) => "Sigma 17-50mm F2.8 EX DC HSM",
+        (8, 27) => "Sigma 18-200mm F3.5-6.3 II DC HSM",
+        (8, 28) => "Sigma 18-250mm F3.5-6.3 DC Macro HSM",
+        (8, 29) => "Sigma 35mm F1.4 DG HSM",
+        (8, 31) => "Sigma 18-35mm F1.8 DC HSM",
+        (8, 32) => "Sigma 30mm F1.4 DC HSM | A",
+        (8, 33) => "Sigma 18-200mm F3.5-6.3 DC Macro HSM",
+        (8, 34) => "Sigma 18-300mm F3.5-6.3 DC Macro HSM",
+        (8, 59) => "HD PENTAX-D FA 150-450mm F4.5-5.6 ED DC AW",
+        (8, 60) => "HD PENTAX-D FA* 70-200mm F2.8 ED DC AW",
+        (8, 61) => "HD PENTAX-D FA 28-105mm F3.5-5.6 ED DC WR",
+        (8, 62) => "HD PENTAX-D FA 24-70mm F2.8 ED SDM WR",
+        (8, 63) => "HD PENTAX-D FA 15-30mm F2.8 ED SDM WR",
+        (8, 64) => "HD PENTAX-D FA* 50mm F1.4 SDM AW",
+        (8, 65) => "HD PENTAX-D FA 70-210mm F4 ED SDM WR",
+        (8, 66) => "HD PENTAX-D FA 85mm F1.4 ED SDM AW",
+        (8, 67) => "HD PENTAX-D FA 21mm F2.4 ED Limited DC WR",
+        (8, 195) => "HD PENTAX DA* 16-50mm F2.8 ED PLM AW",
+        (8, 196) => "HD PENTAX-DA* 11-18mm F2.8 ED DC AW",
+        (8, 197) => "HD PENTAX-DA 55-300mm F4.5-6.3 ED PLM WR RE",
+        (8, 198) => "smc PENTAX-DA L 18-50mm F4-5.6 DC WR RE",
+        (8, 199) => "HD PENTAX-DA 18-50mm F4-5.6 DC WR RE",
+        (8, 200) => "HD PENTAX-DA 16-85mm F3.5-5.6 ED DC WR",
+        (8, 209) => "HD PENTAX-DA 20-40mm F2.8-4 ED Limited DC WR",
+        (8, 210) => "smc PENTAX-DA 18-270mm F3.5-6.3 ED SDM",
+        (8, 211) => "HD PENTAX-DA 560mm F5.6 ED AW",
+        (8, 215) => "smc PENTAX-DA 18-135mm F3.5-5.6 ED AL [IF] DC WR",
+        (8, 226) => "smc PENTAX-DA* 55mm F1.4 SDM",
+        (8, 227) => "smc PENTAX-DA* 60-250mm F4 [IF] SDM",
+        (8, 232) => "smc PENTAX-DA 17-70mm F4 AL [IF] SDM",
+        (8, 234) => "smc PENTAX-DA* 300mm F4 ED [IF] SDM",
+        (8, 235) => "smc PENTAX-DA* 200mm F2.8 ED [IF] SDM",
+        (8, 241) => "smc PENTAX-DA* 50-135mm F2.8 ED [IF] SDM",
+        (8, 242) => "smc PENTAX-DA* 16-50mm F2.8 ED AL [IF] SDM",
+        (8, 255) => "Sigma Lens (8 255)",
+        _ => return None,
+    };
+
+    Some(name)
+}
+
+fn decode_pentax_entry(
+    entry: &IFDEntry,
+    date: &[u8],
+    time: &[u8],
+    model: &str,
+) -> Result<PentaxTag, TiffError> {
+    let invalid = || TiffError(format!("[Pentax tag {}] Invalid value", entry.tag));
+
+    let tag = match entry.tag {
+        0x0000 => PentaxTag::PentaxVersion(
+            get_bytes(entry)?
+                .iter()
+                .map(|b| b.to_string())
+                .collect::<Vec<String>>()
+                .join("."),
+        ),
+        0x0005 => PentaxTag::PentaxModelId(entry.clone().try_into()?),
+        0x0006 => PentaxTag::Date(get_date(&get_bytes(entry)?).ok_or_else(invalid)?),
+        0x0007 => PentaxTag::Time(get_time(&get_bytes(entry)?).ok_or_else(invalid)?),
+        0x0008 => PentaxTag::Quality(
+            match get_u16(entry)? {
+                0 => "Good",
+                1 => "Better",
+                2 => "Best",
+                3 => "TIFF",
+                4 => "RAW",
+                5 => "Premium",
+                7 => "RAW (pixel shift enabled)",
+                8 => "Dynamic Pixel Shift",
+                65535 => "n/a",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        0x000D => PentaxTag::FocusMode(
+            match get_u16(entry)? {
+                0 => "Normal",
+                1 => "Macro",
+                2 => "Infinity",
+                3 => "Manual",
+                4 => "Super Macro",
+                5 => "Pan Focus",
+                16 => "AF-S (Focus-priority)",
+                17 => "AF-C (Focus-priority)",
+                18 => "AF-A (Focus-priority)",
+                32 => "Contrast-detect (Focus-priority)",
+                33 => "Tracking Contrast-detect (Focus-priority)",
+                272 => "AF-S (Release-priority)",
+                273 => "AF-C (Release-priority)",
+                274 => "AF-A (Release-priority)",
+                288 => "Contrast-detect (Release-priority)",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        // The 11 point AF of the *ist and K models up to the K-5. The points of newer models are
+        // numbered differently and aren't decoded.
+        0x000E if has_11_point_af(model) => PentaxTag::AfPointSelected(
+            match get_u16(entry)? {
+                0 => "None",
+                1 => "Upper-left",
+                2 => "Top",
+                3 => "Upper-right",
+                4 => "Left",
+                5 => "Mid-left",
+                6 => "Center",
+                7 => "Mid-right",
+                8 => "Right",
+                9 => "Lower-left",
+                10 => "Bottom",
+                11 => "Lower-right",
+                0xFFFB => "AF Select",
+                0xFFFC => "Spot",
+                0xFFFD => "Auto (tracking)",
+                0xFFFE => "Fixed Center",
+                0xFFFF => "Auto",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        0x0014 => PentaxTag::Iso(get_iso(get_u16(entry)?).ok_or_else(invalid)?),
+        0x0034 => PentaxTag::DriveMode(get_drive_mode(&get_bytes(entry)?).ok_or_else(invalid)?),
+        0x003F => PentaxTag::LensType(get_lens_type(
+            get_lens_rec_type(&get_bytes(entry)?).ok_or_else(invalid)?,
+        )),
+        0x0207 => PentaxTag::LensType(get_lens_type(
+            get_lens_info_type(&get_bytes(entry)?, model).ok_or_else(invalid)?,
+        )),
+        0x005C => {
+            PentaxTag::ShakeReduction(get_shake_reduction(&get_bytes(entry)?).ok_or_else(invalid)?)
+        }
+        0x005D => PentaxTag::ShutterCount(
+            pentax_shutter_count(&get_bytes(entry)?, date, time).ok_or_else(invalid)?,
+        ),
+        0x0229 => PentaxTag::SerialNumber(get_string(entry)?),
+        _ => PentaxTag::Unknown(entry.clone()),
+    };
+
+    Ok(tag)
+}
+
+/// Decodes the entries of a Pentax makernote. The shutter count is only decoded if there's a Date
+/// and Time to de-obfuscate it.
+pub(crate) fn decode_pentax_tags(entries: &[IFDEntry], model: &str) -> Vec<PentaxTag> {
+    let get_raw = |tag: u16| {
+        entries
+            .iter()
+            .find(|entry| entry.tag == tag)
+            .and_then(|entry| get_bytes(entry).ok())
+            .unwrap_or_default()
+    };
+    let date = get_raw(0x0006);
+    let time = get_raw(0x0007);
+
+    decode_entries(entries, |entry| {
+        decode_pentax_entry(entry, &date, &time, model)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        get_drive_mode, get_iso, get_lens_info_type, pentax_shutter_count, PentaxShakeReduction,
+        PentaxTag,
+    };
+    use crate::{
+        get_tag_value,
+        makernote::{read_makernote, MakerNoteTags, MakerNoteVendor, TestIfd},
+        utils::Endianness,
+    };
+
+    #[test]
+    fn test_pentax_makernote() {
+        let date = [0x07, 0xE8, 6, 15]; // 2024:06:15
+        let time = [14, 30, 5];
+        let shutter_count = pentax_shutter_count(&12345_u32.to_be_bytes(), &date, &time)
+            .unwrap()
+            .to_be_bytes();
+
+        // An "AOC\0" makernote with its own byte order at offset 8 of the EXIF data, whose offsets
+        // are relative to the TIFF header
+        let mut tiff_data = b"MM\0\x2a\0\0\0\x08AOC\0II".to_vec();
+        tiff_data.extend(
+            TestIfd::new(Endianness::Little)
+                .add(0x0000, 1, 4, &[10, 0, 0, 0])
+                .add_undefined(0x0006, &date)
+                .add_undefined(0x0007, &time)
+                .add_shorts(0x0008, &[2])
+                .add_shorts(0x000D, &[17])
+                .add_shorts(0x000E, &[6])
+                .add_shorts(0x0014, &[12])
+                .add(0x0034, 1, 4, &[1, 2, 0, 0])
+                .add(0x003F, 1, 2, &[3, 19])
+                .add_undefined(0x005C, &[1, 1, 0, 0])
+                .add_undefined(0x005D, &shutter_count)
+                .add_ascii(0x0229, "4123456")
+                .build(14),
+        );
+
+        let makernote = read_makernote(
+            &tiff_data,
+            8,
+            tiff_data[8..].to_vec(),
+            ("PENTAX", "PENTAX K-5"),
+            Endianness::Big,
+        );
+        assert_eq!(makernote.vendor, MakerNoteVendor::Pentax);
+        let tags = match &makernote.tags {
+            MakerNoteTags::Pentax(tags) => tags,
+            other => panic!("Expected Pentax tags (got {:?})", other),
+        };
+
+        assert_eq!(
+            get_tag_value!(tags, PentaxTag::PentaxVersion).unwrap(),
+            "10.0.0.0"
+        );
+        assert_eq!(get_tag_value!(tags, PentaxTag::Date).unwrap(), "2024:06:15");
+        assert_eq!(get_tag_value!(tags, PentaxTag::Time).unwrap(), "14:30:05");
+        assert_eq!(get_tag_value!(tags, PentaxTag::Quality).unwrap(), "Best");
+        assert_eq!(
+            get_tag_value!(tags, PentaxTag::FocusMode).unwrap(),
+            "AF-C (Focus-priority)"
+        );
+        assert_eq!(
+            get_tag_value!(tags, PentaxTag::AfPointSelected).unwrap(),
+            "Center"
+        );
+        assert_eq!(get_tag_value!(tags, PentaxTag::Iso), Some(&400));
+        assert_eq!(
+            get_tag_value!(tags, PentaxTag::DriveMode).unwrap(),
+            "Continuous, Self-timer (2 s)"
+        );
+        assert_eq!(
+            get_tag_value!(tags, PentaxTag::LensType).unwrap(),
+            "smc PENTAX-F 24-50mm F4"
+        );
+        assert_eq!(
+            get_tag_value!(tags, PentaxTag::ShakeReduction),
+            Some(&PentaxShakeReduction {
+                stabilized: true,
+                shake_reduction: "On".to_string(),
+            })
+        );
+        assert_eq!(get_tag_value!(tags, PentaxTag::ShutterCount), Some(&12345));
+        assert_eq!(
+            get_tag_value!(tags, PentaxTag::SerialNumber).unwrap(),
+            "4123456"
+        );
+        assert!(makernote.unknown_entries().is_empty());
+    }
+
+    #[test]
+    fn test_pentax_lens_info() {
+        // "PENTAX \0" with its own byte order, and offsets relative to the makernote
+        let mut data = b"PENTAX \0MM".to_vec();
+        data.extend(
+            TestIfd::new(Endianness::Big)
+                .add_undefined(0x0207, &[0xF3, 0x00, 0x00, 0x16, 0, 0, 0, 0])
+                .add_undefined(0x005D, &[0, 0, 0, 1])
+                .build(10),
+        );
+
+        let makernote = read_makernote(
+            &[],
+            0,
+            data,
+            ("RICOH IMAGING COMPANY, LTD.", "PENTAX K-1"),
+            Endianness::Little,
+        );
+        let tags = match &makernote.tags {
+            MakerNoteTags::Pentax(tags) => tags,
+            other => panic!("Expected Pentax tags (got {:?})", other),
+        };
+        assert_eq!(
+            get_tag_value!(tags, PentaxTag::LensType).unwrap(),
+            "smc PENTAX-F FISH-EYE 17-28mm F3.5-4.5"
+        );
+
+        // Without the Date and Time the shutter count can't be read
+        assert_eq!(get_tag_value!(tags, PentaxTag::ShutterCount), None);
+        assert_eq!(makernote.unknown_entries().len(), 1);
+    }
+
+    #[test]
+    fn test_lens_info_versions() {
+        let mut lens_info = vec![0; 36];
+        lens_info[..2].copy_from_slice(&[3, 22]);
+        assert_eq!(
+            get_lens_info_type(&lens_info, "PENTAX *ist D"),
+            Some((3, 22))
+        );
+
+        let mut lens_info = vec![0; 90];
+        lens_info[1..5].copy_from_slice(&[0xF7, 0, 0, 203]);
+        assert_eq!(get_lens_info_type(&lens_info, "PENTAX K-5"), Some((7, 203)));
+
+        let mut lens_info = vec![0; 128];
+        lens_info[1..6].copy_from_slice(&[0x08, 0, 0, 0, 62]);
+        assert_eq!(get_lens_info_type(&lens_info, "PENTAX K-1"), Some((8, 62)));
+
+        let mut lens_info = vec![0; 69];
+        lens_info[..4].copy_from_slice(&[0xF8, 0, 0, 242]);
+        assert_eq!(
+            get_lens_info_type(&lens_info, "PENTAX K20D"),
+            Some((8, 242))
+        );
+
+        assert_eq!(get_lens_info_type(&[8, 0, 0], "PENTAX K20D"), None);
+    }
+
+    #[test]
+    fn test_iso() {
+        assert_eq!(get_iso(33), Some(51200));
+        assert_eq!(get_iso(45), Some(819200));
+        assert_eq!(get_iso(278), Some(51200));
+        assert_eq!(get_iso(46), None);
+    }
+
+    #[test]
+    fn test_drive_mode() {
+        assert_eq!(get_drive_mode(&[2, 0]).unwrap(), "Continuous (Lo)");
+        assert_eq!(
+            get_drive_mode(&[0, 16]).unwrap(),
+            "Single-frame, Mirror Lock-up"
+        );
+    }
+
+    #[test]
+    fn test_af_point_selected_models() {
+        // A "PENTAX \0" makernote with offsets relative to the makernote
+        let mut data = b"PENTAX \0MM".to_vec();
+        data.extend(
+            TestIfd::new(Endianness::Big)
+                .add_shorts(0x000E, &[6])
+                .build(10),
+        );
+
+        let read_tags = |model: &str| {
+            let makernote = read_makernote(
+                &[],
+                0,
+                data.clone(),
+                ("RICOH IMAGING COMPANY, LTD.", model),
+                Endianness::Little,
+            );
+            match makernote.tags {
+                MakerNoteTags::Pentax(tags) => tags,
+                other => panic!("Expected Pentax tags (got {:?})", other),
+            }
+        };
+
+        let tags = read_tags("PENTAX K-5 II s");
+        assert_eq!(
+            get_tag_value!(tags, PentaxTag::AfPointSelected).unwrap(),
+            "Center"
+        );
+        let tags = read_tags("PENTAX K-1");
+        assert_eq!(get_tag_value!(tags, PentaxTag::AfPointSelected), None);
+        assert!(matches!(&tags[..], [PentaxTag::Unknown(entry)] if entry.tag == 0x000E));
+    }
+}
@@ -0,0 +1,313 @@
+// See <https://exiftool.org/TagNames/Ricoh.html>
+
+use crate::{
+    tiff::{IFDEntry, TiffError},
+    utils::Endianness,
+};
+
+use super::{decode_entries, decode_or_unknown, get_string, read_ifd_checked, read_u16};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct RicohImageInfo {
+    pub preview_image_start: Option<u16>, // Relative to the makernote's offset base
+    pub preview_image_length: Option<u16>,
+    pub data: Vec<u8>,
+}
+
+/// Tags of Ricoh makernotes, which are either an IFD (GR, Caplio and Theta models) or text of
+/// "key value;" pairs (older models, starting with "Rv" or "Rev")
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub enum RicohTag {
+    Unknown(IFDEntry),
+    UnknownText(String, String), // A key and value of a text makernote
+
+    MakerNoteType(String),
+    FirmwareVersion(String),
+    SerialNumber(String),
+    RecordingFormat(String),
+    ImageInfo(RicohImageInfo),
+    RedGain(String),
+    GreenGain(String),
+    BlueGain(String),
+
+    // From the Theta sub-IFD of the spherical cameras
+    Accelerometer([f64; 2]), // Roll and pitch of the camera, in degrees
+    Compass(f64),            // Heading in degrees
+}
+
+/// Whether a makernote is the text format, which has no header
+pub(crate) fn is_ricoh_text(data: &[u8]) -> bool {
+    (data.starts_with(b"Rv") || data.starts_with(b"Rev"))
+        && data
+            .get(2..7)
+            .is_some_and(|v| v.iter().any(|b| b.is_ascii_digit()))
+}
+
+/// Decodes a text makernote, e.g. "Rv0207;Sf6C84;Rg76;Bg60;Gg42;", which is padded with NULs
+pub(crate) fn decode_ricoh_text(data: &[u8]) -> Vec<RicohTag> {
+    let end = data.iter().position(|b| *b == 0).unwrap_or(data.len());
+
+    String::from_utf8_lossy(&data[..end])
+        .split(';')
+        .map(|field| field.trim())
+        .filter(|field| !field.is_empty())
+        .map(|field| {
+            // Keys are letters, values start at the first digit
+            let split = field
+                .find(|c: char| c.is_ascii_digit())
+                .unwrap_or(field.len());
+            let (key, value) = field.split_at(split);
+            let value = value.to_string();
+
+            match key {
+                "Rv" | "Rev" => RicohTag::FirmwareVersion(value),
+                "Rg" => RicohTag::RedGain(value),
+                "Gg" => RicohTag::GreenGain(value),
+                "Bg" => RicohTag::BlueGain(value),
+                _ => RicohTag::UnknownText(key.to_string(), value),
+            }
+        })
+        .collect()
+}
+
+fn get_image_info(data: Vec<u8>) -> RicohImageInfo {
+    // ImageInfo is always big-endian
+    RicohImageInfo {
+        preview_image_start: read_u16(&data, 28, Endianness::Big),
+        preview_image_length: read_u16(&data, 30, Endianness::Big),
+        data,
+    }
+}
+
+/// The serial number is text on newer models, otherwise it's shown as hex
+fn get_serial_number(entry: &IFDEntry) -> Result<String, TiffError> {
+    let bytes: Vec<u8> = match get_string(entry) {
+        Ok(serial) => return Ok(serial),
+        Err(_) => entry.clone().try_into()?,
+    };
+    let bytes: Vec<u8> = bytes
+        .into_iter()
+        .rev()
+        .skip_while(|b| *b == 0)
+        .collect::<Vec<u8>>()
+        .into_iter()
+        .rev()
+        .collect();
+
+    if bytes.iter().all(|b| b.is_ascii_graphic() || *b == b' ') {
+        Ok(String::from_utf8_lossy(&bytes).trim().to_string())
+    } else {
+        Ok(bytes.iter().map(|b| format!("{:02X}", b)).collect())
+    }
+}
+
+fn decode_theta_entry(entry: &IFDEntry) -> Result<RicohTag, TiffError> {
+    let tag = match entry.tag {
+        0x0003 => {
+            let values: Vec<f64> = entry.clone().try_into()?;
+            match values.as_slice() {
+                [roll, pitch] => RicohTag::Accelerometer([*roll, *pitch]),
+                _ => {
+                    return Err(TiffError(format!(
+                        "[Ricoh Theta tag 3] Expected 2 values (got {})",
+                        values.len()
+                    )))
+                }
+            }
+        }
+        0x0004 => RicohTag::Compass(entry.clone().try_into()?),
+        _ => RicohTag::Unknown(entry.clone()),
+    };
+
+    Ok(tag)
+}
+
+/// Decodes the Theta sub-IFD, whose offset is relative to `base` like the makernote's own values
+fn decode_theta_subdir(
+    entry: &IFDEntry,
+    base: &[u8],
+    endianness: Endianness,
+) -> Result<Vec<RicohTag>, TiffError> {
+    let offset: u32 = entry.clone().try_into()?;
+    let entries = read_ifd_checked(base, offset as usize, endianness)?;
+
+    Ok(decode_entries(&entries, decode_theta_entry))
+}
+
+fn decode_ricoh_entry(entry: &IFDEntry) -> Result<RicohTag, TiffError> {
+    let tag = match entry.tag {
+        0x0001 => RicohTag::MakerNoteType(get_string(entry)?),
+        0x0002 => RicohTag::FirmwareVersion(get_string(entry)?),
+        0x0005 => RicohTag::SerialNumber(get_serial_number(entry)?),
+        0x1000 => RicohTag::RecordingFormat(
+            match <IFDEntry as TryInto<u16>>::try_into(entry.clone())? {
+                2 => "JPEG",
+                3 => "TIFF",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        0x1001 => RicohTag::ImageInfo(get_image_info(entry.clone().try_into()?)),
+        _ => RicohTag::Unknown(entry.clone()),
+    };
+
+    Ok(tag)
+}
+
+/// Decodes the entries of an IFD based makernote, including the Theta sub-IFD. `base` is the data
+/// the entries' offsets are relative to.
+pub(crate) fn decode_ricoh_tags(
+    entries: &[IFDEntry],
+    base: &[u8],
+    endianness: Endianness,
+) -> Vec<RicohTag> {
+    let mut tags: Vec<RicohTag> = vec![];
+
+    for entry in entries {
+        match entry.tag {
+            0x4001 => match decode_theta_subdir(entry, base, endianness) {
+                Ok(theta_tags) => tags.extend(theta_tags),
+                Err(_) => tags.push(RicohTag::Unknown(entry.clone())),
+            },
+            _ => tags.push(decode_or_unknown(entry, decode_ricoh_entry)),
+        }
+    }
+
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::RicohTag;
+    use crate::{
+        get_tag_value,
+        jpeg::read_jpeg,
+        makernote::{read_makernote, MakerNoteTags, MakerNoteVendor, TestIfd},
+        utils::Endianness,
+    };
+
+    #[test]
+    fn test_text_makernote() {
+        let data = fs::read("test_images/ricoh_sample.jpeg").unwrap();
+        let exif = read_jpeg(&data).unwrap().exif.unwrap();
+        let makernote = exif.makernote().unwrap();
+        assert_eq!(makernote.vendor, MakerNoteVendor::Ricoh);
+        assert_eq!(makernote.offset_base, None);
+
+        let tags = match &makernote.tags {
+            MakerNoteTags::Ricoh(tags) => tags,
+            other => panic!("Expected Ricoh tags (got {:?})", other),
+        };
+        assert_eq!(
+            get_tag_value!(tags, RicohTag::FirmwareVersion).unwrap(),
+            "0207"
+        );
+        assert_eq!(get_tag_value!(tags, RicohTag::RedGain).unwrap(), "76");
+        assert_eq!(get_tag_value!(tags, RicohTag::GreenGain).unwrap(), "42");
+        assert_eq!(get_tag_value!(tags, RicohTag::BlueGain).unwrap(), "60");
+        assert!(tags.iter().any(|tag| matches!(tag,
+            RicohTag::UnknownText(key, value) if key == "Ln" && value == "116900086D27")));
+        assert!(!tags
+            .iter()
+            .any(|tag| matches!(tag, RicohTag::UnknownText(key, _) if key == "Rv")));
+    }
+
+    #[test]
+    fn test_ifd_makernote() {
+        // A "RICOH" makernote at offset 8 of the EXIF data, whose offsets are relative to the TIFF
+        // header, with a Theta sub-IFD at offset 98
+        let mut tiff_data = b"MM\0\x2a\0\0\0\x08".to_vec();
+        tiff_data.extend(b"RICOH\0\0\0");
+        tiff_data.extend(b"\0\x05");
+        tiff_data.extend(b"\0\x01\0\x02\0\0\0\x04Rdc\0");
+        tiff_data.extend(b"\0\x02\0\x02\0\0\0\x08\0\0\0\x52"); // "Rev0104" at 82
+        tiff_data.extend(b"\0\x05\0\x07\0\0\0\x08\0\0\0\x5a"); // Serial at 90
+        tiff_data.extend(b"\x10\0\0\x03\0\0\0\x01\0\x02\0\0");
+        tiff_data.extend(b"\x40\x01\0\x04\0\0\0\x01\0\0\0\x62");
+        tiff_data.extend(b"\0\0\0\0");
+        assert_eq!(tiff_data.len(), 82);
+        tiff_data.extend(b"Rev0104\0");
+        tiff_data.extend(b"\x12\x34\x56\x78\0\0\0\0");
+        tiff_data.extend(b"\0\x02");
+        tiff_data.extend(b"\0\x03\0\x0a\0\0\0\x02\0\0\0\x80");
+        tiff_data.extend(b"\0\x04\0\x05\0\0\0\x01\0\0\0\x90");
+        tiff_data.extend(b"\0\0\0\0");
+        assert_eq!(tiff_data.len(), 128);
+        tiff_data.extend(b"\xFF\xFF\xFF\xFD\0\0\0\x02\0\0\0\x05\0\0\0\x01"); // -1.5, 5
+        tiff_data.extend(b"\0\0\x0b\x4c\0\0\0\x0a"); // 289.2
+
+        let makernote = read_makernote(
+            &tiff_data,
+            8,
+            tiff_data[8..].to_vec(),
+            ("RICOH IMAGING COMPANY, LTD.", "RICOH THETA S"),
+            Endianness::Big,
+        );
+        assert_eq!(makernote.vendor, MakerNoteVendor::Ricoh);
+        let tags = match &makernote.tags {
+            MakerNoteTags::Ricoh(tags) => tags,
+            other => panic!("Expected Ricoh tags (got {:?})", other),
+        };
+
+        assert_eq!(
+            get_tag_value!(tags, RicohTag::MakerNoteType).unwrap(),
+            "Rdc"
+        );
+        assert_eq!(
+            get_tag_value!(tags, RicohTag::FirmwareVersion).unwrap(),
+            "Rev0104"
+        );
+        assert_eq!(
+            get_tag_value!(tags, RicohTag::SerialNumber).unwrap(),
+            "12345678"
+        );
+        assert_eq!(
+            get_tag_value!(tags, RicohTag::RecordingFormat).unwrap(),
+            "JPEG"
+        );
+        assert_eq!(
+            get_tag_value!(tags, RicohTag::Accelerometer).unwrap(),
+            &[-1.5, 5.0]
+        );
+        assert_eq!(get_tag_value!(tags, RicohTag::Compass).unwrap(), &289.2);
+        assert_eq!(makernote.unknown_entries().len(), 0);
+    }
+
+    #[test]
+    fn test_theta_unknown_entries() {
+        // The Theta sub-IFD follows the makernote's IFD, which is at offset 16 of the EXIF data
+        let main_ifd = |theta_offset: u32| {
+            TestIfd::new(Endianness::Big)
+                .add_longs(0x4001, &[theta_offset])
+                .build(16)
+        };
+        let theta_offset = 16 + main_ifd(0).len();
+        let mut tiff_data = b"MM\0\x2a\0\0\0\x08RICOH\0\0\0".to_vec();
+        tiff_data.extend(main_ifd(theta_offset as u32));
+        tiff_data.extend(
+            TestIfd::new(Endianness::Big)
+                .add_shorts(0x0003, &[1])
+                .add_shorts(0x0010, &[7])
+                .build(theta_offset),
+        );
+
+        let makernote = read_makernote(
+            &tiff_data,
+            8,
+            tiff_data[8..].to_vec(),
+            ("RICOH IMAGING COMPANY, LTD.", "RICOH THETA Z1"),
+            Endianness::Big,
+        );
+
+        // The accelerometer needs 2 values, and 0x0010 isn't decoded
+        let unknown: Vec<u16> = makernote
+            .unknown_entries()
+            .iter()
+            .map(|entry| entry.tag)
+            .collect();
+        assert_eq!(unknown, vec![0x0003, 0x0010]);
+    }
+}
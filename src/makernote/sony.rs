@@ -0,0 +1,495 @@
+// See <https://exiftool.org/TagNames/Sony.html>
+
+use crate::{
+    tiff::{IFDEntry, IFDEntryValue, TiffError},
+    utils::Endianness,
+};
+
+use super::{decode_entries, get_bytes, get_string, read_u16, read_u32};
+
+/// The enciphered block 0x9050, whose layout depends on the model
+#[derive(Debug, PartialEq, Clone)]
+pub struct SonyTag9050 {
+    pub shutter_count: Option<u32>,
+    pub lens_mount: Option<String>,
+    pub lens_type: Option<u16>,  // A-mount lenses
+    pub lens_type2: Option<u16>, // E-mount lenses and adapters
+    pub data: Vec<u8>,           // Deciphered
+}
+
+/// The enciphered block 0x9400
+#[derive(Debug, PartialEq, Clone)]
+pub struct SonyTag9400 {
+    pub sequence_image_number: Option<u32>,
+    pub sequence_file_number: Option<u32>,
+    pub shot_number_since_power_up: Option<u32>,
+    pub data: Vec<u8>, // Deciphered
+}
+
+/// Tags of Sony makernotes
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub enum SonyTag {
+    Unknown(IFDEntry),
+    Deciphered(u16, Vec<u8>), // An enciphered block we don't decode further, e.g. 0x2010
+
+    Quality(String),
+    WhiteBalance(String),
+    Contrast(i32),
+    Saturation(i32),
+    Sharpness(i32),
+    FocusMode(String),
+    SonyModelId(u16),
+    CreativeStyle(String),
+    ColorTemperature(u32), // Kelvin, 0 for auto
+    ImageStabilization(String),
+    LensType(u32),
+    LensSpec(String), // e.g. "18-55mm F3.5-5.6"
+    Tag9050(SonyTag9050),
+    Tag9400(SonyTag9400),
+}
+
+/// Sony's substitution cipher replaces each byte below 249 with its cube modulo 249, so it's
+/// reversed by raising to the 55th power since 3 * 55 = 1 (mod 164)
+pub fn sony_decipher(data: &[u8]) -> Vec<u8> {
+    let decipher = |byte: u8| {
+        if byte >= 249 {
+            return byte;
+        }
+
+        (0..55).fold(1_u32, |value, _| value * byte as u32 % 249) as u8
+    };
+
+    data.iter().map(|b| decipher(*b)).collect()
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Tag9050Layout {
+    A, // SLT, NEX, ILCA and the first ILCE models
+    B, // ILCE models from the 7R II and 7S II, and the ILCA-99M2
+    C, // ILCE models from 2021
+}
+
+fn get_9050_layout(model: &str) -> Option<Tag9050Layout> {
+    const LAYOUT_C: [&str; 12] = [
+        "ILCE-1",
+        "ILCE-1M2",
+        "ILCE-6700",
+        "ILCE-7CM2",
+        "ILCE-7CR",
+        "ILCE-7M4",
+        "ILCE-7RM5",
+        "ILCE-7SM3",
+        "ILCE-9M3",
+        "ILME-FX3",
+        "ILME-FX30",
+        "ZV-E1",
+    ];
+    const LAYOUT_A: [&str; 11] = [
+        "ILCE-3000",
+        "ILCE-3500",
+        "ILCE-5000",
+        "ILCE-5100",
+        "ILCE-6000",
+        "ILCE-7",
+        "ILCE-7M2",
+        "ILCE-7R",
+        "ILCE-7S",
+        "ILCE-QX1",
+        "ILCA-68",
+    ];
+
+    let model = model.trim();
+    match model {
+        m if LAYOUT_C.contains(&m) => Some(Tag9050Layout::C),
+        m if LAYOUT_A.contains(&m) => Some(Tag9050Layout::A),
+        // The one ILCA model with the newer layout
+        "ILCA-99M2" => Some(Tag9050Layout::B),
+        m if m.starts_with("SLT-") || m.starts_with("NEX-") || m.starts_with("ILCA-") => {
+            Some(Tag9050Layout::A)
+        }
+        m if m.starts_with("ILCE-") || m.starts_with("ILME-") || m.starts_with("ZV-E") => {
+            Some(Tag9050Layout::B)
+        }
+        _ => None,
+    }
+}
+
+fn get_tag_9050(data: Vec<u8>, model: &str) -> SonyTag9050 {
+    let layout = get_9050_layout(model);
+
+    // The shutter count is only 3 bytes, the 4th byte is unrelated
+    let shutter_count = match layout {
+        Some(Tag9050Layout::A) => read_u32(&data, 0x32, Endianness::Little),
+        Some(Tag9050Layout::B) | Some(Tag9050Layout::C) => {
+            read_u32(&data, 0x3A, Endianness::Little)
+        }
+        None => None,
+    }
+    .map(|v| v & 0x00FF_FFFF);
+
+    // Newer models don't store the lens here
+    let has_lens = matches!(layout, Some(Tag9050Layout::A) | Some(Tag9050Layout::B));
+    let lens_mount = data
+        .get(0x105)
+        .filter(|_| has_lens)
+        .map(|v| match v {
+            1 => "A-mount",
+            16 => "E-mount",
+            _ => "Unknown",
+        })
+        .map(|v| v.to_string());
+
+    SonyTag9050 {
+        shutter_count,
+        lens_mount,
+        lens_type2: read_u16(&data, 0x107, Endianness::Little).filter(|_| has_lens),
+        lens_type: read_u16(&data, 0x109, Endianness::Little).filter(|_| has_lens),
+        data,
+    }
+}
+
+fn get_tag_9400(data: Vec<u8>) -> SonyTag9400 {
+    // The first byte identifies the layout, only the oldest one is known
+    let is_known = matches!(data.first(), Some(0x07) | Some(0x09) | Some(0x0A));
+    let read = |offset: usize| read_u32(&data, offset, Endianness::Little).filter(|_| is_known);
+
+    // The numbers are stored starting from 0
+    SonyTag9400 {
+        sequence_image_number: read(0x08).map(|v| v + 1),
+        sequence_file_number: read(0x0C).map(|v| v + 1),
+        shot_number_since_power_up: read(0x1A).map(|v| v + 1),
+        data: data.clone(),
+    }
+}
+
+/// LensSpec is 8 bytes of BCD: flags, the min and max focal length as 2 bytes each, the max
+/// aperture at each (in tenths), then more flags
+fn get_lens_spec(data: &[u8]) -> Option<String> {
+    let bcd = |b: u8| ((b >> 4) * 10 + (b & 0x0F)) as u32;
+    if data.len() != 8 {
+        return None;
+    }
+
+    let min_focal = bcd(data[1]) * 100 + bcd(data[2]);
+    let max_focal = bcd(data[3]) * 100 + bcd(data[4]);
+    let min_aperture = bcd(data[5]) as f64 / 10.0;
+    let max_aperture = bcd(data[6]) as f64 / 10.0;
+    if min_focal == 0 {
+        return None;
+    }
+
+    let focal = match min_focal == max_focal || max_focal == 0 {
+        true => format!("{}mm", min_focal),
+        false => format!("{}-{}mm", min_focal, max_focal),
+    };
+    let aperture = match min_aperture == max_aperture || max_aperture == 0.0 {
+        true => format!("F{:.1}", min_aperture),
+        false => format!("F{:.1}-{:.1}", min_aperture, max_aperture),
+    };
+
+    Some(format!("{} {}", focal, aperture))
+}
+
+fn get_slong(entry: &IFDEntry) -> Result<i32, TiffError> {
+    match entry.get_single_value()? {
+        IFDEntryValue::SLONG(v) => Ok(v),
+        IFDEntryValue::LONG(v) => Ok(v as i32),
+        v => Err(TiffError(format!(
+            "[Sony tag {}] Expected an SLONG (got {:?})",
+            entry.tag, v
+        ))),
+    }
+}
+
+fn get_u8(entry: &IFDEntry) -> Result<u8, TiffError> {
+    let values: Vec<u8> = entry.clone().try_into()?;
+
+    values.first().copied().ok_or(TiffError(format!(
+        "[Sony tag {}] Expected at least one value",
+        entry.tag
+    )))
+}
+
+fn decode_sony_entry(entry: &IFDEntry, model: &str) -> Result<SonyTag, TiffError> {
+    let tag = match entry.tag {
+        0x0102 => SonyTag::Quality(
+            match <IFDEntry as TryInto<u32>>::try_into(entry.clone())? {
+                0 => "RAW",
+                1 => "Super Fine",
+                2 => "Fine",
+                3 => "Standard",
+                4 => "Economy",
+                5 => "Extra Fine",
+                6 => "RAW + JPEG/HEIF",
+                7 => "Compressed RAW",
+                8 => "Compressed RAW + JPEG",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        0x0115 => SonyTag::WhiteBalance(
+            match <IFDEntry as TryInto<u32>>::try_into(entry.clone())? {
+                0 => "Auto",
+                4 => "Custom",
+                5 => "Daylight",
+                6 => "Cloudy",
+                7 => "Cool White Fluorescent",
+                8 => "Day White Fluorescent",
+                9 => "Daylight Fluorescent",
+                10 => "Incandescent2",
+                11 => "Warm White Fluorescent",
+                14 => "Incandescent",
+                15 => "Flash",
+                17 => "Underwater 1 (Blue Water)",
+                18 => "Underwater 2 (Green Water)",
+                19 => "Underwater Auto",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        0x2004 => SonyTag::Contrast(get_slong(entry)?),
+        0x2005 => SonyTag::Saturation(get_slong(entry)?),
+        0x2006 => SonyTag::Sharpness(get_slong(entry)?),
+        0x2010 => SonyTag::Deciphered(entry.tag, sony_decipher(&get_bytes(entry)?)),
+        0x201B => SonyTag::FocusMode(
+            match get_u8(entry)? {
+                0 => "Manual",
+                2 => "AF-S",
+                3 => "AF-C",
+                4 => "AF-A",
+                6 => "DMF",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        0x9050 => SonyTag::Tag9050(get_tag_9050(sony_decipher(&get_bytes(entry)?), model)),
+        0x9400 => SonyTag::Tag9400(get_tag_9400(sony_decipher(&get_bytes(entry)?))),
+        0x9401..=0x94FF => SonyTag::Deciphered(entry.tag, sony_decipher(&get_bytes(entry)?)),
+        0xB001 => SonyTag::SonyModelId(entry.clone().try_into()?),
+        0xB020 => SonyTag::CreativeStyle(get_string(entry)?),
+        0xB021 => SonyTag::ColorTemperature(entry.clone().try_into()?),
+        0xB026 => SonyTag::ImageStabilization(
+            match <IFDEntry as TryInto<u32>>::try_into(entry.clone())? {
+                0 => "Off",
+                1 => "On",
+                0xFFFFFFFF => "n/a",
+                _ => "Unknown",
+            }
+            .to_string(),
+        ),
+        0xB027 => SonyTag::LensType(entry.clone().try_into()?),
+        0xB02A => match get_lens_spec(&get_bytes(entry)?) {
+            Some(lens_spec) => SonyTag::LensSpec(lens_spec),
+            None => SonyTag::Unknown(entry.clone()),
+        },
+        _ => SonyTag::Unknown(entry.clone()),
+    };
+
+    Ok(tag)
+}
+
+/// Decodes the entries of a Sony makernote, deciphering the enciphered blocks.
+pub(crate) fn decode_sony_tags(entries: &[IFDEntry], model: &str) -> Vec<SonyTag> {
+    decode_entries(entries, |entry| decode_sony_entry(entry, model))
+}
+
+/// The name of an E-mount lens or adapter from the LensType2 in block 0x9050
+pub fn sony_e_mount_lens_name(lens_type2: u16) -> Option<&'static str> {
+    let name = match lens_type2 {
+        0 => "Unknown E-mount lens or other lens",
+        1 => "Sony LA-EA1 or Sigma MC-11 Adapter",
+        2 => "Sony LA-EA2 Adapter",
+        3 => "Sony LA-EA3 Adapter",
+        6 => "Sony LA-EA4 Adapter",
+        7 => "Sony LA-EA5 Adapter",
+        32768 => "Sony E 16mm F2.8",
+        32769 => "Sony E 18-55mm F3.5-5.6 OSS",
+        32770 => "Sony E 55-210mm F4.5-6.3 OSS",
+        32771 => "Sony E 18-200mm F3.5-6.3 OSS",
+        32772 => "Sony E 30mm F3.5 Macro",
+        32773 => "Sony E 24mm F1.8 ZA",
+        32774 => "Sony E 50mm F1.8 OSS",
+        32775 => "Sony E 16-70mm F4 ZA OSS",
+        32776 => "Sony E 10-18mm F4 OSS",
+        32777 => "Sony E PZ 16-50mm F3.5-5.6 OSS",
+        32778 => "Sony FE 35mm F2.8 ZA",
+        32779 => "Sony FE 24-70mm F4 ZA OSS",
+        32780 => "Sony FE 85mm F1.8",
+        32781 => "Sony E 18-200mm F3.5-6.3 OSS LE",
+        32782 => "Sony E 20mm F2.8",
+        32783 => "Sony E 35mm F1.8 OSS",
+        32784 => "Sony E PZ 18-105mm F4 G OSS",
+        32785 => "Sony FE 12-24mm F4 G",
+        32786 => "Sony FE 90mm F2.8 Macro G OSS",
+        32787 => "Sony E 18-50mm F4-5.6",
+        32788 => "Sony FE 24mm F1.4 GM",
+        32789 => "Sony FE 24-105mm F4 G OSS",
+        32790 => "Sony E PZ 18-200mm F3.5-6.3 OSS",
+        32791 => "Sony FE 55mm F1.8 ZA",
+        32792 => "Sony FE 70-200mm F4 G OSS",
+        32793 => "Sony FE 16-35mm F4 ZA OSS",
+        32794 => "Sony FE 50mm F2.8 Macro",
+        32795 => "Sony FE 28-70mm F3.5-5.6 OSS",
+        32796 => "Sony FE 35mm F1.4 ZA",
+        32797 => "Sony FE 24-240mm F3.5-6.3 OSS",
+        32798 => "Sony FE 28mm F2",
+        32799 => "Sony FE PZ 28-135mm F4 G OSS",
+        32801 => "Sony FE 100mm F2.8 STF GM OSS",
+        32802 => "Sony E PZ 18-110mm F4 G OSS",
+        32803 => "Sony FE 24-70mm F2.8 GM",
+        32804 => "Sony FE 50mm F1.4 ZA",
+        32805 => "Sony FE 85mm F1.4 GM",
+        32806 => "Sony FE 50mm F1.8",
+        32807 => "Sony FE 21mm F2.8 (SEL28F20 + SEL075UWC)",
+        32808 => "Sony FE 16mm F3.5 Fisheye (SEL28F20 + SEL057FEC)",
+        32809 => "Sony FE 70-300mm F4.5-5.6 G OSS",
+        32810 => "Sony FE 100-400mm F4.5-5.6 GM OSS",
+        32811 => "Sony FE 70-200mm F2.8 GM OSS",
+        32812 => "Sony FE 16-35mm F2.8 GM",
+        32813 => "Sony FE 400mm F2.8 GM OSS",
+        32814 => "Sony E 18-135mm F3.5-5.6 OSS",
+        _ => return None,
+    };
+
+    Some(name)
+}
+
+/// Identifies the lens from the decoded tags: E-mount lenses by their LensType2, otherwise the
+/// LensSpec, e.g. "18-55mm F3.5-5.6"
+pub fn sony_lens(tags: &[SonyTag]) -> Option<String> {
+    let mut lens_spec: Option<&String> = None;
+    let mut lens_type2: Option<u16> = None;
+    for tag in tags {
+        match tag {
+            SonyTag::LensSpec(spec) => lens_spec = Some(spec),
+            SonyTag::Tag9050(tag) if tag.lens_mount.as_deref() == Some("E-mount") => {
+                lens_type2 = tag.lens_type2
+            }
+            _ => {}
+        }
+    }
+
+    lens_type2
+        .and_then(sony_e_mount_lens_name)
+        .filter(|_| lens_type2 != Some(0))
+        .map(|name| name.to_string())
+        .or(lens_spec.cloned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_9050_layout, get_tag_9050, sony_decipher, sony_lens, SonyTag, Tag9050Layout};
+    use crate::{
+        get_tag_value,
+        makernote::{read_makernote, MakerNoteTags, MakerNoteVendor, TestIfd},
+        utils::Endianness,
+    };
+
+    fn encipher(data: &[u8]) -> Vec<u8> {
+        data.iter()
+            .map(|b| match *b {
+                b if b >= 249 => b,
+                b => ((b as u32).pow(3) % 249) as u8,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sony_decipher() {
+        let data: Vec<u8> = (0..=255).collect();
+        assert_ne!(encipher(&data), data);
+        assert_eq!(sony_decipher(&encipher(&data)), data);
+
+        // Values from the cipher's definition, 2^3 = 8 and 7^3 = 343 = 94 (mod 249)
+        assert_eq!(sony_decipher(&[8, 94, 0, 1, 250]), vec![2, 7, 0, 1, 250]);
+    }
+
+    #[test]
+    fn test_sony_makernote() {
+        let mut tag_9050 = vec![0_u8; 0x120];
+        tag_9050[0x3A..0x3E].copy_from_slice(&[0x39, 0x30, 0x00, 0xFF]); // 12345
+        tag_9050[0x105] = 16; // E-mount
+        tag_9050[0x107..0x109].copy_from_slice(&32789_u16.to_le_bytes());
+
+        let mut tag_9400 = vec![0_u8; 0x40];
+        tag_9400[0] = 0x0A;
+        tag_9400[0x08] = 2;
+        tag_9400[0x1A] = 41;
+
+        // A "SONY DSC " makernote at offset 8 of the EXIF data, with the IFD after the header
+        let mut tiff_data = b"II*\0\x08\0\0\0SONY DSC \0\0\0".to_vec();
+        tiff_data.extend(
+            TestIfd::new(Endianness::Little)
+                .add_longs(0x0102, &[2])
+                .add_slongs(0x2004, &[-1])
+                .add_undefined(0x2010, &encipher(&[1, 2, 3, 4, 5, 6]))
+                .add(0x201B, 1, 1, &[3])
+                .add_undefined(0x9050, &encipher(&tag_9050))
+                .add_undefined(0x9400, &encipher(&tag_9400))
+                .add_undefined(0x9416, &encipher(&[0x10, 0x20]))
+                .add_ascii(0xB020, "Standard")
+                .add_longs(0xB027, &[65535])
+                .add(0xB02A, 1, 8, &[0, 0x00, 0x24, 0x01, 0x05, 0x40, 0x40, 0])
+                .build(20),
+        );
+
+        let makernote = read_makernote(
+            &tiff_data,
+            8,
+            tiff_data[8..].to_vec(),
+            ("SONY", "ILCE-7M3"),
+            Endianness::Little,
+        );
+        assert_eq!(makernote.vendor, MakerNoteVendor::Sony);
+        let tags = match &makernote.tags {
+            MakerNoteTags::Sony(tags) => tags,
+            other => panic!("Expected Sony tags (got {:?})", other),
+        };
+
+        assert_eq!(get_tag_value!(tags, SonyTag::Quality).unwrap(), "Fine");
+        assert_eq!(get_tag_value!(tags, SonyTag::Contrast), Some(&-1));
+        assert_eq!(get_tag_value!(tags, SonyTag::FocusMode).unwrap(), "AF-C");
+        assert_eq!(
+            get_tag_value!(tags, SonyTag::CreativeStyle).unwrap(),
+            "Standard"
+        );
+        assert_eq!(get_tag_value!(tags, SonyTag::LensType), Some(&65535));
+        assert_eq!(
+            get_tag_value!(tags, SonyTag::LensSpec).unwrap(),
+            "24-105mm F4.0"
+        );
+
+        let tag_9050 = get_tag_value!(tags, SonyTag::Tag9050).unwrap();
+        assert_eq!(tag_9050.shutter_count, Some(12345));
+        assert_eq!(tag_9050.lens_mount.as_deref(), Some("E-mount"));
+        assert_eq!(tag_9050.lens_type2, Some(32789));
+        assert_eq!(sony_lens(tags).unwrap(), "Sony FE 24-105mm F4 G OSS");
+
+        let tag_9400 = get_tag_value!(tags, SonyTag::Tag9400).unwrap();
+        assert_eq!(tag_9400.sequence_image_number, Some(3));
+        assert_eq!(tag_9400.shot_number_since_power_up, Some(42));
+
+        assert!(tags.iter().any(|tag| matches!(tag,
+            SonyTag::Deciphered(0x2010, data) if data == &vec![1, 2, 3, 4, 5, 6])));
+        assert!(tags.iter().any(|tag| matches!(tag,
+            SonyTag::Deciphered(0x9416, data) if data == &vec![0x10, 0x20])));
+        assert!(makernote.unknown_entries().is_empty());
+    }
+
+    #[test]
+    fn test_9050_layout() {
+        for model in ["ILCE-7RM2", "ILCE-7SM2", "ILCA-99M2", "ILCE-7M3"] {
+            assert_eq!(get_9050_layout(model), Some(Tag9050Layout::B), "{}", model);
+        }
+        for model in ["ILCE-7M2", "ILCA-77M2", "SLT-A99V", "NEX-7"] {
+            assert_eq!(get_9050_layout(model), Some(Tag9050Layout::A), "{}", model);
+        }
+
+        // The 9050b shutter count is at 0x3A, not the 9050a offset of 0x32
+        let mut data = vec![0_u8; 0x120];
+        data[0x32..0x36].copy_from_slice(&[1, 0, 0, 0]);
+        data[0x3A..0x3E].copy_from_slice(&[0x39, 0x30, 0x00, 0xFF]);
+        assert_eq!(get_tag_9050(data, "ILCE-7RM2").shutter_count, Some(12345));
+    }
+}
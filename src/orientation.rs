@@ -0,0 +1,223 @@
+/// How an image's stored pixels need to be transformed for display.
+///
+/// Variants are named after the transform needed to display the image, and their discriminants
+/// are the values of the EXIF/TIFF Orientation tag. Every orientation can be described as an
+/// optional horizontal mirror followed by a clockwise rotation, see [`Orientation::is_mirrored`]
+/// and [`Orientation::rotation_degrees`].
+///
+/// See <https://magnushoff.com/articles/jpeg-orientation/>
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum Orientation {
+    Normal = 1,
+    MirrorHorizontal = 2,
+    Rotate180 = 3,
+    MirrorVertical = 4,
+    MirrorHorizontalRotate270 = 5, // Also known as transpose
+    Rotate90 = 6,
+    MirrorHorizontalRotate90 = 7, // Also known as transverse
+    Rotate270 = 8,
+}
+
+impl TryFrom<u16> for Orientation {
+    type Error = String;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Orientation::Normal),
+            2 => Ok(Orientation::MirrorHorizontal),
+            3 => Ok(Orientation::Rotate180),
+            4 => Ok(Orientation::MirrorVertical),
+            5 => Ok(Orientation::MirrorHorizontalRotate270),
+            6 => Ok(Orientation::Rotate90),
+            7 => Ok(Orientation::MirrorHorizontalRotate90),
+            8 => Ok(Orientation::Rotate270),
+            _ => Err(format!("Invalid orientation value: {}", value)),
+        }
+    }
+}
+
+impl Orientation {
+    /// Builds an orientation from a horizontal mirror (applied first) and a clockwise rotation.
+    ///
+    /// The rotation is rounded down to a multiple of 90 degrees.
+    pub fn from_mirror_and_rotation(mirrored: bool, rotation_degrees: u32) -> Orientation {
+        match (mirrored, (rotation_degrees / 90) % 4) {
+            (false, 0) => Orientation::Normal,
+            (false, 1) => Orientation::Rotate90,
+            (false, 2) => Orientation::Rotate180,
+            (false, _) => Orientation::Rotate270,
+            (true, 0) => Orientation::MirrorHorizontal,
+            (true, 1) => Orientation::MirrorHorizontalRotate90,
+            (true, 2) => Orientation::MirrorVertical,
+            (true, _) => Orientation::MirrorHorizontalRotate270,
+        }
+    }
+
+    /// Builds an orientation from the HEIF `irot` and `imir` transformative properties.
+    ///
+    /// `irot_angle` is the anti-clockwise rotation in units of 90 degrees, and `imir_axis` is 0 to
+    /// mirror about a vertical axis (left-right) or 1 to mirror about a horizontal axis
+    /// (top-bottom). As mandated by the HEIF spec the rotation is applied before the mirror.
+    pub fn from_heif(irot_angle: Option<u8>, imir_axis: Option<u8>) -> Orientation {
+        let rotation = (4 - (irot_angle.unwrap_or(0) as u32 % 4)) % 4 * 90;
+
+        // Mirroring after rotating by r is the same as mirroring first and then rotating by -r
+        match imir_axis {
+            None => Orientation::from_mirror_and_rotation(false, rotation),
+            Some(0) => Orientation::from_mirror_and_rotation(true, 360 - rotation),
+            Some(_) => Orientation::from_mirror_and_rotation(true, 540 - rotation),
+        }
+    }
+
+    /// Whether the image needs to be mirrored horizontally (before rotating) for display
+    pub fn is_mirrored(&self) -> bool {
+        matches!(
+            self,
+            Orientation::MirrorHorizontal
+                | Orientation::MirrorVertical
+                | Orientation::MirrorHorizontalRotate270
+                | Orientation::MirrorHorizontalRotate90
+        )
+    }
+
+    /// How far the image needs to be rotated clockwise (after mirroring) for display
+    pub fn rotation_degrees(&self) -> u32 {
+        match self {
+            Orientation::Normal | Orientation::MirrorHorizontal => 0,
+            Orientation::Rotate90 | Orientation::MirrorHorizontalRotate90 => 90,
+            Orientation::Rotate180 | Orientation::MirrorVertical => 180,
+            Orientation::Rotate270 | Orientation::MirrorHorizontalRotate270 => 270,
+        }
+    }
+
+    /// Whether the width and height of the stored image are swapped when displayed
+    pub fn swaps_dimensions(&self) -> bool {
+        self.rotation_degrees() % 180 == 90
+    }
+
+    /// The (width, height) the image will have when displayed
+    pub fn display_dimensions(&self, width: u32, height: u32) -> (u32, u32) {
+        match self.swaps_dimensions() {
+            true => (height, width),
+            false => (width, height),
+        }
+    }
+
+    /// The orientation which undoes this one, i.e. the transform from display back to storage
+    pub fn inverse(&self) -> Orientation {
+        match self {
+            Orientation::Rotate90 => Orientation::Rotate270,
+            Orientation::Rotate270 => Orientation::Rotate90,
+            // Every other orientation is its own inverse
+            orientation => *orientation,
+        }
+    }
+
+    /// Maps the coordinates of a pixel in the stored image (of the given dimensions) to where it
+    /// ends up in the displayed image
+    pub fn transform_point(&self, x: u32, y: u32, width: u32, height: u32) -> (u32, u32) {
+        let x = match self.is_mirrored() {
+            true => width - 1 - x,
+            false => x,
+        };
+
+        match self.rotation_degrees() {
+            90 => (height - 1 - y, x),
+            180 => (width - 1 - x, height - 1 - y),
+            270 => (y, width - 1 - x),
+            _ => (x, y),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Orientation;
+
+    const ALL: [Orientation; 8] = [
+        Orientation::Normal,
+        Orientation::MirrorHorizontal,
+        Orientation::Rotate180,
+        Orientation::MirrorVertical,
+        Orientation::MirrorHorizontalRotate270,
+        Orientation::Rotate90,
+        Orientation::MirrorHorizontalRotate90,
+        Orientation::Rotate270,
+    ];
+
+    #[test]
+    fn test_exif_values() {
+        for (i, orientation) in ALL.iter().enumerate() {
+            assert_eq!(Orientation::try_from(i as u16 + 1).unwrap(), *orientation);
+        }
+
+        assert!(Orientation::try_from(0).is_err());
+        assert!(Orientation::try_from(9).is_err());
+    }
+
+    #[test]
+    fn test_mirror_and_rotation_round_trip() {
+        for orientation in ALL {
+            assert_eq!(
+                Orientation::from_mirror_and_rotation(
+                    orientation.is_mirrored(),
+                    orientation.rotation_degrees()
+                ),
+                orientation,
+            );
+        }
+    }
+
+    #[test]
+    fn test_transform_point() {
+        // A 3x2 image, looking at where the top left and top right pixels end up
+        let cases = [
+            (Orientation::Normal, (0, 0), (2, 0)),
+            (Orientation::MirrorHorizontal, (2, 0), (0, 0)),
+            (Orientation::Rotate180, (2, 1), (0, 1)),
+            (Orientation::MirrorVertical, (0, 1), (2, 1)),
+            (Orientation::MirrorHorizontalRotate270, (0, 0), (0, 2)),
+            (Orientation::Rotate90, (1, 0), (1, 2)),
+            (Orientation::MirrorHorizontalRotate90, (1, 2), (1, 0)),
+            (Orientation::Rotate270, (0, 2), (0, 0)),
+        ];
+
+        for (orientation, top_left, top_right) in cases {
+            assert_eq!(orientation.transform_point(0, 0, 3, 2), top_left, "{:?}", orientation);
+            assert_eq!(orientation.transform_point(2, 0, 3, 2), top_right, "{:?}", orientation);
+        }
+    }
+
+    #[test]
+    fn test_inverse() {
+        for orientation in ALL {
+            let (width, height) = orientation.display_dimensions(3, 2);
+            let inverse = orientation.inverse();
+
+            for (x, y) in [(0, 0), (2, 0), (1, 1), (2, 1)] {
+                let (dx, dy) = orientation.transform_point(x, y, 3, 2);
+                assert_eq!(inverse.transform_point(dx, dy, width, height), (x, y));
+            }
+        }
+    }
+
+    #[test]
+    fn test_display_dimensions() {
+        assert_eq!(Orientation::Normal.display_dimensions(4000, 3000), (4000, 3000));
+        assert_eq!(Orientation::Rotate90.display_dimensions(4000, 3000), (3000, 4000));
+        assert_eq!(Orientation::MirrorHorizontalRotate270.display_dimensions(4000, 3000), (3000, 4000));
+        assert_eq!(Orientation::MirrorVertical.display_dimensions(4000, 3000), (4000, 3000));
+    }
+
+    #[test]
+    fn test_from_heif() {
+        assert_eq!(Orientation::from_heif(None, None), Orientation::Normal);
+        assert_eq!(Orientation::from_heif(Some(1), None), Orientation::Rotate270);
+        assert_eq!(Orientation::from_heif(Some(3), None), Orientation::Rotate90);
+        assert_eq!(Orientation::from_heif(None, Some(0)), Orientation::MirrorHorizontal);
+        assert_eq!(Orientation::from_heif(None, Some(1)), Orientation::MirrorVertical);
+        assert_eq!(Orientation::from_heif(Some(1), Some(0)), Orientation::MirrorHorizontalRotate90);
+        assert_eq!(Orientation::from_heif(Some(3), Some(0)), Orientation::MirrorHorizontalRotate270);
+        assert_eq!(Orientation::from_heif(Some(1), Some(1)), Orientation::MirrorHorizontalRotate270);
+    }
+}
@@ -0,0 +1,467 @@
+// https://www.adobe.com/devnet-apps/photoshop/fileformatashtml/#50577409_38034
+
+use crate::{
+    get_tag_value,
+    iptc::{read_iptc, Iptc},
+    thumbnail::{Thumbnail, ThumbnailFormat},
+    tiff::{Tiff, TiffTag},
+};
+
+#[derive(Debug)]
+pub struct PhotoshopError(pub String);
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PhotoshopResolutionInfo {
+    pub horizontal_resolution: f64,
+    pub horizontal_resolution_unit: String, // "pixels per inch" or "pixels per cm"
+    pub width_unit: String,
+    pub vertical_resolution: f64,
+    pub vertical_resolution_unit: String,
+    pub height_unit: String,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PhotoshopVersionInfo {
+    pub version: u32,
+    pub has_real_merged_data: bool,
+    pub writer_name: String,
+    pub reader_name: String,
+    pub file_version: u32,
+}
+
+/// The decoded value of a resource, resources we don't decode are kept as `Unknown`
+#[derive(Debug, Clone)]
+pub enum PhotoshopResourceValue {
+    ResolutionInfo(PhotoshopResolutionInfo),
+    Caption(String),
+    Iptc(Iptc),
+    Thumbnail(Thumbnail),
+    CopyrightFlag(bool),
+    Url(String),
+    GlobalAngle(i32),
+    IccProfile(Vec<u8>),
+    IccUntagged(bool),
+    GlobalAltitude(i32),
+    VersionInfo(PhotoshopVersionInfo),
+    Xmp(String),
+    CaptionDigest([u8; 16]), // MD5 of the IPTC data, used to detect edits by other applications
+    PrintScale {
+        style: String,
+        x: f32,
+        y: f32,
+        scale: f32,
+    },
+    PixelAspectRatio(f64),
+    Unknown,
+}
+
+/// A single image resource block
+#[derive(Debug, Clone)]
+pub struct PhotoshopResource {
+    pub id: u16,
+    pub kind: String, // The name of the resource type, e.g. "IPTC-NAA record"
+    pub name: String, // The resource's own name, which is almost always empty
+    pub data: Vec<u8>,
+    pub value: PhotoshopResourceValue,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Photoshop {
+    pub resources: Vec<PhotoshopResource>,
+}
+
+fn get_resource_kind(id: u16) -> &'static str {
+    match id {
+        0x03E8 => "Channels, rows, columns, depth and mode",
+        0x03E9 => "Macintosh print manager print info",
+        0x03EB => "Indexed color table",
+        0x03ED => "Resolution info",
+        0x03EE => "Alpha channel names",
+        0x03EF => "Display info (obsolete)",
+        0x03F0 => "Caption",
+        0x03F1 => "Border information",
+        0x03F2 => "Background color",
+        0x03F3 => "Print flags",
+        0x03F4 => "Grayscale and multichannel halftoning information",
+        0x03F5 => "Color halftoning information",
+        0x03F6 => "Duotone halftoning information",
+        0x03F7 => "Grayscale and multichannel transfer function",
+        0x03F8 => "Color transfer functions",
+        0x03F9 => "Duotone transfer functions",
+        0x03FA => "Duotone image information",
+        0x03FB => "Effective black and white values",
+        0x03FD => "EPS options",
+        0x03FE => "Quick Mask information",
+        0x0400 => "Layer state information",
+        0x0401 => "Working path",
+        0x0402 => "Layers group information",
+        0x0404 => "IPTC-NAA record",
+        0x0405 => "Image mode for raw format files",
+        0x0406 => "JPEG quality",
+        0x0408 => "Grid and guides information",
+        0x0409 => "Thumbnail resource (Photoshop 4.0)",
+        0x040A => "Copyright flag",
+        0x040B => "URL",
+        0x040C => "Thumbnail resource",
+        0x040D => "Global Angle",
+        0x040E => "Color samplers resource (obsolete)",
+        0x040F => "ICC Profile",
+        0x0410 => "Watermark",
+        0x0411 => "ICC Untagged Profile",
+        0x0412 => "Effects visible",
+        0x0413 => "Spot Halftone",
+        0x0414 => "Document-specific IDs seed number",
+        0x0415 => "Unicode Alpha Names",
+        0x0416 => "Indexed Color Table Count",
+        0x0417 => "Transparency Index",
+        0x0419 => "Global Altitude",
+        0x041A => "Slices",
+        0x041B => "Workflow URL",
+        0x041C => "Jump To XPEP",
+        0x041D => "Alpha Identifiers",
+        0x041E => "URL List",
+        0x0421 => "Version Info",
+        0x0422 => "EXIF data 1",
+        0x0423 => "EXIF data 3",
+        0x0424 => "XMP metadata",
+        0x0425 => "Caption digest",
+        0x0426 => "Print scale",
+        0x0428 => "Pixel Aspect Ratio",
+        0x0429 => "Layer Comps",
+        0x042A => "Alternate Duotone Colors",
+        0x042B => "Alternate Spot Colors",
+        0x042D => "Layer Selection ID(s)",
+        0x042E => "HDR Toning information",
+        0x042F => "Print info",
+        0x0430 => "Layer Group(s) Enabled ID",
+        0x0431 => "Color samplers resource",
+        0x0432 => "Measurement Scale",
+        0x0433 => "Timeline Information",
+        0x0434 => "Sheet Disclosure",
+        0x0435 => "DisplayInfo",
+        0x0436 => "Onion Skins",
+        0x0438 => "Count Information",
+        0x043A => "Print Information",
+        0x043B => "Print Style",
+        0x043C => "Macintosh NSPrintInfo",
+        0x043D => "Windows DEVMODE",
+        0x043E => "Auto Save File Path",
+        0x043F => "Auto Save Format",
+        0x0440 => "Path Selection State",
+        0x07D0..=0x0BB6 => "Path Information",
+        0x0BB7 => "Name of clipping path",
+        0x0BB8 => "Origin Path Info",
+        0x0FA0..=0x1387 => "Plug-In resource",
+        0x1B58 => "Image Ready variables",
+        0x1B59 => "Image Ready data sets",
+        0x1B5A => "Image Ready default selected state",
+        0x1B5B => "Image Ready 7 rollover expanded state",
+        0x1B5C => "Image Ready rollover expanded state",
+        0x1B5D => "Image Ready save layer settings",
+        0x1B5E => "Image Ready version",
+        0x1F40 => "Lightroom workflow",
+        0x2710 => "Print flags information",
+        _ => "Unknown",
+    }
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(
+        data.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(
+        data.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+/// Reads a "Unicode string", a u32 count of UTF-16 code units followed by the code units
+fn read_unicode_string(data: &[u8], offset: usize) -> Option<(String, usize)> {
+    let count = read_u32(data, offset)? as usize;
+    let code_units: Vec<u16> = data
+        .get(offset + 4..offset + 4 + count * 2)?
+        .chunks_exact(2)
+        .map(|c| u16::from_be_bytes([c[0], c[1]]))
+        .collect();
+
+    Some((
+        String::from_utf16_lossy(&code_units)
+            .trim_end_matches('\0')
+            .to_string(),
+        offset + 4 + count * 2,
+    ))
+}
+
+fn read_resolution_info(data: &[u8]) -> Option<PhotoshopResolutionInfo> {
+    let resolution_unit = |offset| {
+        let unit = match read_u16(data, offset)? {
+            1 => "pixels per inch",
+            2 => "pixels per cm",
+            _ => "Invalid",
+        };
+        Some(unit.to_string())
+    };
+    let dimension_unit = |offset| {
+        let unit = match read_u16(data, offset)? {
+            1 => "inches",
+            2 => "cm",
+            3 => "points",
+            4 => "picas",
+            5 => "columns",
+            _ => "Invalid",
+        };
+        Some(unit.to_string())
+    };
+
+    // The resolutions are 16.16 fixed point numbers
+    Some(PhotoshopResolutionInfo {
+        horizontal_resolution: read_u32(data, 0)? as f64 / 65536.0,
+        horizontal_resolution_unit: resolution_unit(4)?,
+        width_unit: dimension_unit(6)?,
+        vertical_resolution: read_u32(data, 8)? as f64 / 65536.0,
+        vertical_resolution_unit: resolution_unit(12)?,
+        height_unit: dimension_unit(14)?,
+    })
+}
+
+/// The thumbnail resource has a 28 byte header followed by JFIF data
+fn read_thumbnail_resource(data: &[u8]) -> Option<Thumbnail> {
+    // 1 = kJpegRGB, 0 = kRawRGB which no application seems to write
+    if read_u32(data, 0)? != 1 {
+        return None;
+    }
+
+    Some(Thumbnail {
+        format: ThumbnailFormat::Jpeg,
+        width: Some(read_u32(data, 4)?),
+        height: Some(read_u32(data, 8)?),
+        data: data.get(28..)?.to_vec(),
+        tags: vec![],
+    })
+}
+
+fn read_version_info(data: &[u8]) -> Option<PhotoshopVersionInfo> {
+    let (writer_name, offset) = read_unicode_string(data, 5)?;
+    let (reader_name, offset) = read_unicode_string(data, offset)?;
+
+    Some(PhotoshopVersionInfo {
+        version: read_u32(data, 0)?,
+        has_real_merged_data: *data.get(4)? != 0,
+        writer_name,
+        reader_name,
+        file_version: read_u32(data, offset)?,
+    })
+}
+
+fn decode_resource(id: u16, data: &[u8]) -> Option<PhotoshopResourceValue> {
+    let value = match id {
+        0x03ED => PhotoshopResourceValue::ResolutionInfo(read_resolution_info(data)?),
+        0x03F0 => {
+            let length = *data.first()? as usize;
+            PhotoshopResourceValue::Caption(
+                String::from_utf8_lossy(data.get(1..1 + length)?).to_string(),
+            )
+        }
+        0x0404 => PhotoshopResourceValue::Iptc(read_iptc(data).ok()?),
+        0x0409 | 0x040C => PhotoshopResourceValue::Thumbnail(read_thumbnail_resource(data)?),
+        0x040A => PhotoshopResourceValue::CopyrightFlag(*data.first()? != 0),
+        0x040B => PhotoshopResourceValue::Url(String::from_utf8_lossy(data).to_string()),
+        0x040D => PhotoshopResourceValue::GlobalAngle(read_u32(data, 0)? as i32),
+        0x040F => PhotoshopResourceValue::IccProfile(data.to_vec()),
+        0x0411 => PhotoshopResourceValue::IccUntagged(*data.first()? != 0),
+        0x0419 => PhotoshopResourceValue::GlobalAltitude(read_u32(data, 0)? as i32),
+        0x0421 => PhotoshopResourceValue::VersionInfo(read_version_info(data)?),
+        0x0424 => PhotoshopResourceValue::Xmp(String::from_utf8_lossy(data).to_string()),
+        0x0425 => PhotoshopResourceValue::CaptionDigest(data.get(0..16)?.try_into().ok()?),
+        0x0426 => PhotoshopResourceValue::PrintScale {
+            style: match read_u16(data, 0)? {
+                0 => "Centered",
+                1 => "Size to fit",
+                2 => "User defined",
+                _ => "Invalid",
+            }
+            .to_string(),
+            x: f32::from_bits(read_u32(data, 2)?),
+            y: f32::from_bits(read_u32(data, 6)?),
+            scale: f32::from_bits(read_u32(data, 10)?),
+        },
+        0x0428 => PhotoshopResourceValue::PixelAspectRatio(f64::from_be_bytes(
+            data.get(4..12)?.try_into().ok()?,
+        )),
+        _ => PhotoshopResourceValue::Unknown,
+    };
+
+    Some(value)
+}
+
+/// Reads a sequence of image resource blocks. Each one is a signature (usually "8BIM"), a u16 ID,
+/// a Pascal string name padded to an even length and the u32 size of the data, which is also
+/// padded to an even length. Reading stops at the first block without a known signature.
+pub fn read_photoshop_resources(data: &[u8]) -> Result<Photoshop, PhotoshopError> {
+    let mut resources: Vec<PhotoshopResource> = vec![];
+
+    let mut position = 0;
+    while position < data.len() {
+        // Anything other than a resource block (such as trailing padding) ends the resources
+        let signatures = [b"8BIM", b"PHUT", b"AgHg", b"DCSR", b"MeSa"];
+        let signature = data.get(position..position + 4);
+        if !signatures.iter().any(|s| signature == Some(&s[..])) {
+            break;
+        }
+
+        let header = data.get(position..position + 7).ok_or_else(|| {
+            PhotoshopError(format!(
+                "Expected a resource header at offset {} but the data is only {} bytes",
+                position,
+                data.len()
+            ))
+        })?;
+
+        let id = u16::from_be_bytes([header[4], header[5]]);
+        let name_length = header[6] as usize;
+        let name = data
+            .get(position + 7..position + 7 + name_length)
+            .map(|n| String::from_utf8_lossy(n).to_string())
+            .unwrap_or_default();
+
+        // The length byte and the name together are padded to an even length
+        let name_size = name_length + 1 + (name_length + 1) % 2;
+        position += 6 + name_size;
+
+        let size = read_u32(data, position).ok_or_else(|| {
+            PhotoshopError(format!("[Resource {:#06x}] Missing the data size", id))
+        })? as usize;
+        position += 4;
+
+        let resource_data = data.get(position..position + size).ok_or_else(|| {
+            PhotoshopError(format!(
+                "[Resource {:#06x}] Expected {} bytes but only {} are left",
+                id,
+                size,
+                data.len() - position
+            ))
+        })?;
+        position += size + size % 2;
+
+        resources.push(PhotoshopResource {
+            id,
+            kind: get_resource_kind(id).to_string(),
+            name,
+            data: resource_data.to_vec(),
+            value: decode_resource(id, resource_data).unwrap_or(PhotoshopResourceValue::Unknown),
+        });
+    }
+
+    Ok(Photoshop { resources })
+}
+
+impl Photoshop {
+    pub fn get(&self, id: u16) -> Option<&PhotoshopResource> {
+        self.resources.iter().find(|r| r.id == id)
+    }
+
+    pub fn resolution_info(&self) -> Option<PhotoshopResolutionInfo> {
+        self.resources.iter().find_map(|r| match &r.value {
+            PhotoshopResourceValue::ResolutionInfo(info) => Some(info.clone()),
+            _ => None,
+        })
+    }
+
+    pub fn iptc(&self) -> Option<Iptc> {
+        self.resources.iter().find_map(|r| match &r.value {
+            PhotoshopResourceValue::Iptc(iptc) => Some(iptc.clone()),
+            _ => None,
+        })
+    }
+
+    /// The thumbnail, preferring the newer 0x040C resource over the Photoshop 4.0 one (which has
+    /// its red and blue channels swapped)
+    pub fn thumbnail(&self) -> Option<Thumbnail> {
+        [0x040C, 0x0409]
+            .iter()
+            .find_map(|id| match &self.get(*id)?.value {
+                PhotoshopResourceValue::Thumbnail(thumbnail) => Some(thumbnail.clone()),
+                _ => None,
+            })
+    }
+
+    pub fn caption_digest(&self) -> Option<[u8; 16]> {
+        self.resources.iter().find_map(|r| match &r.value {
+            PhotoshopResourceValue::CaptionDigest(digest) => Some(*digest),
+            _ => None,
+        })
+    }
+}
+
+impl Tiff {
+    /// The image resources from the Photoshop tag, if there are valid ones
+    pub fn photoshop(&self) -> Option<Photoshop> {
+        let data = get_tag_value!(self.tags, TiffTag::Photoshop)?;
+
+        read_photoshop_resources(data).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{read_photoshop_resources, PhotoshopResourceValue};
+    use crate::jpeg::read_jpeg;
+
+    #[test]
+    fn test_read_photoshop_resources_from_jpeg() {
+        let data = fs::read("test_images/no_exif_only_xmp.jpeg").unwrap();
+        let photoshop = read_jpeg(&data).unwrap().photoshop.unwrap();
+
+        let ids: Vec<u16> = photoshop.resources.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![0x03ED, 0x0408, 0x0404, 0x0425]);
+        assert_eq!(photoshop.resources[1].kind, "Grid and guides information");
+        assert!(matches!(
+            photoshop.resources[1].value,
+            PhotoshopResourceValue::Unknown
+        ));
+
+        let resolution = photoshop.resolution_info().unwrap();
+        assert_eq!(resolution.horizontal_resolution, 300.0);
+        assert_eq!(resolution.horizontal_resolution_unit, "pixels per inch");
+        assert_eq!(resolution.width_unit, "inches");
+        assert_eq!(resolution.vertical_resolution, 300.0);
+
+        let iptc = photoshop.iptc().unwrap();
+        assert_eq!(iptc.caption().as_deref(), Some("Der Goalie bin ig"));
+
+        assert!(photoshop.caption_digest().is_some());
+        assert!(photoshop.thumbnail().is_none());
+    }
+
+    #[test]
+    fn test_thumbnail_and_names() {
+        let mut thumbnail = vec![0, 0, 0, 1, 0, 0, 0, 160, 0, 0, 0, 120];
+        thumbnail.extend([0; 16]);
+        thumbnail.extend(b"\xFF\xD8\xFF\xD9");
+
+        let mut data = b"8BIM\x04\x0C\x03abc".to_vec();
+        data.extend((thumbnail.len() as u32).to_be_bytes());
+        data.extend(&thumbnail);
+        data.extend(b"8BIM\x04\x0A\x00\x00\x00\x00\x00\x01\x01\x00");
+
+        let photoshop = read_photoshop_resources(&data).unwrap();
+        assert_eq!(photoshop.resources[0].name, "abc");
+        assert!(matches!(
+            photoshop.resources[1].value,
+            PhotoshopResourceValue::CopyrightFlag(true)
+        ));
+
+        let thumbnail = photoshop.thumbnail().unwrap();
+        assert_eq!((thumbnail.width, thumbnail.height), (Some(160), Some(120)));
+        assert_eq!(thumbnail.data, b"\xFF\xD8\xFF\xD9");
+
+        assert!(read_photoshop_resources(b"8BIM\x04\x04\x00\x00\x00\x00\x00\x10").is_err());
+
+        data.extend(b"\0\0\0\08BIM\x04\x04");
+        assert_eq!(read_photoshop_resources(&data).unwrap().resources.len(), 2);
+    }
+}
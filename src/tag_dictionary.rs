@@ -0,0 +1,530 @@
+// See <https://www.cipa.jp/std/documents/download_e.html?DC-008-Translation-2023-E>, the TIFF 6.0
+// specification and the DNG 1.6 specification
+
+use crate::{tag_registry::ValueType, tiff::IfdKind};
+
+/// Static information about a tag decoded by [`TiffTag`](crate::tiff::TiffTag)
+#[derive(Debug, PartialEq, Clone)]
+pub struct TagInfo {
+    pub id: u16,
+    pub ifd: IfdKind,
+    pub name: &'static str, // The same as the TiffTag variant
+    pub value_types: &'static [ValueType],
+    pub count: Option<u32>, // None if the count varies, e.g. for strings or per sample values
+    pub description: &'static str,
+}
+
+impl TagInfo {
+    /// The Exiv2 group of the tag's IFD
+    pub fn group(&self) -> &'static str {
+        match self.ifd {
+            IfdKind::Primary => "Image",
+            IfdKind::Exif => "Photo",
+            IfdKind::Gps => "GPSInfo",
+            IfdKind::MakerNote(_) => "MakerNote",
+        }
+    }
+
+    /// The tag's name in Exiv2, which differs from the TiffTag variant for a few tags
+    pub fn exiv2_name(&self) -> &'static str {
+        match self.name {
+            "Threshholding" => "Thresholding",
+            "ModelPixelScale" => "ModelPixelScaleTag",
+            "IptcNaa" => "IPTCNAA",
+            "ModelTiepoint" => "ModelTiePointTag",
+            "ModelTransformation" => "ModelTransformationTag",
+            "Photoshop" => "ImageResources",
+            "ExifIfdPointer" => "ExifTag",
+            "GeoKeyDirectory" => "GeoKeyDirectoryTag",
+            "GeoDoubleParams" => "GeoDoubleParamsTag",
+            "GeoAsciiParams" => "GeoAsciiParamsTag",
+            "GpsIfdPointer" => "GPSTag",
+            "PhotographicSensitivity" => "ISOSpeedRatings",
+            "Oecf" => "OECF",
+            "SubsecTime" => "SubSecTime",
+            "SubsecTimeOriginal" => "SubSecTimeOriginal",
+            "SubsecTimeDigitized" => "SubSecTimeDigitized",
+            "InteropIfdPointer" => "InteroperabilityTag",
+            "GPSDop" => "GPSDOP",
+            name => name,
+        }
+    }
+
+    /// The Exiv2 key, e.g. "Exif.Photo.FNumber" or "Exif.Photo.ISOSpeedRatings"
+    pub fn key(&self) -> String {
+        format!("Exif.{}.{}", self.group(), self.exiv2_name())
+    }
+}
+
+const fn tag(
+    id: u16,
+    ifd: IfdKind,
+    name: &'static str,
+    value_types: &'static [ValueType],
+    count: Option<u32>,
+    description: &'static str,
+) -> TagInfo {
+    TagInfo {
+        id,
+        ifd,
+        name,
+        value_types,
+        count,
+        description,
+    }
+}
+
+use IfdKind::{Exif, Gps, Primary};
+
+// EXIF 3.0 allows UTF-8 wherever ASCII is allowed
+const ASCII: &[ValueType] = &[ValueType::Ascii, ValueType::Utf8];
+const ASCII_OR_BYTE: &[ValueType] = &[ValueType::Ascii, ValueType::Utf8, ValueType::Byte];
+const BYTE: &[ValueType] = &[ValueType::Byte];
+const SHORT: &[ValueType] = &[ValueType::Short];
+const LONG: &[ValueType] = &[ValueType::Long];
+const SHORT_OR_LONG: &[ValueType] = &[ValueType::Short, ValueType::Long];
+const SHORT_LONG_OR_RATIONAL: &[ValueType] =
+    &[ValueType::Short, ValueType::Long, ValueType::Rational];
+const SHORT_OR_RATIONAL: &[ValueType] = &[ValueType::Short, ValueType::Rational];
+const RATIONAL: &[ValueType] = &[ValueType::Rational];
+const SRATIONAL: &[ValueType] = &[ValueType::SRational];
+const UNDEFINED: &[ValueType] = &[ValueType::Undefined];
+const FLOAT: &[ValueType] = &[ValueType::Float];
+const DOUBLE: &[ValueType] = &[ValueType::Double];
+
+#[rustfmt::skip]
+static TAGS: &[TagInfo] = &[
+    tag(254, Primary, "NewSubfileType", LONG, Some(1), "The kind of data in the subfile, e.g. a reduced resolution image"),
+    tag(255, Primary, "SubfileType", SHORT, Some(1), "The kind of data in the subfile (deprecated in favor of NewSubfileType)"),
+    tag(256, Primary, "ImageWidth", SHORT_OR_LONG, Some(1), "The number of columns in the image"),
+    tag(257, Primary, "ImageLength", SHORT_OR_LONG, Some(1), "The number of rows in the image"),
+    tag(258, Primary, "BitsPerSample", SHORT, None, "The number of bits per component"),
+    tag(259, Primary, "Compression", SHORT, Some(1), "The compression scheme of the image data"),
+    tag(262, Primary, "PhotometricInterpretation", SHORT, Some(1), "The color space of the image data"),
+    tag(263, Primary, "Threshholding", SHORT, Some(1), "The technique used to convert gray to black and white pixels"),
+    tag(264, Primary, "CellWidth", SHORT, Some(1), "The width of the dithering or halftoning matrix"),
+    tag(265, Primary, "CellLength", SHORT, Some(1), "The length of the dithering or halftoning matrix"),
+    tag(266, Primary, "FillOrder", SHORT, Some(1), "The logical order of bits within a byte"),
+    tag(269, Primary, "DocumentName", ASCII, None, "The name of the document the image was scanned from"),
+    tag(270, Primary, "ImageDescription", ASCII, None, "A description of the image"),
+    tag(271, Primary, "Make", ASCII, None, "The manufacturer of the camera or scanner"),
+    tag(272, Primary, "Model", ASCII, None, "The model name of the camera or scanner"),
+    tag(273, Primary, "StripOffsets", SHORT_OR_LONG, None, "The byte offset of each strip"),
+    tag(274, Primary, "Orientation", SHORT, Some(1), "The orientation of the image relative to its rows and columns"),
+    tag(277, Primary, "SamplesPerPixel", SHORT, Some(1), "The number of components per pixel"),
+    tag(278, Primary, "RowsPerStrip", SHORT_OR_LONG, Some(1), "The number of rows in each strip"),
+    tag(279, Primary, "StripByteCounts", SHORT_OR_LONG, None, "The number of bytes in each strip"),
+    tag(280, Primary, "MinSampleValue", SHORT, None, "The minimum component value used"),
+    tag(281, Primary, "MaxSampleValue", SHORT, None, "The maximum component value used"),
+    tag(282, Primary, "XResolution", RATIONAL, Some(1), "The number of pixels per ResolutionUnit in the image's width"),
+    tag(283, Primary, "YResolution", RATIONAL, Some(1), "The number of pixels per ResolutionUnit in the image's length"),
+    tag(284, Primary, "PlanarConfiguration", SHORT, Some(1), "Whether the components of each pixel are stored interleaved or in separate planes"),
+    tag(285, Primary, "PageName", ASCII, None, "The name of the page the image was scanned from"),
+    tag(286, Primary, "XPosition", RATIONAL, Some(1), "The X offset of the image's left side, in ResolutionUnits"),
+    tag(287, Primary, "YPosition", RATIONAL, Some(1), "The Y offset of the image's top side, in ResolutionUnits"),
+    tag(296, Primary, "ResolutionUnit", SHORT, Some(1), "The unit of XResolution and YResolution"),
+    tag(297, Primary, "PageNumber", SHORT, Some(2), "The page number and total number of pages"),
+    tag(301, Primary, "TransferFunction", SHORT, None, "The transfer function of the image, as a lookup table"),
+    tag(305, Primary, "Software", ASCII, None, "The name and version of the software that created the image"),
+    tag(306, Primary, "DateTime", ASCII, Some(20), "The date and time the file was last changed"),
+    tag(315, Primary, "Artist", ASCII, None, "The person who created the image"),
+    tag(316, Primary, "HostComputer", ASCII, None, "The computer and operating system used to create the image"),
+    tag(317, Primary, "Predictor", SHORT, Some(1), "The predictor applied before compression"),
+    tag(318, Primary, "WhitePoint", RATIONAL, Some(2), "The chromaticity of the white point"),
+    tag(319, Primary, "PrimaryChromaticities", RATIONAL, Some(6), "The chromaticities of the primaries"),
+    tag(320, Primary, "ColorMap", SHORT, None, "The red, green and blue values of each palette color"),
+    tag(322, Primary, "TileWidth", SHORT_OR_LONG, Some(1), "The number of columns in each tile"),
+    tag(323, Primary, "TileLength", SHORT_OR_LONG, Some(1), "The number of rows in each tile"),
+    tag(324, Primary, "TileOffsets", LONG, None, "The byte offset of each tile"),
+    tag(325, Primary, "TileByteCounts", SHORT_OR_LONG, None, "The number of bytes in each tile"),
+    tag(330, Primary, "SubIFDs", LONG, None, "The offsets of child IFDs"),
+    tag(338, Primary, "ExtraSamples", SHORT, None, "The meaning of any components beyond the color components"),
+    tag(339, Primary, "SampleFormat", SHORT, None, "How to interpret each component, e.g. as an unsigned integer"),
+    tag(513, Primary, "JPEGInterchangeFormat", LONG, Some(1), "The offset of the JPEG compressed thumbnail"),
+    tag(514, Primary, "JPEGInterchangeFormatLength", LONG, Some(1), "The number of bytes of the JPEG compressed thumbnail"),
+    tag(529, Primary, "YCbCrCoefficients", RATIONAL, Some(3), "The coefficients to convert RGB to YCbCr"),
+    tag(530, Primary, "YCbCrSubSampling", SHORT, Some(2), "The subsampling factors of the chroma components"),
+    tag(531, Primary, "YCbCrPositioning", SHORT, Some(1), "The position of the chroma components relative to the luma components"),
+    tag(532, Primary, "ReferenceBlackWhite", RATIONAL, Some(6), "The reference black and white point of each component"),
+    tag(33432, Primary, "Copyright", ASCII, None, "The photographer's and editor's copyright notices"),
+    tag(33434, Exif, "ExposureTime", RATIONAL, Some(1), "The exposure time in seconds"),
+    tag(33437, Exif, "FNumber", RATIONAL, Some(1), "The F number"),
+    tag(33550, Primary, "ModelPixelScale", DOUBLE, Some(3), "The size of a pixel in model space (GeoTIFF)"),
+    tag(33723, Primary, "IptcNaa", &[ValueType::Undefined, ValueType::Byte, ValueType::Long], None, "The IPTC-IIM metadata"),
+    tag(33922, Primary, "ModelTiepoint", DOUBLE, None, "Raster points and their location in model space (GeoTIFF)"),
+    tag(34264, Primary, "ModelTransformation", DOUBLE, Some(16), "The affine transformation from raster to model space (GeoTIFF)"),
+    tag(34377, Primary, "Photoshop", BYTE, None, "The Photoshop image resource blocks"),
+    tag(34665, Primary, "ExifIfdPointer", LONG, Some(1), "The offset of the EXIF IFD"),
+    tag(34675, Primary, "InterColorProfile", UNDEFINED, None, "The embedded ICC profile"),
+    tag(34735, Primary, "GeoKeyDirectory", SHORT, None, "The GeoTIFF keys"),
+    tag(34736, Primary, "GeoDoubleParams", DOUBLE, None, "The values of the DOUBLE GeoTIFF keys"),
+    tag(34737, Primary, "GeoAsciiParams", ASCII, None, "The values of the ASCII GeoTIFF keys"),
+    tag(34850, Exif, "ExposureProgram", SHORT, Some(1), "The program the camera used to set the exposure"),
+    tag(34852, Exif, "SpectralSensitivity", ASCII, None, "The spectral sensitivity of each channel"),
+    tag(34853, Primary, "GpsIfdPointer", LONG, Some(1), "The offset of the GPS IFD"),
+    tag(34855, Exif, "PhotographicSensitivity", SHORT, None, "The sensitivity of the camera, usually the ISO speed (ISOSpeedRatings before EXIF 2.3)"),
+    tag(34856, Exif, "Oecf", UNDEFINED, None, "The opto-electric conversion function"),
+    tag(34864, Exif, "SensitivityType", SHORT, Some(1), "Which ISO 12232 parameter PhotographicSensitivity is"),
+    tag(34865, Exif, "StandardOutputSensitivity", LONG, Some(1), "The standard output sensitivity"),
+    tag(34866, Exif, "RecommendedExposureIndex", LONG, Some(1), "The recommended exposure index"),
+    tag(34867, Exif, "ISOSpeed", LONG, Some(1), "The ISO speed"),
+    tag(34868, Exif, "ISOSpeedLatitudeyyy", LONG, Some(1), "The ISO speed latitude yyy"),
+    tag(34869, Exif, "ISOSpeedLatitudezzz", LONG, Some(1), "The ISO speed latitude zzz"),
+    tag(36864, Exif, "ExifVersion", UNDEFINED, Some(4), "The version of the EXIF standard"),
+    tag(36867, Exif, "DateTimeOriginal", ASCII, Some(20), "The date and time the image was captured"),
+    tag(36868, Exif, "DateTimeDigitized", ASCII, Some(20), "The date and time the image was stored as digital data"),
+    tag(36880, Exif, "OffsetTime", ASCII, Some(7), "The UTC offset of DateTime"),
+    tag(36881, Exif, "OffsetTimeOriginal", ASCII, Some(7), "The UTC offset of DateTimeOriginal"),
+    tag(36882, Exif, "OffsetTimeDigitized", ASCII, Some(7), "The UTC offset of DateTimeDigitized"),
+    tag(37121, Exif, "ComponentsConfiguration", UNDEFINED, Some(4), "The order of the components in compressed data"),
+    tag(37122, Exif, "CompressedBitsPerPixel", RATIONAL, Some(1), "The compression ratio in bits per pixel"),
+    tag(37377, Exif, "ShutterSpeedValue", SRATIONAL, Some(1), "The shutter speed as an APEX value"),
+    tag(37378, Exif, "ApertureValue", RATIONAL, Some(1), "The aperture as an APEX value"),
+    tag(37379, Exif, "BrightnessValue", SRATIONAL, Some(1), "The scene brightness as an APEX value"),
+    tag(37380, Exif, "ExposureBiasValue", SRATIONAL, Some(1), "The exposure compensation in APEX units (EV)"),
+    tag(37381, Exif, "MaxApertureValue", RATIONAL, Some(1), "The lens' largest aperture as an APEX value"),
+    tag(37382, Exif, "SubjectDistance", RATIONAL, Some(1), "The distance to the subject in meters"),
+    tag(37383, Exif, "MeteringMode", SHORT, Some(1), "The metering mode"),
+    tag(37384, Exif, "LightSource", SHORT, Some(1), "The kind of light source, e.g. the white balance setting"),
+    tag(37385, Exif, "Flash", SHORT, Some(1), "Whether the flash fired, along with its mode and return light detection"),
+    tag(37386, Exif, "FocalLength", RATIONAL, Some(1), "The actual focal length of the lens in millimeters"),
+    tag(37396, Exif, "SubjectArea", SHORT, None, "The location and area of the main subject"),
+    tag(37500, Exif, "MakerNote", UNDEFINED, None, "Manufacturer specific data"),
+    tag(37510, Exif, "UserComment", UNDEFINED, None, "A comment, prefixed by its character code"),
+    tag(37520, Exif, "SubsecTime", ASCII, None, "Fractions of a second for DateTime"),
+    tag(37521, Exif, "SubsecTimeOriginal", ASCII, None, "Fractions of a second for DateTimeOriginal"),
+    tag(37522, Exif, "SubsecTimeDigitized", ASCII, None, "Fractions of a second for DateTimeDigitized"),
+    tag(37888, Exif, "Temperature", SRATIONAL, Some(1), "The ambient temperature in degrees Celsius"),
+    tag(37889, Exif, "Humidity", RATIONAL, Some(1), "The ambient relative humidity in percent"),
+    tag(37890, Exif, "Pressure", RATIONAL, Some(1), "The ambient air or water pressure in hPa"),
+    tag(37891, Exif, "WaterDepth", SRATIONAL, Some(1), "The depth under water in meters, negative above the surface"),
+    tag(37892, Exif, "Acceleration", RATIONAL, Some(1), "The acceleration of the camera in mGal"),
+    tag(37893, Exif, "CameraElevationAngle", SRATIONAL, Some(1), "The elevation angle of the camera in degrees"),
+    tag(40960, Exif, "FlashpixVersion", UNDEFINED, Some(4), "The supported Flashpix version"),
+    tag(40961, Exif, "ColorSpace", SHORT, Some(1), "The color space, usually sRGB or uncalibrated"),
+    tag(40962, Exif, "PixelXDimension", SHORT_OR_LONG, Some(1), "The width of the compressed image"),
+    tag(40963, Exif, "PixelYDimension", SHORT_OR_LONG, Some(1), "The height of the compressed image"),
+    tag(40964, Exif, "RelatedSoundFile", ASCII, Some(13), "The name of an audio file related to the image"),
+    tag(40965, Exif, "InteropIfdPointer", LONG, Some(1), "The offset of the interoperability IFD"),
+    tag(41483, Exif, "FlashEnergy", RATIONAL, Some(1), "The strobe energy in BCPS"),
+    tag(41484, Exif, "SpatialFrequencyResponse", UNDEFINED, None, "The spatial frequency table and SFR values"),
+    tag(41486, Exif, "FocalPlaneXResolution", RATIONAL, Some(1), "The number of pixels per FocalPlaneResolutionUnit in the image's width"),
+    tag(41487, Exif, "FocalPlaneYResolution", RATIONAL, Some(1), "The number of pixels per FocalPlaneResolutionUnit in the image's height"),
+    tag(41488, Exif, "FocalPlaneResolutionUnit", SHORT, Some(1), "The unit of FocalPlaneXResolution and FocalPlaneYResolution"),
+    tag(41492, Exif, "SubjectLocation", SHORT, Some(2), "The location of the main subject"),
+    tag(41493, Exif, "ExposureIndex", RATIONAL, Some(1), "The exposure index"),
+    tag(41495, Exif, "SensingMethod", SHORT, Some(1), "The kind of image sensor"),
+    tag(41728, Exif, "FileSource", UNDEFINED, Some(1), "The kind of device which created the image"),
+    tag(41729, Exif, "SceneType", UNDEFINED, Some(1), "Whether the image was directly photographed"),
+    tag(41730, Exif, "CFAPattern", UNDEFINED, None, "The color filter array pattern of the sensor"),
+    tag(41985, Exif, "CustomRendered", SHORT, Some(1), "Whether the image data was specially processed"),
+    tag(41986, Exif, "ExposureMode", SHORT, Some(1), "The exposure mode"),
+    tag(41987, Exif, "WhiteBalance", SHORT, Some(1), "Whether the white balance was set automatically or manually"),
+    tag(41988, Exif, "DigitalZoomRatio", RATIONAL, Some(1), "The digital zoom ratio, 0 if it wasn't used"),
+    tag(41989, Exif, "FocalLengthIn35mmFilm", SHORT, Some(1), "The equivalent focal length for a 35mm film camera in millimeters"),
+    tag(41990, Exif, "SceneCaptureType", SHORT, Some(1), "The kind of scene"),
+    tag(41991, Exif, "GainControl", SHORT, Some(1), "The degree of overall gain adjustment"),
+    tag(41992, Exif, "Contrast", SHORT, Some(1), "The contrast processing applied by the camera"),
+    tag(41993, Exif, "Saturation", SHORT, Some(1), "The saturation processing applied by the camera"),
+    tag(41994, Exif, "Sharpness", SHORT, Some(1), "The sharpness processing applied by the camera"),
+    tag(41995, Exif, "DeviceSettingDescription", UNDEFINED, None, "The picture-taking conditions of a particular camera model"),
+    tag(41996, Exif, "SubjectDistanceRange", SHORT, Some(1), "The range of the distance to the subject"),
+    tag(42016, Exif, "ImageUniqueID", ASCII, Some(33), "A unique identifier of the image, as a 128-bit hexadecimal string"),
+    tag(42032, Exif, "CameraOwnerName", ASCII, None, "The name of the camera's owner"),
+    tag(42033, Exif, "BodySerialNumber", ASCII, None, "The serial number of the camera body"),
+    tag(42034, Exif, "LensSpecification", RATIONAL, Some(4), "The minimum and maximum focal length and the F number at each"),
+    tag(42035, Exif, "LensMake", ASCII, None, "The manufacturer of the lens"),
+    tag(42036, Exif, "LensModel", ASCII, None, "The model name of the lens"),
+    tag(42037, Exif, "LensSerialNumber", ASCII, None, "The serial number of the lens"),
+    tag(42038, Exif, "ImageTitle", ASCII, None, "The title of the image"),
+    tag(42039, Exif, "Photographer", ASCII, None, "The name of the photographer"),
+    tag(42040, Exif, "ImageEditor", ASCII, None, "The name of the person who edited the image"),
+    tag(42041, Exif, "CameraFirmware", ASCII, None, "The name and version of the camera's firmware"),
+    tag(42042, Exif, "RAWDevelopingSoftware", ASCII, None, "The name and version of the software used to develop the raw image"),
+    tag(42043, Exif, "ImageEditingSoftware", ASCII, None, "The name and version of the software used to edit the image"),
+    tag(42044, Exif, "MetadataEditingSoftware", ASCII, None, "The name and version of the software used to edit the metadata"),
+    tag(42080, Exif, "CompositeImage", SHORT, Some(1), "Whether the image is a composite of several captures"),
+    tag(42081, Exif, "SourceImageNumberOfCompositeImage", SHORT, Some(2), "The number of source images of a composite image, and how many were used"),
+    tag(42082, Exif, "SourceExposureTimesOfCompositeImage", UNDEFINED, None, "The exposure times of the source images of a composite image"),
+    tag(42240, Exif, "Gamma", RATIONAL, Some(1), "The gamma coefficient"),
+    tag(50706, Primary, "DNGVersion", BYTE, Some(4), "The version of the DNG specification the file conforms to"),
+    tag(50707, Primary, "DNGBackwardVersion", BYTE, Some(4), "The oldest DNG version a reader must support to read the file"),
+    tag(50708, Primary, "UniqueCameraModel", ASCII, None, "A unique, non-localized name of the camera model"),
+    tag(50709, Primary, "LocalizedCameraModel", ASCII_OR_BYTE, None, "A localized name of the camera model"),
+    tag(50710, Primary, "CFAPlaneColor", BYTE, None, "The color of each plane of the color filter array"),
+    tag(50711, Primary, "CFALayout", SHORT, Some(1), "The spatial layout of the color filter array"),
+    tag(50712, Primary, "LinearizationTable", SHORT, None, "A lookup table which maps raw values to linear values"),
+    tag(50713, Primary, "BlackLevelRepeatDim", SHORT, Some(2), "The number of rows and columns of the BlackLevel pattern"),
+    tag(50714, Primary, "BlackLevel", SHORT_LONG_OR_RATIONAL, None, "The zero light encoding level of each sample in the pattern"),
+    tag(50715, Primary, "BlackLevelDeltaH", SRATIONAL, None, "The black level change per column"),
+    tag(50716, Primary, "BlackLevelDeltaV", SRATIONAL, None, "The black level change per row"),
+    tag(50717, Primary, "WhiteLevel", SHORT_OR_LONG, None, "The fully saturated encoding level of each sample"),
+    tag(50718, Primary, "DefaultScale", RATIONAL, Some(2), "The scale factors which give square pixels"),
+    tag(50719, Primary, "DefaultCropOrigin", SHORT_LONG_OR_RATIONAL, Some(2), "The origin of the final image area, relative to the top left of the ActiveArea"),
+    tag(50720, Primary, "DefaultCropSize", SHORT_LONG_OR_RATIONAL, Some(2), "The size of the final image area"),
+    tag(50721, Primary, "ColorMatrix1", SRATIONAL, None, "The matrix from XYZ to the camera's color space under CalibrationIlluminant1"),
+    tag(50722, Primary, "ColorMatrix2", SRATIONAL, None, "The matrix from XYZ to the camera's color space under CalibrationIlluminant2"),
+    tag(50723, Primary, "CameraCalibration1", SRATIONAL, None, "The calibration matrix from the reference camera to this camera under CalibrationIlluminant1"),
+    tag(50724, Primary, "CameraCalibration2", SRATIONAL, None, "The calibration matrix from the reference camera to this camera under CalibrationIlluminant2"),
+    tag(50725, Primary, "ReductionMatrix1", SRATIONAL, None, "The dimensionality reduction matrix under CalibrationIlluminant1"),
+    tag(50726, Primary, "ReductionMatrix2", SRATIONAL, None, "The dimensionality reduction matrix under CalibrationIlluminant2"),
+    tag(50727, Primary, "AnalogBalance", RATIONAL, None, "The gain applied to each color plane before digitization"),
+    tag(50728, Primary, "AsShotNeutral", SHORT_OR_RATIONAL, None, "The selected white balance as coordinates of a neutral color"),
+    tag(50729, Primary, "AsShotWhiteXY", RATIONAL, Some(2), "The selected white balance as x-y chromaticity coordinates"),
+    tag(50730, Primary, "BaselineExposure", SRATIONAL, Some(1), "The exposure compensation needed to match the camera's zero point, in EV"),
+    tag(50731, Primary, "BaselineNoise", RATIONAL, Some(1), "The relative noise level at ISO 100"),
+    tag(50732, Primary, "BaselineSharpness", RATIONAL, Some(1), "The relative amount of sharpening needed"),
+    tag(50733, Primary, "BayerGreenSplit", LONG, Some(1), "How closely the values of the green pixels in blue/green and red/green rows track"),
+    tag(50734, Primary, "LinearResponseLimit", RATIONAL, Some(1), "The fraction of the encoding range above which the response may become non-linear"),
+    tag(50735, Primary, "CameraSerialNumber", ASCII, None, "The serial number of the camera"),
+    tag(50736, Primary, "LensInfo", RATIONAL, Some(4), "The minimum and maximum focal length and the F number at each"),
+    tag(50737, Primary, "ChromaBlurRadius", RATIONAL, Some(1), "The chroma blur radius needed to remove color aliasing, in pixels"),
+    tag(50738, Primary, "AntiAliasStrength", RATIONAL, Some(1), "The relative strength of the camera's anti-alias filter"),
+    tag(50739, Primary, "ShadowScale", RATIONAL, Some(1), "Used by Adobe Camera Raw to control the sensitivity of its shadows slider"),
+    tag(50740, Primary, "DNGPrivateData", BYTE, None, "Private data for the software which created the DNG"),
+    tag(50741, Primary, "MakerNoteSafety", SHORT, Some(1), "Whether the MakerNote is safe to preserve when editing"),
+    tag(50778, Primary, "CalibrationIlluminant1", SHORT, Some(1), "The illuminant of the first set of calibration tags"),
+    tag(50779, Primary, "CalibrationIlluminant2", SHORT, Some(1), "The illuminant of the second set of calibration tags"),
+    tag(50780, Primary, "BestQualityScale", RATIONAL, Some(1), "The scale factor to apply to DefaultScale for the best quality image"),
+    tag(50781, Primary, "RawDataUniqueID", BYTE, Some(16), "A unique identifier of the raw image data"),
+    tag(50827, Primary, "OriginalRawFileName", ASCII_OR_BYTE, None, "The file name of the raw file the DNG was converted from"),
+    tag(50828, Primary, "OriginalRawFileData", UNDEFINED, None, "The contents of the raw file the DNG was converted from"),
+    tag(50829, Primary, "ActiveArea", SHORT_OR_LONG, Some(4), "The top, left, bottom and right of the active area of the sensor"),
+    tag(50830, Primary, "MaskedAreas", SHORT_OR_LONG, None, "The rectangles of fully masked pixels"),
+    tag(50831, Primary, "AsShotICCProfile", UNDEFINED, None, "An ICC profile for rendering the image as shot"),
+    tag(50832, Primary, "AsShotPreProfileMatrix", SRATIONAL, None, "A matrix to apply before AsShotICCProfile"),
+    tag(50833, Primary, "CurrentICCProfile", UNDEFINED, None, "An ICC profile for rendering the image with the current settings"),
+    tag(50834, Primary, "CurrentPreProfileMatrix", SRATIONAL, None, "A matrix to apply before CurrentICCProfile"),
+    tag(50879, Primary, "ColorimetricReference", SHORT, Some(1), "The colorimetric reference of the XYZ values"),
+    tag(50931, Primary, "CameraCalibrationSignature", ASCII_OR_BYTE, None, "The signature of the camera calibration tags"),
+    tag(50932, Primary, "ProfileCalibrationSignature", ASCII_OR_BYTE, None, "The signature of the camera profile"),
+    tag(50933, Primary, "ExtraCameraProfiles", LONG, None, "The offsets of additional camera profiles"),
+    tag(50934, Primary, "AsShotProfileName", ASCII_OR_BYTE, None, "The name of the camera profile selected when shooting"),
+    tag(50935, Primary, "NoiseReductionApplied", RATIONAL, Some(1), "The amount of noise reduction applied to the raw data"),
+    tag(50936, Primary, "ProfileName", ASCII_OR_BYTE, None, "The name of the camera profile"),
+    tag(50937, Primary, "ProfileHueSatMapDims", LONG, Some(3), "The number of hue, saturation and value divisions of the hue/saturation maps"),
+    tag(50938, Primary, "ProfileHueSatMapData1", FLOAT, None, "The hue/saturation map for CalibrationIlluminant1"),
+    tag(50939, Primary, "ProfileHueSatMapData2", FLOAT, None, "The hue/saturation map for CalibrationIlluminant2"),
+    tag(50940, Primary, "ProfileToneCurve", FLOAT, None, "The default tone curve, as input and output pairs"),
+    tag(50941, Primary, "ProfileEmbedPolicy", LONG, Some(1), "How the camera profile may be used"),
+    tag(50942, Primary, "ProfileCopyright", ASCII_OR_BYTE, None, "The copyright of the camera profile"),
+    tag(50964, Primary, "ForwardMatrix1", SRATIONAL, None, "The matrix from white balanced camera colors to XYZ D50 under CalibrationIlluminant1"),
+    tag(50965, Primary, "ForwardMatrix2", SRATIONAL, None, "The matrix from white balanced camera colors to XYZ D50 under CalibrationIlluminant2"),
+    tag(50966, Primary, "PreviewApplicationName", ASCII_OR_BYTE, None, "The name of the application which created the preview"),
+    tag(50967, Primary, "PreviewApplicationVersion", ASCII_OR_BYTE, None, "The version of the application which created the preview"),
+    tag(50968, Primary, "PreviewSettingsName", ASCII_OR_BYTE, None, "The name of the settings used to render the preview"),
+    tag(50969, Primary, "PreviewSettingsDigest", BYTE, Some(16), "A digest of the settings used to render the preview"),
+    tag(50970, Primary, "PreviewColorSpace", LONG, Some(1), "The color space of the preview"),
+    tag(50971, Primary, "PreviewDateTime", ASCII, None, "The date and time the preview was rendered, in ISO 8601 format"),
+    tag(50972, Primary, "RawImageDigest", BYTE, Some(16), "An MD5 digest of the raw image data"),
+    tag(50973, Primary, "OriginalRawFileDigest", BYTE, Some(16), "An MD5 digest of OriginalRawFileData"),
+    tag(50974, Primary, "SubTileBlockSize", SHORT_OR_LONG, Some(2), "The size of the blocks each tile is divided into"),
+    tag(50975, Primary, "RowInterleaveFactor", SHORT_OR_LONG, Some(1), "The number of interleaved fields of the image"),
+    tag(50981, Primary, "ProfileLookTableDims", LONG, Some(3), "The number of hue, saturation and value divisions of the look table"),
+    tag(50982, Primary, "ProfileLookTableData", FLOAT, None, "The look table applied after the hue/saturation maps"),
+    tag(51008, Primary, "OpcodeList1", UNDEFINED, None, "The opcodes to apply to the raw image as read from the file"),
+    tag(51009, Primary, "OpcodeList2", UNDEFINED, None, "The opcodes to apply to the raw image after linearization"),
+    tag(51022, Primary, "OpcodeList3", UNDEFINED, None, "The opcodes to apply to the raw image after demosaicing"),
+    tag(51041, Primary, "NoiseProfile", DOUBLE, None, "The noise model of each color plane"),
+    tag(51089, Primary, "OriginalDefaultFinalSize", SHORT_OR_LONG, Some(2), "The default final size of the original image, before any proxy downscaling"),
+    tag(51090, Primary, "OriginalBestQualityFinalSize", SHORT_OR_LONG, Some(2), "The best quality final size of the original image"),
+    tag(51091, Primary, "OriginalDefaultCropSize", SHORT_LONG_OR_RATIONAL, Some(2), "The default crop size of the original image"),
+    tag(51107, Primary, "ProfileHueSatMapEncoding", LONG, Some(1), "Whether the hue/saturation maps use linear or sRGB gamma encoding"),
+    tag(51108, Primary, "ProfileLookTableEncoding", LONG, Some(1), "Whether the look table uses linear or sRGB gamma encoding"),
+    tag(51109, Primary, "BaselineExposureOffset", SRATIONAL, Some(1), "An additional exposure offset for the camera profile, in EV"),
+    tag(51110, Primary, "DefaultBlackRender", LONG, Some(1), "Whether the black level should be automatically adjusted"),
+    tag(51111, Primary, "NewRawImageDigest", BYTE, Some(16), "An MD5 digest of the raw image data, computed after decompression"),
+    tag(51112, Primary, "RawToPreviewGain", DOUBLE, Some(1), "The gain between the raw image and the preview"),
+    tag(51125, Primary, "DefaultUserCrop", RATIONAL, Some(4), "The default user crop as top, left, bottom and right fractions of the image"),
+    tag(51177, Primary, "DepthFormat", SHORT, Some(1), "The encoding of the depth map"),
+    tag(51178, Primary, "DepthNear", RATIONAL, Some(1), "The distance of the depth map's minimum value"),
+    tag(51179, Primary, "DepthFar", RATIONAL, Some(1), "The distance of the depth map's maximum value"),
+    tag(51180, Primary, "DepthUnits", SHORT, Some(1), "The unit of DepthNear and DepthFar"),
+    tag(51181, Primary, "DepthMeasureType", SHORT, Some(1), "How the depth was measured"),
+    tag(51182, Primary, "EnhanceParams", ASCII, None, "The parameters of the enhancement applied to the image"),
+    tag(52525, Primary, "ProfileGainTableMap", UNDEFINED, None, "A spatially varying gain table map"),
+    tag(52526, Primary, "SemanticName", ASCII, None, "The semantic name of a semantic mask"),
+    tag(52528, Primary, "SemanticInstanceID", ASCII, None, "The instance identifier of a semantic mask"),
+    tag(52529, Primary, "CalibrationIlluminant3", SHORT, Some(1), "The illuminant of the third set of calibration tags"),
+    tag(52530, Primary, "CameraCalibration3", SRATIONAL, None, "The calibration matrix from the reference camera to this camera under CalibrationIlluminant3"),
+    tag(52531, Primary, "ColorMatrix3", SRATIONAL, None, "The matrix from XYZ to the camera's color space under CalibrationIlluminant3"),
+    tag(52532, Primary, "ForwardMatrix3", SRATIONAL, None, "The matrix from white balanced camera colors to XYZ D50 under CalibrationIlluminant3"),
+    tag(52533, Primary, "IlluminantData1", UNDEFINED, None, "The spectral data of CalibrationIlluminant1 if it's Other"),
+    tag(52534, Primary, "IlluminantData2", UNDEFINED, None, "The spectral data of CalibrationIlluminant2 if it's Other"),
+    tag(52535, Primary, "IlluminantData3", UNDEFINED, None, "The spectral data of CalibrationIlluminant3 if it's Other"),
+    tag(52536, Primary, "MaskSubArea", LONG, Some(4), "The area of a semantic mask within the main image"),
+    tag(52537, Primary, "ProfileHueSatMapData3", FLOAT, None, "The hue/saturation map for CalibrationIlluminant3"),
+    tag(52538, Primary, "ReductionMatrix3", SRATIONAL, None, "The dimensionality reduction matrix under CalibrationIlluminant3"),
+    tag(52543, Primary, "RGBTables", UNDEFINED, None, "RGB lookup tables applied after the camera profile"),
+
+    tag(0, Gps, "GPSVersionID", BYTE, Some(4), "The version of the GPS IFD"),
+    tag(1, Gps, "GPSLatitudeRef", ASCII, Some(2), "Whether the latitude is north (N) or south (S)"),
+    tag(2, Gps, "GPSLatitude", RATIONAL, Some(3), "The latitude in degrees, minutes and seconds"),
+    tag(3, Gps, "GPSLongitudeRef", ASCII, Some(2), "Whether the longitude is east (E) or west (W)"),
+    tag(4, Gps, "GPSLongitude", RATIONAL, Some(3), "The longitude in degrees, minutes and seconds"),
+    tag(5, Gps, "GPSAltitudeRef", BYTE, Some(1), "Whether the altitude is above or below sea level"),
+    tag(6, Gps, "GPSAltitude", RATIONAL, Some(1), "The altitude in meters"),
+    tag(7, Gps, "GPSTimeStamp", RATIONAL, Some(3), "The UTC time of the fix as hours, minutes and seconds"),
+    tag(8, Gps, "GPSSatellites", ASCII, None, "The satellites used for the measurement"),
+    tag(9, Gps, "GPSStatus", ASCII, Some(2), "The status of the receiver, A for in progress and V for interrupted"),
+    tag(10, Gps, "GPSMeasureMode", ASCII, Some(2), "Whether the measurement is 2D or 3D"),
+    tag(11, Gps, "GPSDop", RATIONAL, Some(1), "The dilution of precision of the measurement"),
+    tag(12, Gps, "GPSSpeedRef", ASCII, Some(2), "The unit of GPSSpeed"),
+    tag(13, Gps, "GPSSpeed", RATIONAL, Some(1), "The speed of the receiver"),
+    tag(14, Gps, "GPSTrackRef", ASCII, Some(2), "Whether GPSTrack is relative to true (T) or magnetic (M) north"),
+    tag(15, Gps, "GPSTrack", RATIONAL, Some(1), "The direction of movement in degrees"),
+    tag(16, Gps, "GPSImgDirectionRef", ASCII, Some(2), "Whether GPSImgDirection is relative to true (T) or magnetic (M) north"),
+    tag(17, Gps, "GPSImgDirection", RATIONAL, Some(1), "The direction the camera was pointing in degrees"),
+    tag(18, Gps, "GPSMapDatum", ASCII, None, "The geodetic datum of the measurement"),
+    tag(19, Gps, "GPSDestLatitudeRef", ASCII, Some(2), "Whether the destination latitude is north (N) or south (S)"),
+    tag(20, Gps, "GPSDestLatitude", RATIONAL, Some(3), "The destination latitude in degrees, minutes and seconds"),
+    tag(21, Gps, "GPSDestLongitudeRef", ASCII, Some(2), "Whether the destination longitude is east (E) or west (W)"),
+    tag(22, Gps, "GPSDestLongitude", RATIONAL, Some(3), "The destination longitude in degrees, minutes and seconds"),
+    tag(23, Gps, "GPSDestBearingRef", ASCII, Some(2), "Whether GPSDestBearing is relative to true (T) or magnetic (M) north"),
+    tag(24, Gps, "GPSDestBearing", RATIONAL, Some(1), "The bearing to the destination in degrees"),
+    tag(25, Gps, "GPSDestDistanceRef", ASCII, Some(2), "The unit of GPSDestDistance"),
+    tag(26, Gps, "GPSDestDistance", RATIONAL, Some(1), "The distance to the destination"),
+    tag(27, Gps, "GPSProcessingMethod", UNDEFINED, None, "The name of the method used for finding the location, prefixed by its character code"),
+    tag(28, Gps, "GPSAreaInformation", UNDEFINED, None, "The name of the GPS area, prefixed by its character code"),
+    tag(29, Gps, "GPSDateStamp", ASCII, Some(11), "The UTC date of the fix as YYYY:MM:DD"),
+    tag(30, Gps, "GPSDifferential", SHORT, Some(1), "Whether differential correction was applied"),
+    tag(31, Gps, "GPSHPositioningError", RATIONAL, Some(1), "The horizontal positioning error in meters"),
+];
+
+/// Every tag decoded by [`TiffTag`](crate::tiff::TiffTag)
+pub fn tags() -> &'static [TagInfo] {
+    TAGS
+}
+
+pub fn tag_info(ifd: IfdKind, id: u16) -> Option<&'static TagInfo> {
+    TAGS.iter().find(|t| t.ifd == ifd && t.id == id)
+}
+
+/// Looks up a tag by its Exiv2 key (e.g. "Exif.Photo.FNumber"), an ExifTool style group and
+/// TiffTag name (e.g. "EXIF:FNumber" or "ExifIFD:FNumber") or just its TiffTag name. Names are
+/// case insensitive.
+pub fn tag_info_by_name(name: &str) -> Option<&'static TagInfo> {
+    if let ["Exif", group, name] = name.split('.').collect::<Vec<&str>>()[..] {
+        return TAGS
+            .iter()
+            .find(|t| t.group() == group && t.exiv2_name().eq_ignore_ascii_case(name));
+    }
+
+    let (ifd, name) = match name.split_once(':') {
+        Some((group, name)) => match group.to_ascii_lowercase().as_str() {
+            "exif" => (None, name),
+            "ifd0" => (Some(Primary), name),
+            "exififd" => (Some(Exif), name),
+            "gps" => (Some(Gps), name),
+            _ => return None,
+        },
+        None => (None, name),
+    };
+
+    TAGS.iter()
+        .find(|t| ifd.is_none_or(|ifd| t.ifd == ifd) && t.name.eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tag_info, tag_info_by_name, tags};
+    use crate::{
+        tag_registry::ValueType,
+        tiff::{tiff_tag_from_entry, IFDEntry, IFDEntryValue, IfdKind, TiffTag},
+    };
+
+    #[test]
+    fn test_lookup() {
+        let info = tag_info(IfdKind::Exif, 33437).unwrap();
+        assert_eq!(info.name, "FNumber");
+        assert_eq!(info.key(), "Exif.Photo.FNumber");
+        assert_eq!(info.count, Some(1));
+
+        assert_eq!(tag_info_by_name("Exif.Photo.FNumber"), Some(info));
+        assert_eq!(tag_info_by_name("EXIF:FNumber"), Some(info));
+        assert_eq!(tag_info_by_name("ExifIFD:fnumber"), Some(info));
+        assert_eq!(tag_info_by_name("FNumber"), Some(info));
+        assert_eq!(tag_info_by_name("Exif.Image.FNumber"), None);
+        assert_eq!(tag_info_by_name("XMP:FNumber"), None);
+
+        // Exiv2 keys use Exiv2's names, which differ from the TiffTag variants for a few tags
+        let info = tag_info(IfdKind::Exif, 34855).unwrap();
+        assert_eq!(info.key(), "Exif.Photo.ISOSpeedRatings");
+        assert_eq!(tag_info_by_name("Exif.Photo.ISOSpeedRatings"), Some(info));
+        assert_eq!(tag_info_by_name("Exif.Photo.PhotographicSensitivity"), None);
+        assert_eq!(
+            tag_info_by_name("ExifIFD:PhotographicSensitivity"),
+            Some(info)
+        );
+        assert_eq!(
+            tag_info(IfdKind::Primary, 34665).unwrap().key(),
+            "Exif.Image.ExifTag"
+        );
+        assert_eq!(
+            tag_info(IfdKind::Primary, 33723).unwrap().key(),
+            "Exif.Image.IPTCNAA"
+        );
+        assert_eq!(
+            tag_info(IfdKind::Gps, 11).unwrap().key(),
+            "Exif.GPSInfo.GPSDOP"
+        );
+
+        // The GPS IFD reuses the IDs of other tags
+        assert_eq!(
+            tag_info(IfdKind::Gps, 2).unwrap().key(),
+            "Exif.GPSInfo.GPSLatitude"
+        );
+        assert_eq!(tag_info(IfdKind::Primary, 2), None);
+        assert_eq!(tag_info_by_name("GPS:GPSLatitude").unwrap().id, 2);
+    }
+
+    /// Checks every tag in the dictionary decodes to the TiffTag variant of the same name
+    #[test]
+    fn test_dictionary_matches_decoded_tags() {
+        for info in tags() {
+            let value = match info.value_types[0] {
+                ValueType::Byte => IFDEntryValue::BYTE(1),
+                ValueType::Ascii => IFDEntryValue::ASCII(b'1'),
+                ValueType::Short => IFDEntryValue::SHORT(1),
+                ValueType::Long => IFDEntryValue::LONG(1),
+                ValueType::Rational => IFDEntryValue::RATIONAL(1, 1),
+                ValueType::Undefined => IFDEntryValue::UNDEFINED(0),
+                ValueType::SRational => IFDEntryValue::SRATIONAL(1, 1),
+                ValueType::Float => IFDEntryValue::FLOAT(1.0),
+                ValueType::Double => IFDEntryValue::DOUBLE(1.0),
+                other => panic!("No test value for {:?}", other),
+            };
+
+            // Tags with a variable count may still need e.g. a multiple of 4 values (or, for
+            // strings with a character code, at least the 8 byte prefix)
+            let counts = match info.count {
+                Some(count) => vec![count],
+                None => vec![9, 12, 16],
+            };
+            let decoded = counts
+                .iter()
+                .map(|count| {
+                    let entry = IFDEntry {
+                        tag: info.id,
+                        values: vec![value; *count as usize],
+                    };
+                    tiff_tag_from_entry(&entry, info.ifd)
+                })
+                .find_map(|result| result.ok())
+                .unwrap_or_else(|| panic!("{} couldn't be decoded", info.name));
+
+            if let TiffTag::Unknown(_) = decoded {
+                panic!("{} isn't decoded", info.name);
+            }
+
+            assert!(
+                format!("{:?}", decoded).starts_with(&format!("{}(", info.name)),
+                "{} was decoded as {:?}",
+                info.name,
+                decoded
+            );
+        }
+
+        assert_eq!(tags().len(), 294);
+    }
+}
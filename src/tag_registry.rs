@@ -0,0 +1,280 @@
+use std::collections::HashMap;
+
+use crate::tiff::{get_numbers_from_entry, IFDEntry, IFDEntryValue, IfdKind, Tiff, TiffError};
+
+/// The TIFF value types, for declaring which type a registered tag is expected to have
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum ValueType {
+    Byte,
+    Ascii,
+    Short,
+    Long,
+    Rational,
+    SByte,
+    Undefined,
+    SShort,
+    SLong,
+    SRational,
+    Float,
+    Double,
+    Utf8,
+}
+
+impl ValueType {
+    fn matches(&self, value: &IFDEntryValue) -> bool {
+        matches!(
+            (self, value),
+            (ValueType::Byte, IFDEntryValue::BYTE(_))
+                | (ValueType::Ascii, IFDEntryValue::ASCII(_))
+                | (ValueType::Short, IFDEntryValue::SHORT(_))
+                | (ValueType::Long, IFDEntryValue::LONG(_))
+                | (ValueType::Rational, IFDEntryValue::RATIONAL(_, _))
+                | (ValueType::SByte, IFDEntryValue::SBYTE(_))
+                | (ValueType::Undefined, IFDEntryValue::UNDEFINED(_))
+                | (ValueType::SShort, IFDEntryValue::SSHORT(_))
+                | (ValueType::SLong, IFDEntryValue::SLONG(_))
+                | (ValueType::SRational, IFDEntryValue::SRATIONAL(_, _))
+                | (ValueType::Float, IFDEntryValue::FLOAT(_))
+                | (ValueType::Double, IFDEntryValue::DOUBLE(_))
+                | (ValueType::Utf8, IFDEntryValue::UTF8(_))
+        )
+    }
+}
+
+/// The decoded value of a registered tag
+#[derive(Debug, PartialEq, Clone)]
+pub enum CustomTagValue {
+    Text(String),
+    Integers(Vec<i64>),
+    Numbers(Vec<f64>),
+    Bytes(Vec<u8>),
+}
+
+pub type DecodeFn = fn(IFDEntry) -> Result<CustomTagValue, TiffError>;
+
+/// A tag which isn't decoded by [`TiffTag`](crate::tiff::TiffTag), e.g. a private tag (65000+)
+#[derive(Debug, Clone)]
+pub struct TagDefinition {
+    pub ifd: IfdKind,
+    pub id: u16,
+    pub name: String,
+    pub value_type: ValueType,
+    pub decode: DecodeFn,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct CustomTag {
+    pub ifd: IfdKind,
+    pub id: u16,
+    pub name: String,
+    pub value: CustomTagValue,
+}
+
+/// Tag definitions keyed by IFD kind and tag ID, used to decode tags which would otherwise end up
+/// as `TiffTag::Unknown`. Tags that are already decoded can't be overridden.
+#[derive(Debug, Clone, Default)]
+pub struct TagRegistry {
+    definitions: HashMap<(IfdKind, u16), TagDefinition>,
+}
+
+impl TagRegistry {
+    pub fn new() -> TagRegistry {
+        TagRegistry::default()
+    }
+
+    /// Registers a tag, replacing any previous definition for the same IFD kind and ID
+    pub fn register(&mut self, definition: TagDefinition) {
+        self.definitions
+            .insert((definition.ifd, definition.id), definition);
+    }
+
+    pub fn get(&self, ifd: IfdKind, id: u16) -> Option<&TagDefinition> {
+        self.definitions.get(&(ifd, id))
+    }
+
+    /// Decodes an entry with its registered definition, after checking all values have the
+    /// expected type. Returns None if the tag isn't registered.
+    pub fn decode(&self, ifd: IfdKind, entry: IFDEntry) -> Option<Result<CustomTag, TiffError>> {
+        let definition = self.get(ifd, entry.tag)?;
+
+        if let Some(value) = entry
+            .values
+            .iter()
+            .find(|v| !definition.value_type.matches(v))
+        {
+            return Some(Err(TiffError(format!(
+                "[Tag {} ({})] Expected all values to be {:?} (got {:?})",
+                entry.tag, definition.name, definition.value_type, value
+            ))));
+        }
+
+        Some((definition.decode)(entry).map(|value| CustomTag {
+            ifd,
+            id: definition.id,
+            name: definition.name.clone(),
+            value,
+        }))
+    }
+}
+
+/// Decodes ASCII or UTF-8 values, up to the first NUL
+pub fn decode_text(entry: IFDEntry) -> Result<CustomTagValue, TiffError> {
+    let text: String = entry.try_into()?;
+
+    Ok(CustomTagValue::Text(text))
+}
+
+/// Decodes BYTE, SHORT, LONG, SBYTE, SSHORT or SLONG values
+pub fn decode_integers(entry: IFDEntry) -> Result<CustomTagValue, TiffError> {
+    let mut result: Vec<i64> = vec![];
+
+    for v in entry.values {
+        result.push(match v {
+            IFDEntryValue::BYTE(v) => v as i64,
+            IFDEntryValue::SHORT(v) => v as i64,
+            IFDEntryValue::LONG(v) => v as i64,
+            IFDEntryValue::SBYTE(v) => v as i64,
+            IFDEntryValue::SSHORT(v) => v as i64,
+            IFDEntryValue::SLONG(v) => v as i64,
+            _ => {
+                return Err(TiffError(format!(
+                    "[Tag {}] Expected all values to be integers (got {:?})",
+                    entry.tag, v
+                )))
+            }
+        });
+    }
+
+    Ok(CustomTagValue::Integers(result))
+}
+
+/// Decodes any numeric values, including rationals
+pub fn decode_numbers(entry: IFDEntry) -> Result<CustomTagValue, TiffError> {
+    Ok(CustomTagValue::Numbers(get_numbers_from_entry(&entry)?))
+}
+
+/// Decodes BYTE or UNDEFINED values as raw bytes
+pub fn decode_bytes(entry: IFDEntry) -> Result<CustomTagValue, TiffError> {
+    Ok(CustomTagValue::Bytes(entry.try_into()?))
+}
+
+impl Tiff {
+    /// Decodes all tags which weren't decoded as a [`TiffTag`](crate::tiff::TiffTag) but are
+    /// registered in `registry`. Each tag is decoded on its own, so one malformed tag doesn't hide
+    /// the others.
+    pub fn custom_tags(&self, registry: &TagRegistry) -> Vec<Result<CustomTag, TiffError>> {
+        self.unknown_entries
+            .iter()
+            .filter_map(|(ifd, entry)| registry.decode(*ifd, entry.clone()))
+            .collect()
+    }
+
+    /// Decodes a single registered tag, if it's present
+    pub fn custom_tag(
+        &self,
+        registry: &TagRegistry,
+        ifd: IfdKind,
+        id: u16,
+    ) -> Option<Result<CustomTag, TiffError>> {
+        let (_, entry) = self
+            .unknown_entries
+            .iter()
+            .find(|(entry_ifd, entry)| *entry_ifd == ifd && entry.tag == id)?;
+
+        registry.decode(ifd, entry.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        decode_integers, decode_numbers, decode_text, CustomTagValue, TagDefinition, TagRegistry,
+        ValueType,
+    };
+    use crate::tiff::{read_tiff_file, IfdKind, TiffError};
+
+    fn read_rig_tiff() -> Vec<u8> {
+        let mut data = b"II*\0\x08\0\0\0".to_vec();
+        data.extend([3, 0]);
+        data.extend([0x00, 0x01, 3, 0, 1, 0, 0, 0, 100, 0, 0, 0]); // ImageWidth = 100
+        data.extend([0xE8, 0xFD, 2, 0, 4, 0, 0, 0, b'R', b'i', b'g', 0]); // 65000 = "Rig"
+        data.extend([0xE9, 0xFD, 3, 0, 2, 0, 0, 0, 7, 0, 9, 0]); // 65001 = [7, 9]
+        data.extend([0, 0, 0, 0]);
+        data
+    }
+
+    #[test]
+    fn test_custom_tags() {
+        let tiff = read_tiff_file(&read_rig_tiff()).unwrap();
+        let mut registry = TagRegistry::new();
+
+        assert!(tiff.custom_tags(&registry).is_empty());
+
+        registry.register(TagDefinition {
+            ifd: IfdKind::Primary,
+            id: 65000,
+            name: "RigName".to_string(),
+            value_type: ValueType::Ascii,
+            decode: decode_text,
+        });
+        registry.register(TagDefinition {
+            ifd: IfdKind::Primary,
+            id: 65001,
+            name: "RigPosition".to_string(),
+            value_type: ValueType::Short,
+            decode: decode_integers,
+        });
+        // Registered for the wrong IFD, so it's never used
+        registry.register(TagDefinition {
+            ifd: IfdKind::Exif,
+            id: 65000,
+            name: "ExifRigName".to_string(),
+            value_type: ValueType::Ascii,
+            decode: |_| Err(TiffError("Not called".to_string())),
+        });
+
+        let tags: Vec<_> = tiff
+            .custom_tags(&registry)
+            .into_iter()
+            .map(|t| t.unwrap())
+            .collect();
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[0].name, "RigName");
+        assert_eq!(tags[0].value, CustomTagValue::Text("Rig".to_string()));
+        assert_eq!(tags[1].value, CustomTagValue::Integers(vec![7, 9]));
+
+        let tag = tiff.custom_tag(&registry, IfdKind::Primary, 65001);
+        assert_eq!(tag.unwrap().unwrap().name, "RigPosition");
+        assert!(tiff.custom_tag(&registry, IfdKind::Exif, 65000).is_none());
+    }
+
+    #[test]
+    fn test_custom_tag_type_mismatch() {
+        let tiff = read_tiff_file(&read_rig_tiff()).unwrap();
+        let mut registry = TagRegistry::new();
+
+        registry.register(TagDefinition {
+            ifd: IfdKind::Primary,
+            id: 65001,
+            name: "RigPosition".to_string(),
+            value_type: ValueType::Rational,
+            decode: decode_numbers,
+        });
+        registry.register(TagDefinition {
+            ifd: IfdKind::Primary,
+            id: 65000,
+            name: "RigName".to_string(),
+            value_type: ValueType::Ascii,
+            decode: decode_text,
+        });
+
+        let tags = tiff.custom_tags(&registry);
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[0].as_ref().unwrap().name, "RigName");
+        assert!(tags[1].is_err());
+        assert!(tiff
+            .custom_tag(&registry, IfdKind::Primary, 65001)
+            .unwrap()
+            .is_err());
+    }
+}
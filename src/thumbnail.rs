@@ -0,0 +1,174 @@
+use std::io::{Cursor, Read};
+
+use crate::{
+    get_tag_value,
+    jpeg::read_jpeg_dimensions,
+    tiff::{Tiff, TiffTag},
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ThumbnailFormat {
+    Jpeg,
+    Uncompressed {
+        photometric_interpretation: u16, // 2 = RGB, 6 = YCbCr
+        bits_per_sample: Vec<u16>,
+    },
+}
+
+/// A preview image embedded in the metadata, usually in IFD1 of the EXIF data
+#[derive(Debug, Clone)]
+pub struct Thumbnail {
+    pub format: ThumbnailFormat,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub data: Vec<u8>,      // The complete JPEG file or the raw strip data
+    pub tags: Vec<TiffTag>, // The tags from the thumbnail's IFD
+}
+
+impl Tiff {
+    /// The embedded thumbnail, if there is one
+    pub fn thumbnail(&self) -> Option<Thumbnail> {
+        self.thumbnail.clone()
+    }
+}
+
+/// Whether an IFD (other than IFD0) describes a thumbnail rather than another page
+pub(crate) fn is_thumbnail_ifd(tags: &[TiffTag]) -> bool {
+    let is_reduced_resolution = get_tag_value!(tags, TiffTag::NewSubfileType)
+        .map(|v| v & 1 == 1)
+        .unwrap_or(false);
+
+    is_reduced_resolution || get_tag_value!(tags, TiffTag::JPEGInterchangeFormat).is_some()
+}
+
+fn read_bytes(cursor: &mut Cursor<Vec<u8>>, offset: u32, length: u32) -> Option<Vec<u8>> {
+    let end = (offset as usize).checked_add(length as usize)?;
+    if length == 0 || end > cursor.get_ref().len() {
+        return None;
+    }
+
+    cursor.set_position(offset as u64);
+
+    let mut data = vec![0_u8; length as usize];
+    cursor.read_exact(&mut data).ok()?;
+
+    Some(data)
+}
+
+/// Reads the thumbnail described by the tags of a thumbnail IFD, returning None if the IFD
+/// doesn't point to valid image data
+pub(crate) fn read_thumbnail(
+    cursor: &mut Cursor<Vec<u8>>,
+    tags: Vec<TiffTag>,
+) -> Option<Thumbnail> {
+    let jpeg_offset = get_tag_value!(tags, TiffTag::JPEGInterchangeFormat);
+    let jpeg_length = get_tag_value!(tags, TiffTag::JPEGInterchangeFormatLength);
+
+    if let (Some(offset), Some(length)) = (jpeg_offset, jpeg_length) {
+        let data = read_bytes(cursor, *offset, *length)?;
+
+        // The JPEG's own dimensions are authoritative, IFD1 rarely includes them
+        let (width, height) = match read_jpeg_dimensions(&data) {
+            Some((width, height)) => (Some(width), Some(height)),
+            None => (
+                get_tag_value!(tags, TiffTag::ImageWidth).copied(),
+                get_tag_value!(tags, TiffTag::ImageLength).copied(),
+            ),
+        };
+
+        return Some(Thumbnail {
+            format: ThumbnailFormat::Jpeg,
+            width,
+            height,
+            data,
+            tags,
+        });
+    }
+
+    let compression = get_tag_value!(tags, TiffTag::Compression).map(|c| c.as_str());
+    if compression.unwrap_or("No compression") != "No compression" {
+        return None;
+    }
+
+    let strip_offsets = get_tag_value!(tags, TiffTag::StripOffsets)?;
+    let strip_byte_counts = get_tag_value!(tags, TiffTag::StripByteCounts)?;
+
+    let mut data: Vec<u8> = vec![];
+    for (offset, length) in strip_offsets.iter().zip(strip_byte_counts) {
+        data.extend(read_bytes(cursor, *offset, *length)?);
+    }
+
+    Some(Thumbnail {
+        format: ThumbnailFormat::Uncompressed {
+            photometric_interpretation: *get_tag_value!(tags, TiffTag::PhotometricInterpretation)?,
+            bits_per_sample: get_tag_value!(tags, TiffTag::BitsPerSample)
+                .cloned()
+                .unwrap_or(vec![8, 8, 8]),
+        },
+        width: get_tag_value!(tags, TiffTag::ImageWidth).copied(),
+        height: get_tag_value!(tags, TiffTag::ImageLength).copied(),
+        data,
+        tags,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::ThumbnailFormat;
+    use crate::{
+        heif::read_heif,
+        jpeg::read_jpeg,
+        tiff::{read_exif_section, read_tiff_file},
+    };
+
+    #[test]
+    fn test_jpeg_thumbnails() {
+        for (file, width, height, length) in [
+            ("test_images/test9.jpeg", 160, 120, 4589),
+            ("test_images/gps.jpeg", 160, 120, 6339),
+            ("test_images/PaintTool_sample.jpeg", 77, 88, 2251),
+        ] {
+            let data = fs::read(file).unwrap();
+            let exif = read_jpeg(&data).unwrap().exif.unwrap();
+
+            let thumbnail = exif.thumbnail().unwrap();
+            assert_eq!(thumbnail.format, ThumbnailFormat::Jpeg, "{}", file);
+            assert_eq!(
+                (thumbnail.width, thumbnail.height),
+                (Some(width), Some(height)),
+                "{}",
+                file
+            );
+            assert_eq!(thumbnail.data.len(), length, "{}", file);
+            assert!(thumbnail.data.starts_with(b"\xFF\xD8\xFF"), "{}", file);
+        }
+    }
+
+    #[test]
+    fn test_exif_section_thumbnail() {
+        // The EXIF item of a HEIF file, after its 4 byte header offset: IFD0 with only an
+        // Orientation, then IFD1 pointing to a JPEG right after it
+        let jpeg = b"\xFF\xD8\xFF\xD9";
+        let mut data = b"Exif\0\0II*\0\x08\0\0\0".to_vec();
+        data.extend([1, 0, 0x12, 0x01, 3, 0, 1, 0, 0, 0, 1, 0, 0, 0, 26, 0, 0, 0]);
+        data.extend([2, 0, 0x01, 0x02, 4, 0, 1, 0, 0, 0, 56, 0, 0, 0]);
+        data.extend([0x02, 0x02, 4, 0, 1, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0]);
+        data.extend(jpeg);
+
+        let thumbnail = read_exif_section(&data).unwrap().thumbnail().unwrap();
+        assert_eq!(thumbnail.format, ThumbnailFormat::Jpeg);
+        assert_eq!((thumbnail.width, thumbnail.height), (None, None));
+        assert_eq!(thumbnail.data, jpeg);
+    }
+
+    #[test]
+    fn test_no_thumbnail() {
+        let data = fs::read("test_images/test.tif").unwrap();
+        assert!(read_tiff_file(&data).unwrap().thumbnail().is_none());
+
+        let data = fs::read("test_images/image1.heic").unwrap();
+        assert!(read_heif(data).exif.unwrap().thumbnail().is_none());
+    }
+}
@@ -1,6 +1,14 @@
 use std::io::{Cursor, Read};
 
-use crate::{read_unpack, unpack};
+use crate::{
+    gps::GpsAltitudeRef,
+    icc::{read_icc_profile, IccProfile},
+    makernote::{find_value_offset, read_ifd_checked, read_makernote, MakerNote, MakerNoteVendor},
+    orientation::Orientation,
+    read_unpack,
+    thumbnail::{is_thumbnail_ifd, read_thumbnail, Thumbnail},
+    unpack,
+};
 
 use super::utils::{vec_to_array, Endianness};
 
@@ -18,9 +26,10 @@ pub enum IFDEntryValue {
     SRATIONAL(i32, i32),
     FLOAT(f32),
     DOUBLE(f64),
+    UTF8(u8), // Added in EXIF 3.0
 }
 
-fn get_tiff_value_type_size(value_type: u16) -> Result<usize, TiffError> {
+pub(crate) fn get_tiff_value_type_size(value_type: u16) -> Result<usize, TiffError> {
     match value_type {
         1 => Ok(1),
         2 => Ok(1),
@@ -34,11 +43,13 @@ fn get_tiff_value_type_size(value_type: u16) -> Result<usize, TiffError> {
         10 => Ok(8),
         11 => Ok(4),
         12 => Ok(8),
+        13 => Ok(4),
+        129 => Ok(1),
         unknown => Err(TiffError(format!("Encountered unknown TIFF value type: {}", unknown))),
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct IFDEntry {
     pub tag: u16,
     pub values: Vec<IFDEntryValue>,
@@ -55,18 +66,18 @@ impl IFDEntry {
     }
 }
 
-impl TryInto<String> for IFDEntry {
+impl TryInto<String> for &IFDEntry {
     type Error = TiffError;
 
     fn try_into(self) -> Result<String, Self::Error> {
         let mut chars: Vec<u8> = vec![];
-        for value in self.values {
+        for &value in &self.values {
             match value {
-                IFDEntryValue::ASCII(0) => {}
-                IFDEntryValue::ASCII(b) => chars.push(b),
+                IFDEntryValue::ASCII(0) | IFDEntryValue::UTF8(0) => {}
+                IFDEntryValue::ASCII(b) | IFDEntryValue::UTF8(b) => chars.push(b),
                 _ => {
                     return Err(TiffError(
-                        format!("[Tag {}] Expected only ASCII values (got {:?})", self.tag, value),
+                        format!("[Tag {}] Expected only ASCII/UTF-8 values (got {:?})", self.tag, value),
                     ))
                 }
             }
@@ -76,7 +87,7 @@ impl TryInto<String> for IFDEntry {
     }
 }
 
-impl TryInto<u8> for IFDEntry {
+impl TryInto<u8> for &IFDEntry {
     type Error = TiffError;
 
     fn try_into(self) -> Result<u8, Self::Error> {
@@ -89,13 +100,13 @@ impl TryInto<u8> for IFDEntry {
     }
 }
 
-impl TryInto<Vec<u8>> for IFDEntry {
+impl TryInto<Vec<u8>> for &IFDEntry {
     type Error = TiffError;
 
     fn try_into(self) -> Result<Vec<u8>, Self::Error> {
         let mut result = vec![];
 
-        for v in self.values {
+        for &v in &self.values {
             match v {
                 IFDEntryValue::BYTE(v) | IFDEntryValue::ASCII(v) | IFDEntryValue::UNDEFINED(v) => {
                     result.push(v)
@@ -116,7 +127,7 @@ impl TryInto<Vec<u8>> for IFDEntry {
     }
 }
 
-impl TryInto<u16> for IFDEntry {
+impl TryInto<u16> for &IFDEntry {
     type Error = TiffError;
 
     fn try_into(self) -> Result<u16, Self::Error> {
@@ -129,7 +140,28 @@ impl TryInto<u16> for IFDEntry {
     }
 }
 
-impl TryInto<u32> for IFDEntry {
+impl TryInto<Vec<u16>> for &IFDEntry {
+    type Error = TiffError;
+
+    fn try_into(self) -> Result<Vec<u16>, Self::Error> {
+        let mut result: Vec<u16> = vec![];
+
+        for &v in &self.values {
+            match v {
+                IFDEntryValue::SHORT(v) => result.push(v),
+                _ => {
+                    return Err(TiffError(
+                        format!("[Tag {}] Expected all values to be SHORT (got {:?})", self.tag, v),
+                    ))
+                }
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+impl TryInto<u32> for &IFDEntry {
     type Error = TiffError;
 
     fn try_into(self) -> Result<u32, Self::Error> {
@@ -142,7 +174,29 @@ impl TryInto<u32> for IFDEntry {
     }
 }
 
-impl TryInto<f64> for IFDEntry {
+impl TryInto<Vec<u32>> for &IFDEntry {
+    type Error = TiffError;
+
+    fn try_into(self) -> Result<Vec<u32>, Self::Error> {
+        let mut result: Vec<u32> = vec![];
+
+        for &v in &self.values {
+            match v {
+                IFDEntryValue::SHORT(v) => result.push(v as u32),
+                IFDEntryValue::LONG(v) => result.push(v),
+                _ => {
+                    return Err(TiffError(
+                        format!("[Tag {}] Expected all values to be SHORT/LONG (got {:?})", self.tag, v),
+                    ))
+                }
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+impl TryInto<f64> for &IFDEntry {
     type Error = TiffError;
 
     fn try_into(self) -> Result<f64, Self::Error> {
@@ -157,13 +211,13 @@ impl TryInto<f64> for IFDEntry {
     }
 }
 
-impl TryInto<Vec<f64>> for IFDEntry {
+impl TryInto<Vec<f64>> for &IFDEntry {
     type Error = TiffError;
 
     fn try_into(self) -> Result<Vec<f64>, Self::Error> {
         let mut result: Vec<f64> = vec![];
 
-        for v in self.values {
+        for &v in &self.values {
             match v {
                 IFDEntryValue::RATIONAL(a, b) => result.push((a as f64) / (b as f64)),
                 IFDEntryValue::SRATIONAL(a, b) => result.push((a as f64) / (b as f64)),
@@ -180,40 +234,107 @@ impl TryInto<Vec<f64>> for IFDEntry {
     }
 }
 
+// Owned entries convert the same way, so the conversions above don't need to take ownership
+macro_rules! impl_owned_try_into {
+    ($($type:ty),*) => {
+        $(
+            impl TryInto<$type> for IFDEntry {
+                type Error = TiffError;
+
+                fn try_into(self) -> Result<$type, Self::Error> {
+                    (&self).try_into()
+                }
+            }
+        )*
+    };
+}
+
+impl_owned_try_into!(String, u8, Vec<u8>, u16, Vec<u16>, u32, Vec<u32>, f64, Vec<f64>);
+
 /// TIFF tags.
 ///
-/// See <https://www.loc.gov/preservation/digital/formats/content/tiff_tags.shtml>
-// TODO: Some tags have overlapping IDs because the other IFDs (EXIF, GPS) can just put whatever tag IDs they want
+/// Covers the baseline TIFF 6.0 tags needed to describe an image's layout plus every tag defined
+/// for IFD0 and the EXIF IFD by EXIF 2.32 / 3.0, the GeoTIFF tags and the DNG 1.6 tags.
+///
+/// See <https://www.loc.gov/preservation/digital/formats/content/tiff_tags.shtml> and
+/// <https://www.cipa.jp/std/documents/download_e.html?DC-008-Translation-2023-E>
+// Tag IDs are only unique within an IFD (the GPS IFD reuses 0-31), so use tiff_tag_from_entry() to decode them
 #[non_exhaustive]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum TiffTag {
     Unknown(IFDEntry),
 
+    NewSubfileType(u32),
+    SubfileType(u16),
     ImageWidth(u32),
     ImageLength(u32), // also referred to as ImageHeight
-    BitsPerSample(u16),
+    BitsPerSample(Vec<u16>),
     PhotometricInterpretation(u16),
+    Threshholding(u16),
+    CellWidth(u16),
+    CellLength(u16),
     FillOrder(u16),
-    StripOffsets(u32),
+    DocumentName(String),
+    StripOffsets(Vec<u32>),
     SamplesPerPixel(u16),
     RowsPerStrip(u32),
-    StripByteCounts(u32),
+    StripByteCounts(Vec<u32>),
+    MinSampleValue(Vec<u16>),
+    MaxSampleValue(Vec<u16>),
     PlanarConfiguration(u16),
+    PageName(String),
+    PageNumber([u16; 2]),
+    TransferFunction(Vec<u16>),
+    Predictor(u16),
+    WhitePoint([f64; 2]),
+    PrimaryChromaticities([f64; 6]),
+    ColorMap(Vec<u16>),
+    TileWidth(u32),
+    TileLength(u32),
+    TileOffsets(Vec<u32>),
+    TileByteCounts(Vec<u32>),
+    SubIFDs(Vec<u32>), // Offsets to child IFDs, e.g. the raw image of a DNG, see Tiff::pages()
+    ExtraSamples(Vec<u16>),
+    SampleFormat(Vec<u16>),
+    JPEGInterchangeFormat(u32),
+    JPEGInterchangeFormatLength(u32),
+    YCbCrCoefficients([f64; 3]),
+    YCbCrSubSampling([u16; 2]),
+    YCbCrPositioning(String),
+    ReferenceBlackWhite([f64; 6]),
 
     GPSVersionID([u8; 4]),
     GPSLatitudeRef(String),
     GPSLatitude([f64; 3]),
     GPSLongitudeRef(String),
     GPSLongitude([f64; 3]),
-    GPSAltitudeRef(String),
+    GPSAltitudeRef(GpsAltitudeRef),
     GPSAltitude(f64),
     GPSTimeStamp([f64; 3]),
     GPSSatellites(String),
     GPSStatus(String),
+    GPSMeasureMode(String),
+    GPSDop(f64),
+    GPSSpeedRef(String),
+    GPSSpeed(f64),
+    GPSTrackRef(String),
+    GPSTrack(f64),
     GPSImgDirectionRef(String),
     GPSImgDirection(f64),
     GPSMapDatum(String),
+    GPSDestLatitudeRef(String),
+    GPSDestLatitude([f64; 3]),
+    GPSDestLongitudeRef(String),
+    GPSDestLongitude([f64; 3]),
+    GPSDestBearingRef(String),
+    GPSDestBearing(f64),
+    GPSDestDistanceRef(String),
+    GPSDestDistance(f64),
+    GPSProcessingMethod(String),
+    GPSAreaInformation(String),
     GPSDateStamp(String),
+    GPSDifferential(String),
+    GPSHPositioningError(f64),
     Compression(String),
     ImageDescription(String),
     Make(String),
@@ -232,34 +353,76 @@ pub enum TiffTag {
     Copyright(String),
     ExposureTime(String),
     FNumber(String),
+    ModelPixelScale(Vec<f64>), // GeoTIFF, see Tiff::geotiff()
+    IptcNaa(Vec<u8>), // IPTC-IIM data, see Tiff::iptc()
+    ModelTiepoint(Vec<f64>),
+    ModelTransformation([f64; 16]),
+    Photoshop(Vec<u8>), // Photoshop image resources, see Tiff::photoshop()
     ExifIfdPointer(u32),
+    InterColorProfile(Vec<u8>), // An ICC profile, see Tiff::icc_profile()
+    GeoKeyDirectory(Vec<u16>),
+    GeoDoubleParams(Vec<f64>),
+    GeoAsciiParams(String), // Keeps the NUL terminators since the GeoKeys index into this
     ExposureProgram(String),
+    SpectralSensitivity(String),
     GpsIfdPointer(u32),
+    PhotographicSensitivity(Vec<u16>), // ISO, called ISOSpeedRatings before EXIF 2.3
+    Oecf(Vec<u8>), // Opto-electronic conversion function
+    SensitivityType(String),
+    StandardOutputSensitivity(u32),
+    RecommendedExposureIndex(u32),
+    ISOSpeed(u32),
+    ISOSpeedLatitudeyyy(u32),
+    ISOSpeedLatitudezzz(u32),
     ExifVersion(String),
     DateTimeOriginal(String),
     DateTimeDigitized(String),
+    OffsetTime(String),
+    OffsetTimeOriginal(String),
+    OffsetTimeDigitized(String),
+    ComponentsConfiguration(String),
     CompressedBitsPerPixel(String),
     ShutterSpeedValue(String),
     ApertureValue(String),
+    BrightnessValue(String),
     ExposureBiasValue(String),
     MaxApertureValue(String),
+    SubjectDistance(String),
     MeteringMode(String),
     LightSource(String),
     Flash(String),
     FocalLength(String),
+    SubjectArea(Vec<u16>),
     MakerNote(Vec<u8>),
     UserComment(String),
     SubsecTime(String),
     SubsecTimeOriginal(String),
     SubsecTimeDigitized(String),
+    Temperature(f64),
+    Humidity(f64),
+    Pressure(f64),
+    WaterDepth(f64),
+    Acceleration(f64),
+    CameraElevationAngle(f64),
 
     FlashpixVersion(String),
+    ColorSpace(String),
     PixelXDimension(u32),
     PixelYDimension(u32),
+    RelatedSoundFile(String),
+    InteropIfdPointer(u32),
+    FlashEnergy(f64),
+    SpatialFrequencyResponse(Vec<u8>),
     FocalPlaneXResolution(String),
     FocalPlaneYResolution(String),
     FocalPlaneResolutionUnit(String),
+    SubjectLocation([u16; 2]),
+    ExposureIndex(f64),
     SensingMethod(String),
+    FileSource(String),
+    SceneType(String),
+    CFAPattern(Vec<u8>),
+    CustomRendered(String),
     ExposureMode(String),
     WhiteBalance(String),
     DigitalZoomRatio(String),
@@ -269,24 +432,150 @@ pub enum TiffTag {
     Contrast(String),
     Saturation(String),
     Sharpness(String),
+    DeviceSettingDescription(Vec<u8>),
     SubjectDistanceRange(String),
+    ImageUniqueID(String),
+    CameraOwnerName(String),
+    BodySerialNumber(String),
+    LensSpecification([f64; 4]), // Min focal length, max focal length, min F number at min focal length, min F number at max focal length
     LensMake(String),
     LensModel(String),
-    
-    // This enum is incomplete and includes tags I found interesting/encountered while testing
+    LensSerialNumber(String),
+    ImageTitle(String),
+    Photographer(String),
+    ImageEditor(String),
+    CameraFirmware(String),
+    RAWDevelopingSoftware(String),
+    ImageEditingSoftware(String),
+    MetadataEditingSoftware(String),
+    CompositeImage(String),
+    SourceImageNumberOfCompositeImage([u16; 2]),
+    SourceExposureTimesOfCompositeImage(Vec<u8>),
+    Gamma(f64),
+
+    // DNG 1.6, see <https://helpx.adobe.com/camera-raw/digital-negative.html>
+    DNGVersion([u8; 4]),
+    DNGBackwardVersion([u8; 4]),
+    UniqueCameraModel(String),
+    LocalizedCameraModel(String),
+    CFAPlaneColor(Vec<u8>),
+    CFALayout(String),
+    LinearizationTable(Vec<u16>),
+    BlackLevelRepeatDim([u16; 2]),
+    BlackLevel(Vec<f64>),
+    BlackLevelDeltaH(Vec<f64>),
+    BlackLevelDeltaV(Vec<f64>),
+    WhiteLevel(Vec<u32>),
+    DefaultScale([f64; 2]),
+    DefaultCropOrigin([f64; 2]),
+    DefaultCropSize([f64; 2]),
+    ColorMatrix1(Vec<Vec<f64>>), // Matrices are stored as rows
+    ColorMatrix2(Vec<Vec<f64>>),
+    CameraCalibration1(Vec<Vec<f64>>),
+    CameraCalibration2(Vec<Vec<f64>>),
+    ReductionMatrix1(Vec<Vec<f64>>),
+    ReductionMatrix2(Vec<Vec<f64>>),
+    AnalogBalance(Vec<f64>),
+    AsShotNeutral(Vec<f64>),
+    AsShotWhiteXY([f64; 2]),
+    BaselineExposure(f64),
+    BaselineNoise(f64),
+    BaselineSharpness(f64),
+    BayerGreenSplit(u32),
+    LinearResponseLimit(f64),
+    CameraSerialNumber(String),
+    LensInfo([f64; 4]),
+    ChromaBlurRadius(f64),
+    AntiAliasStrength(f64),
+    ShadowScale(f64),
+    DNGPrivateData(Vec<u8>),
+    MakerNoteSafety(String),
+    CalibrationIlluminant1(String),
+    CalibrationIlluminant2(String),
+    BestQualityScale(f64),
+    RawDataUniqueID([u8; 16]),
+    OriginalRawFileName(String),
+    OriginalRawFileData(Vec<u8>),
+    ActiveArea([u32; 4]), // Top, left, bottom, right
+    MaskedAreas(Vec<[u32; 4]>),
+    AsShotICCProfile(Vec<u8>),
+    AsShotPreProfileMatrix(Vec<f64>),
+    CurrentICCProfile(Vec<u8>),
+    CurrentPreProfileMatrix(Vec<f64>),
+    ColorimetricReference(String),
+    CameraCalibrationSignature(String),
+    ProfileCalibrationSignature(String),
+    ExtraCameraProfiles(Vec<u32>),
+    AsShotProfileName(String),
+    NoiseReductionApplied(f64),
+    ProfileName(String),
+    ProfileHueSatMapDims([u32; 3]),
+    ProfileHueSatMapData1(Vec<f64>),
+    ProfileHueSatMapData2(Vec<f64>),
+    ProfileToneCurve(Vec<f64>),
+    ProfileEmbedPolicy(String),
+    ProfileCopyright(String),
+    ForwardMatrix1(Vec<Vec<f64>>),
+    ForwardMatrix2(Vec<Vec<f64>>),
+    PreviewApplicationName(String),
+    PreviewApplicationVersion(String),
+    PreviewSettingsName(String),
+    PreviewSettingsDigest([u8; 16]),
+    PreviewColorSpace(String),
+    PreviewDateTime(String),
+    RawImageDigest([u8; 16]),
+    OriginalRawFileDigest([u8; 16]),
+    SubTileBlockSize([u32; 2]),
+    RowInterleaveFactor(u32),
+    ProfileLookTableDims([u32; 3]),
+    ProfileLookTableData(Vec<f64>),
+    OpcodeList1(Vec<u8>),
+    OpcodeList2(Vec<u8>),
+    OpcodeList3(Vec<u8>),
+    NoiseProfile(Vec<f64>),
+    OriginalDefaultFinalSize([u32; 2]),
+    OriginalBestQualityFinalSize([u32; 2]),
+    OriginalDefaultCropSize([f64; 2]),
+    ProfileHueSatMapEncoding(String),
+    ProfileLookTableEncoding(String),
+    BaselineExposureOffset(f64),
+    DefaultBlackRender(String),
+    NewRawImageDigest([u8; 16]),
+    RawToPreviewGain(f64),
+    DefaultUserCrop([f64; 4]), // Top, left, bottom, right relative to the default crop
+    DepthFormat(String),
+    DepthNear(f64),
+    DepthFar(f64),
+    DepthUnits(String),
+    DepthMeasureType(String),
+    EnhanceParams(String),
+    ProfileGainTableMap(Vec<u8>),
+    SemanticName(String),
+    SemanticInstanceID(String),
+    CalibrationIlluminant3(String),
+    CameraCalibration3(Vec<Vec<f64>>),
+    ColorMatrix3(Vec<Vec<f64>>),
+    ForwardMatrix3(Vec<Vec<f64>>),
+    IlluminantData1(Vec<u8>),
+    IlluminantData2(Vec<u8>),
+    IlluminantData3(Vec<u8>),
+    MaskSubArea([u32; 4]),
+    ProfileHueSatMapData3(Vec<f64>),
+    ReductionMatrix3(Vec<Vec<f64>>),
+    RGBTables(Vec<u8>),
 }
 
 #[macro_export]
 macro_rules! get_tag_value {
     ($tiff_tags:expr, $tag_variant:path) => {
-        $tiff_tags.iter().find_map(|tag: &TiffTag| match tag {
+        $tiff_tags.iter().find_map(|tag| match tag {
             $tag_variant(value) => Some(value),
             _ => None,
         })
     };
 }
 
-fn get_rational_repr_from_ifd_entry(entry: IFDEntry) -> Result<String, TiffError> {
+fn get_rational_repr_from_ifd_entry(entry: &IFDEntry) -> Result<String, TiffError> {
     if entry.values.len() != 1 {
         return Err(TiffError(
             format!("Expected only one value (got {})", entry.values.len())
@@ -303,10 +592,10 @@ fn get_rational_repr_from_ifd_entry(entry: IFDEntry) -> Result<String, TiffError
     }
 }
 
-fn get_string_from_entry_with_undefined_values(entry: IFDEntry) -> Result<String, TiffError> {
+fn get_string_from_entry_with_undefined_values(entry: &IFDEntry) -> Result<String, TiffError> {
     let mut string_data: Vec<u8> = vec![];
 
-    for v in entry.values {
+    for &v in &entry.values {
         match v {
             IFDEntryValue::UNDEFINED(b) => string_data.push(b),
             _ => return Err(TiffError(
@@ -318,7 +607,163 @@ fn get_string_from_entry_with_undefined_values(entry: IFDEntry) -> Result<String
     Ok(String::from_utf8_lossy(&string_data).to_string())
 }
 
-fn get_ushort_or_ulong_from_entry(entry: IFDEntry) -> Result<u32, TiffError> {
+/// Decodes an UNDEFINED value which starts with an 8 byte character code (UserComment, GPSProcessingMethod, etc)
+fn get_string_from_entry_with_character_code(entry: &IFDEntry) -> Result<String, TiffError> {
+    let tag = entry.tag;
+    let data: Vec<u8> = entry.try_into()?;
+
+    if data.len() < 8 {
+        return Err(TiffError(format!(
+            "[Tag {}] Expected at least 8 bytes for the character code (got {})",
+            tag,
+            data.len(),
+        )));
+    }
+
+    let (character_code, string_data) = data.split_at(8);
+
+    let string_value = match character_code {
+        b"UNICODE\0" => {
+            // The spec doesn't say which byte order UCS-2 is stored in, and writers disagree, so
+            // guess based on which half of each code unit holds the (usually empty) high byte
+            let even_zeros = string_data.iter().step_by(2).filter(|b| **b == 0).count();
+            let odd_zeros = string_data.iter().skip(1).step_by(2).filter(|b| **b == 0).count();
+
+            let code_units: Vec<u16> = string_data
+                .chunks_exact(2)
+                .map(|c| match even_zeros > odd_zeros {
+                    true => u16::from_be_bytes([c[0], c[1]]),
+                    false => u16::from_le_bytes([c[0], c[1]]),
+                })
+                .collect();
+
+            String::from_utf16_lossy(&code_units)
+        }
+        // TODO: Properly decode JIS?
+        _ => String::from_utf8_lossy(string_data).to_string(),
+    };
+
+    Ok(string_value.trim_end_matches('\0').to_string())
+}
+
+/// Reads a degrees, minutes, seconds coordinate. Some writers only store degrees or degrees and
+/// (decimal) minutes, in which case the missing components are zero
+fn get_dms_from_entry(entry: &IFDEntry) -> Result<[f64; 3], TiffError> {
+    let tag = entry.tag;
+    let values: Vec<f64> = entry.try_into()?;
+
+    match values.len() {
+        1..=3 => Ok([0, 1, 2].map(|i| values.get(i).copied().unwrap_or(0.0))),
+        count => Err(TiffError(format!(
+            "[Tag {}] Expected 1-3 values for a coordinate (got {})",
+            tag, count,
+        ))),
+    }
+}
+
+fn get_single_undefined_value_from_entry(entry: &IFDEntry) -> Result<u8, TiffError> {
+    match entry.get_single_value()? {
+        IFDEntryValue::UNDEFINED(v) => Ok(v),
+        v => Err(TiffError(format!(
+            "[Tag {}] Expected value to be UNDEFINED (got {:?})",
+            entry.tag, v,
+        ))),
+    }
+}
+
+fn get_light_source_name(value: u16) -> &'static str {
+    match value {
+        0 => "Unknown",
+        1 => "Daylight",
+        2 => "Fluorescent",
+        3 => "Tungsten (incandescent light)",
+        4 => "Flash",
+        9 => "Fine weather",
+        10 => "Cloudy weather",
+        11 => "Shade",
+        12 => "Daylight fluorescent (D 5700 - 7100K)",
+        13 => "Day white fluorescent (N 4600 - 5400K)",
+        14 => "Cool white fluorescent (W 3900 - 4500K)",
+        15 => "White fluorescent (WW 3200 - 3700K)",
+        17 => "Standard light A",
+        18 => "Standard light B",
+        19 => "Standard light C",
+        20 => "D55",
+        21 => "D65",
+        22 => "D75",
+        23 => "D50",
+        24 => "ISO studio tungsten",
+        255 => "Other light source",
+        _ => "Invalid",
+    }
+}
+
+/// Reads numbers of any type, for tags which allow several types like DNG's BlackLevel
+pub(crate) fn get_numbers_from_entry(entry: &IFDEntry) -> Result<Vec<f64>, TiffError> {
+    let mut result: Vec<f64> = vec![];
+
+    for &v in &entry.values {
+        result.push(match v {
+            IFDEntryValue::BYTE(v) => v as f64,
+            IFDEntryValue::SHORT(v) => v as f64,
+            IFDEntryValue::LONG(v) => v as f64,
+            IFDEntryValue::RATIONAL(a, b) => (a as f64) / (b as f64),
+            IFDEntryValue::SBYTE(v) => v as f64,
+            IFDEntryValue::SSHORT(v) => v as f64,
+            IFDEntryValue::SLONG(v) => v as f64,
+            IFDEntryValue::SRATIONAL(a, b) => (a as f64) / (b as f64),
+            IFDEntryValue::FLOAT(v) => v as f64,
+            IFDEntryValue::DOUBLE(v) => v,
+            _ => return Err(TiffError(
+                format!("[Tag {}] Expected all values to be numbers (got {:?})", entry.tag, v),
+            )),
+        });
+    }
+
+    Ok(result)
+}
+
+/// Reads a string which may be stored as BYTEs instead of ASCII, as some DNG tags allow
+fn get_string_from_bytes_or_ascii(entry: &IFDEntry) -> Result<String, TiffError> {
+    let data: Vec<u8> = entry.try_into()?;
+
+    Ok(String::from_utf8_lossy(&data).trim_end_matches('\0').to_string())
+}
+
+/// The shape of a DNG matrix, whose size depends on the number of color planes (usually 3 or 4)
+enum MatrixShape {
+    ThreeColumns, // e.g. ColorMatrix, which maps XYZ to the camera's color planes
+    ThreeRows,    // e.g. ForwardMatrix, which maps the camera's color planes to XYZ
+    Square,       // e.g. CameraCalibration
+}
+
+fn get_matrix_from_entry(entry: &IFDEntry, shape: MatrixShape) -> Result<Vec<Vec<f64>>, TiffError> {
+    let tag = entry.tag;
+    let values: Vec<f64> = entry.try_into()?;
+
+    let columns = match shape {
+        MatrixShape::ThreeColumns => 3,
+        MatrixShape::ThreeRows => values.len() / 3,
+        MatrixShape::Square => (values.len() as f64).sqrt() as usize,
+    };
+
+    let rows = match columns {
+        0 => 0,
+        _ => values.len() / columns,
+    };
+
+    if rows == 0 || rows * columns != values.len() {
+        return Err(TiffError(format!(
+            "[Tag {}] {} values don't make a valid matrix",
+            tag,
+            values.len()
+        )));
+    }
+
+    Ok(values.chunks_exact(columns).map(|row| row.to_vec()).collect())
+}
+
+fn get_ushort_or_ulong_from_entry(entry: &IFDEntry) -> Result<u32, TiffError> {
     if entry.values.len() != 1 {
         return Err(TiffError(
             format!("Expected only one value (got {})", entry.values.len())
@@ -335,45 +780,83 @@ fn get_ushort_or_ulong_from_entry(entry: IFDEntry) -> Result<u32, TiffError> {
     }
 }
 
+fn gps_tag_from_entry(entry: &IFDEntry) -> Result<TiffTag, TiffError> {
+    match entry.tag {
+        0 => match vec_to_array(entry.try_into()?) {
+            Ok(arr) => Ok(TiffTag::GPSVersionID(arr)),
+            Err(message) => Err(TiffError(message)),
+        },
+        1 => Ok(TiffTag::GPSLatitudeRef(entry.try_into()?)),
+        2 => Ok(TiffTag::GPSLatitude(get_dms_from_entry(entry)?)),
+        3 => Ok(TiffTag::GPSLongitudeRef(entry.try_into()?)),
+        4 => Ok(TiffTag::GPSLongitude(get_dms_from_entry(entry)?)),
+        5 => Ok(TiffTag::GPSAltitudeRef(GpsAltitudeRef::from(<&IFDEntry as TryInto<u8>>::try_into(entry)?))),
+        6 => Ok(TiffTag::GPSAltitude(entry.try_into()?)),
+        7 => match vec_to_array(entry.try_into()?) {
+            Ok(arr) => Ok(TiffTag::GPSTimeStamp(arr)),
+            Err(message) => Err(TiffError(message)),
+        },
+        8 => Ok(TiffTag::GPSSatellites(entry.try_into()?)),
+        9 => Ok(TiffTag::GPSStatus(entry.try_into()?)),
+        10 => Ok(TiffTag::GPSMeasureMode(entry.try_into()?)),
+        11 => Ok(TiffTag::GPSDop(entry.try_into()?)),
+        12 => Ok(TiffTag::GPSSpeedRef(entry.try_into()?)),
+        13 => Ok(TiffTag::GPSSpeed(entry.try_into()?)),
+        14 => Ok(TiffTag::GPSTrackRef(entry.try_into()?)),
+        15 => Ok(TiffTag::GPSTrack(entry.try_into()?)),
+        16 => Ok(TiffTag::GPSImgDirectionRef(entry.try_into()?)),
+        17 => Ok(TiffTag::GPSImgDirection(entry.try_into()?)),
+        18 => Ok(TiffTag::GPSMapDatum(entry.try_into()?)),
+        19 => Ok(TiffTag::GPSDestLatitudeRef(entry.try_into()?)),
+        20 => Ok(TiffTag::GPSDestLatitude(get_dms_from_entry(entry)?)),
+        21 => Ok(TiffTag::GPSDestLongitudeRef(entry.try_into()?)),
+        22 => Ok(TiffTag::GPSDestLongitude(get_dms_from_entry(entry)?)),
+        23 => Ok(TiffTag::GPSDestBearingRef(entry.try_into()?)),
+        24 => Ok(TiffTag::GPSDestBearing(entry.try_into()?)),
+        25 => Ok(TiffTag::GPSDestDistanceRef(entry.try_into()?)),
+        26 => Ok(TiffTag::GPSDestDistance(entry.try_into()?)),
+        27 => Ok(TiffTag::GPSProcessingMethod(get_string_from_entry_with_character_code(entry)?)),
+        28 => Ok(TiffTag::GPSAreaInformation(get_string_from_entry_with_character_code(entry)?)),
+        29 => Ok(TiffTag::GPSDateStamp(entry.try_into()?)),
+        30 => Ok(TiffTag::GPSDifferential(match <&IFDEntry as TryInto<u16>>::try_into(entry)? {
+            0 => "Measurement without differential correction",
+            1 => "Differential correction applied",
+            _ => "Invalid",
+        }.to_string())),
+        31 => Ok(TiffTag::GPSHPositioningError(entry.try_into()?)),
+
+        _ => Ok(TiffTag::Unknown(entry.clone())),
+    }
+}
+
+/// Converts an entry to a [`TiffTag`], interpreting its tag ID according to the IFD it was read from
+pub fn tiff_tag_from_entry(entry: &IFDEntry, ifd: IfdKind) -> Result<TiffTag, TiffError> {
+    match ifd {
+        IfdKind::Gps => gps_tag_from_entry(entry),
+        IfdKind::Primary | IfdKind::Exif => TiffTag::try_from(entry),
+        IfdKind::MakerNote(_) => Ok(TiffTag::Unknown(entry.clone())),
+    }
+}
+
 impl TryFrom<IFDEntry> for TiffTag {
     type Error = TiffError;
 
     fn try_from(entry: IFDEntry) -> Result<TiffTag, TiffError> {
+        TiffTag::try_from(&entry)
+    }
+}
+
+impl TryFrom<&IFDEntry> for TiffTag {
+    type Error = TiffError;
+
+    fn try_from(entry: &IFDEntry) -> Result<TiffTag, TiffError> {
         match entry.tag {
-            0 => match vec_to_array(entry.try_into()?) {
-                Ok(arr) => Ok(TiffTag::GPSVersionID(arr)),
-                Err(message) => Err(TiffError(message)),
-            },
-            1 => Ok(TiffTag::GPSLatitudeRef(entry.try_into()?)),
-            2 => match vec_to_array(entry.try_into()?) {
-                Ok(arr) => Ok(TiffTag::GPSLatitude(arr)),
-                Err(message) => Err(TiffError(message)),
-            },
-            3 => Ok(TiffTag::GPSLongitudeRef(entry.try_into()?)),
-            4 => match vec_to_array(entry.try_into()?) {
-                Ok(arr) => Ok(TiffTag::GPSLongitude(arr)),
-                Err(message) => Err(TiffError(message)),
-            },
-            5 => Ok(TiffTag::GPSAltitudeRef(match <IFDEntry as TryInto<u8>>::try_into(entry)? {
-                0 => "Above sea level",
-                1 => "Below sea level",
-                _ => "Invalid",
-            }.to_string())),
-            6 => Ok(TiffTag::GPSAltitude(entry.try_into()?)),
-            7 => match vec_to_array(entry.try_into()?) {
-                Ok(arr) => Ok(TiffTag::GPSTimeStamp(arr)),
-                Err(message) => Err(TiffError(message)),
-            },
-            8 => Ok(TiffTag::GPSSatellites(entry.try_into()?)),
-            9 => Ok(TiffTag::GPSStatus(entry.try_into()?)),
-            16 => Ok(TiffTag::GPSImgDirectionRef(entry.try_into()?)),
-            17 => Ok(TiffTag::GPSImgDirection(entry.try_into()?)),
-            18 => Ok(TiffTag::GPSMapDatum(entry.try_into()?)),
-            29 => Ok(TiffTag::GPSDateStamp(entry.try_into()?)),
+            254 => Ok(TiffTag::NewSubfileType(entry.try_into()?)),
+            255 => Ok(TiffTag::SubfileType(entry.try_into()?)),
             256 => Ok(TiffTag::ImageWidth(get_ushort_or_ulong_from_entry(entry)?)),
             257 => Ok(TiffTag::ImageLength(get_ushort_or_ulong_from_entry(entry)?)),
             258 => Ok(TiffTag::BitsPerSample(entry.try_into()?)),
-            259 => Ok(TiffTag::Compression(match <IFDEntry as TryInto<u16>>::try_into(entry)? {
+            259 => Ok(TiffTag::Compression(match <&IFDEntry as TryInto<u16>>::try_into(entry)? {
                 1 => "No compression",
                 2 => "CCITT modified Huffman RLE",
                 3 => "CCITT Group 3 fax encoding",
@@ -383,39 +866,102 @@ impl TryFrom<IFDEntry> for TiffTag {
                 7 => "JPEG (new-style)",
                 8 => "Deflate",
                 32773 => "PackBits",
+                32946 => "Deflate (PKZIP)",
                 _ => "Invalid/Unknown",
             }.to_string())),
             262 => Ok(TiffTag::PhotometricInterpretation(entry.try_into()?)),
+            263 => Ok(TiffTag::Threshholding(entry.try_into()?)),
+            264 => Ok(TiffTag::CellWidth(entry.try_into()?)),
+            265 => Ok(TiffTag::CellLength(entry.try_into()?)),
             266 => Ok(TiffTag::FillOrder(entry.try_into()?)),
+            269 => Ok(TiffTag::DocumentName(entry.try_into()?)),
             270 => Ok(TiffTag::ImageDescription(entry.try_into()?)),
             271 => Ok(TiffTag::Make(entry.try_into()?)),
             272 => Ok(TiffTag::Model(entry.try_into()?)),
-            273 => Ok(TiffTag::StripOffsets(get_ushort_or_ulong_from_entry(entry)?)),
+            273 => Ok(TiffTag::StripOffsets(entry.try_into()?)),
             277 => Ok(TiffTag::SamplesPerPixel(entry.try_into()?)),
             278 => Ok(TiffTag::RowsPerStrip(get_ushort_or_ulong_from_entry(entry)?)),
-            279 => Ok(TiffTag::StripByteCounts(get_ushort_or_ulong_from_entry(entry)?)),
+            279 => Ok(TiffTag::StripByteCounts(entry.try_into()?)),
+            280 => Ok(TiffTag::MinSampleValue(entry.try_into()?)),
+            281 => Ok(TiffTag::MaxSampleValue(entry.try_into()?)),
             282 => Ok(TiffTag::XResolution(entry.try_into()?)),
             283 => Ok(TiffTag::YResolution(entry.try_into()?)),
             284 => Ok(TiffTag::PlanarConfiguration(entry.try_into()?)),
+            285 => Ok(TiffTag::PageName(entry.try_into()?)),
             286 => Ok(TiffTag::XPosition(entry.try_into()?)),
             287 => Ok(TiffTag::YPosition(entry.try_into()?)),
-            296 => Ok(TiffTag::ResolutionUnit(match <IFDEntry as TryInto<u16>>::try_into(entry)? {
+            296 => Ok(TiffTag::ResolutionUnit(match <&IFDEntry as TryInto<u16>>::try_into(entry)? {
                 1 => "none",
                 2 => "inch",
                 3  => "centimeter",
                 _ => "invalid",
             }.to_string())),
             274 => Ok(TiffTag::Orientation(entry.try_into()?)),
+            297 => match vec_to_array(entry.try_into()?) {
+                Ok(arr) => Ok(TiffTag::PageNumber(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
+            301 => Ok(TiffTag::TransferFunction(entry.try_into()?)),
             305 => Ok(TiffTag::Software(entry.try_into()?)),
             306 => Ok(TiffTag::DateTime(entry.try_into()?)),
             315 => Ok(TiffTag::Artist(entry.try_into()?)),
             316 => Ok(TiffTag::HostComputer(entry.try_into()?)),
+            317 => Ok(TiffTag::Predictor(entry.try_into()?)),
+            318 => match vec_to_array(entry.try_into()?) {
+                Ok(arr) => Ok(TiffTag::WhitePoint(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
+            319 => match vec_to_array(entry.try_into()?) {
+                Ok(arr) => Ok(TiffTag::PrimaryChromaticities(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
+            320 => Ok(TiffTag::ColorMap(entry.try_into()?)),
+            322 => Ok(TiffTag::TileWidth(get_ushort_or_ulong_from_entry(entry)?)),
+            323 => Ok(TiffTag::TileLength(get_ushort_or_ulong_from_entry(entry)?)),
+            324 => Ok(TiffTag::TileOffsets(entry.try_into()?)),
+            325 => Ok(TiffTag::TileByteCounts(entry.try_into()?)),
+            330 => Ok(TiffTag::SubIFDs(entry.try_into()?)),
+            338 => Ok(TiffTag::ExtraSamples(entry.try_into()?)),
+            339 => Ok(TiffTag::SampleFormat(entry.try_into()?)),
+            513 => Ok(TiffTag::JPEGInterchangeFormat(entry.try_into()?)),
+            514 => Ok(TiffTag::JPEGInterchangeFormatLength(entry.try_into()?)),
+            529 => match vec_to_array(entry.try_into()?) {
+                Ok(arr) => Ok(TiffTag::YCbCrCoefficients(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
+            530 => match vec_to_array(entry.try_into()?) {
+                Ok(arr) => Ok(TiffTag::YCbCrSubSampling(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
+            531 => Ok(TiffTag::YCbCrPositioning(match <&IFDEntry as TryInto<u16>>::try_into(entry)? {
+                1 => "Centered",
+                2 => "Co-sited",
+                _ => "Invalid",
+            }.to_string())),
+            532 => match vec_to_array(entry.try_into()?) {
+                Ok(arr) => Ok(TiffTag::ReferenceBlackWhite(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
 
             33432 => Ok(TiffTag::Copyright(entry.try_into()?)),
             33434 => Ok(TiffTag::ExposureTime(get_rational_repr_from_ifd_entry(entry)?)),
             33437 => Ok(TiffTag::FNumber(get_rational_repr_from_ifd_entry(entry)?)),
+            33550 => Ok(TiffTag::ModelPixelScale(entry.try_into()?)),
+            33723 => Ok(TiffTag::IptcNaa(entry.try_into()?)),
+            33922 => Ok(TiffTag::ModelTiepoint(entry.try_into()?)),
+            34264 => match vec_to_array(entry.try_into()?) {
+                Ok(arr) => Ok(TiffTag::ModelTransformation(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
+            34377 => Ok(TiffTag::Photoshop(entry.try_into()?)),
             34665 => Ok(TiffTag::ExifIfdPointer(entry.try_into()?)),
-            34850 => Ok(TiffTag::ExposureProgram(match <IFDEntry as TryInto<u16>>::try_into(entry)? {
+            34675 => Ok(TiffTag::InterColorProfile(entry.try_into()?)),
+            34735 => Ok(TiffTag::GeoKeyDirectory(entry.try_into()?)),
+            34736 => Ok(TiffTag::GeoDoubleParams(entry.try_into()?)),
+            34737 => Ok(TiffTag::GeoAsciiParams(
+                <&IFDEntry as TryInto<Vec<u8>>>::try_into(entry)?.into_iter().map(|b| b as char).collect(),
+            )),
+            34850 => Ok(TiffTag::ExposureProgram(match <&IFDEntry as TryInto<u16>>::try_into(entry)? {
                 0 => "Not defined",
                 1 => "Manual",
                 2 => "Normal program",
@@ -427,16 +973,56 @@ impl TryFrom<IFDEntry> for TiffTag {
                 8 => "Landscape mode (for landscape photos with the background in focus)",
                 _ => "Invalid",
             }.to_string())),
+            34852 => Ok(TiffTag::SpectralSensitivity(entry.try_into()?)),
             34853 => Ok(TiffTag::GpsIfdPointer(entry.try_into()?)),
+            34855 => Ok(TiffTag::PhotographicSensitivity(entry.try_into()?)),
+            34856 => Ok(TiffTag::Oecf(entry.try_into()?)),
+            34864 => Ok(TiffTag::SensitivityType(match <&IFDEntry as TryInto<u16>>::try_into(entry)? {
+                0 => "Unknown",
+                1 => "Standard output sensitivity (SOS)",
+                2 => "Recommended exposure index (REI)",
+                3 => "ISO speed",
+                4 => "Standard output sensitivity (SOS) and recommended exposure index (REI)",
+                5 => "Standard output sensitivity (SOS) and ISO speed",
+                6 => "Recommended exposure index (REI) and ISO speed",
+                7 => "Standard output sensitivity (SOS) and recommended exposure index (REI) and ISO speed",
+                _ => "Invalid",
+            }.to_string())),
+            34865 => Ok(TiffTag::StandardOutputSensitivity(entry.try_into()?)),
+            34866 => Ok(TiffTag::RecommendedExposureIndex(entry.try_into()?)),
+            34867 => Ok(TiffTag::ISOSpeed(entry.try_into()?)),
+            34868 => Ok(TiffTag::ISOSpeedLatitudeyyy(entry.try_into()?)),
+            34869 => Ok(TiffTag::ISOSpeedLatitudezzz(entry.try_into()?)),
             36864 => Ok(TiffTag::ExifVersion(get_string_from_entry_with_undefined_values(entry)?)),
             36867 => Ok(TiffTag::DateTimeOriginal(entry.try_into()?)),
             36868 => Ok(TiffTag::DateTimeDigitized(entry.try_into()?)),
+            36880 => Ok(TiffTag::OffsetTime(entry.try_into()?)),
+            36881 => Ok(TiffTag::OffsetTimeOriginal(entry.try_into()?)),
+            36882 => Ok(TiffTag::OffsetTimeDigitized(entry.try_into()?)),
+            37121 => {
+                let data: Vec<u8> = entry.try_into()?;
+
+                let components: Vec<&str> = data.iter().map(|c| match c {
+                    0 => "-",
+                    1 => "Y",
+                    2 => "Cb",
+                    3 => "Cr",
+                    4 => "R",
+                    5 => "G",
+                    6 => "B",
+                    _ => "?",
+                }).collect();
+
+                Ok(TiffTag::ComponentsConfiguration(components.join(", ")))
+            },
             37122 => Ok(TiffTag::CompressedBitsPerPixel(get_rational_repr_from_ifd_entry(entry)?)),
             37377 => Ok(TiffTag::ShutterSpeedValue(get_rational_repr_from_ifd_entry(entry)?)),
             37378 => Ok(TiffTag::ApertureValue(get_rational_repr_from_ifd_entry(entry)?)),
+            37379 => Ok(TiffTag::BrightnessValue(get_rational_repr_from_ifd_entry(entry)?)),
             37380 => Ok(TiffTag::ExposureBiasValue(get_rational_repr_from_ifd_entry(entry)?)),
             37381 => Ok(TiffTag::MaxApertureValue(get_rational_repr_from_ifd_entry(entry)?)),
-            37383 => Ok(TiffTag::MeteringMode(match <IFDEntry as TryInto<u16>>::try_into(entry)? {
+            37382 => Ok(TiffTag::SubjectDistance(get_rational_repr_from_ifd_entry(entry)?)),
+            37383 => Ok(TiffTag::MeteringMode(match <&IFDEntry as TryInto<u16>>::try_into(entry)? {
                 0 => "Unknown",
                 1 => "Average",
                 2 => "CenterWeightedAverage",
@@ -447,31 +1033,8 @@ impl TryFrom<IFDEntry> for TiffTag {
                 255 => "Other",
                 _ => "Invalid",
             }.to_string())),
-            37384 => Ok(TiffTag::LightSource(match <IFDEntry as TryInto<u16>>::try_into(entry)? {
-                0 => "Unknown",
-                1 => "Daylight",
-                2 => "Fluorescent",
-                3 => "Tungsten (incandescent light)",
-                4 => "Flash",
-                9 => "Fine weather",
-                10 => "Cloudy weather",
-                11 => "Shade",
-                12 => "Daylight fluorescent (D 5700 - 7100K)",
-                13 => "Day white fluorescent (N 4600 - 5400K)",
-                14 => "Cool white fluorescent (W 3900 - 4500K)",
-                15 => "White fluorescent (WW 3200 - 3700K)",
-                17 => "Standard light A",
-                18 => "Standard light B",
-                19 => "Standard light C",
-                20 => "D55",
-                21 => "D65",
-                22 => "D75",
-                23 => "D50",
-                24 => "ISO studio tungsten",
-                255 => "Other light source",
-                _ => "Invalid",
-            }.to_string())),
-            37385 => Ok(TiffTag::Flash(match <IFDEntry as TryInto<u16>>::try_into(entry)? {
+            37384 => Ok(TiffTag::LightSource(get_light_source_name(entry.try_into()?).to_string())),
+            37385 => Ok(TiffTag::Flash(match <&IFDEntry as TryInto<u16>>::try_into(entry)? {
                 0x0000 => "Flash did not fire",
                 0x0001 => "Flash fired",
                 0x0005 => "Strobe return light not detected",
@@ -497,43 +1060,45 @@ impl TryFrom<IFDEntry> for TiffTag {
                 _ => "Invalid",
             }.to_string())),
             37386 => Ok(TiffTag::FocalLength(get_rational_repr_from_ifd_entry(entry)?)),
+            37396 => Ok(TiffTag::SubjectArea(entry.try_into()?)),
             37500 => Ok(TiffTag::MakerNote(entry.try_into()?)),
-            37510 => {
-                let data: Vec<u8> = entry.try_into()?;
-
-                let encoding = match vec_to_array(data[0..8].to_vec()) {
-                    Ok(arr) => Ok(match &arr {
-                        b"ASCII\0\0\0" => "ascii",
-                        b"JIS\0\0\0\0\0" => "jis",
-                        b"UNICODE\0" => "unicode",
-                        _ => "unknown",
-                    }),
-                    Err(message) => Err(TiffError(message)),
-                }?;
-                
-                // TODO: Properly decode JIS/Unicode?
-                let string_value = match encoding {
-                    "ascii" | "unknown" | "jis" | "unicode" | &_ => String::from_utf8_lossy(&data[8..data.len()-1]),
-                };
-
-                Ok(TiffTag::UserComment(string_value.to_string()))
-            },
+            37510 => Ok(TiffTag::UserComment(get_string_from_entry_with_character_code(entry)?)),
             37520 => Ok(TiffTag::SubsecTime(entry.try_into()?)),
             37521 => Ok(TiffTag::SubsecTimeOriginal(entry.try_into()?)),
             37522 => Ok(TiffTag::SubsecTimeDigitized(entry.try_into()?)),
+            37888 => Ok(TiffTag::Temperature(entry.try_into()?)),
+            37889 => Ok(TiffTag::Humidity(entry.try_into()?)),
+            37890 => Ok(TiffTag::Pressure(entry.try_into()?)),
+            37891 => Ok(TiffTag::WaterDepth(entry.try_into()?)),
+            37892 => Ok(TiffTag::Acceleration(entry.try_into()?)),
+            37893 => Ok(TiffTag::CameraElevationAngle(entry.try_into()?)),
 
             40960 => Ok(TiffTag::FlashpixVersion(get_string_from_entry_with_undefined_values(entry)?)),
+            40961 => Ok(TiffTag::ColorSpace(match <&IFDEntry as TryInto<u16>>::try_into(entry)? {
+                1 => "sRGB",
+                0xFFFF => "Uncalibrated",
+                _ => "Invalid",
+            }.to_string())),
             40962 => Ok(TiffTag::PixelXDimension(get_ushort_or_ulong_from_entry(entry)?)),
             40963 => Ok(TiffTag::PixelYDimension(get_ushort_or_ulong_from_entry(entry)?)),
+            40964 => Ok(TiffTag::RelatedSoundFile(entry.try_into()?)),
+            40965 => Ok(TiffTag::InteropIfdPointer(entry.try_into()?)),
+            41483 => Ok(TiffTag::FlashEnergy(entry.try_into()?)),
+            41484 => Ok(TiffTag::SpatialFrequencyResponse(entry.try_into()?)),
             41486 => Ok(TiffTag::FocalPlaneXResolution(get_rational_repr_from_ifd_entry(entry)?)),
             41487 => Ok(TiffTag::FocalPlaneYResolution(get_rational_repr_from_ifd_entry(entry)?)),
-            41488 => Ok(TiffTag::FocalPlaneResolutionUnit(match <IFDEntry as TryInto<u16>>::try_into(entry)? {
+            41488 => Ok(TiffTag::FocalPlaneResolutionUnit(match <&IFDEntry as TryInto<u16>>::try_into(entry)? {
                 1 => "none",
                 2 => "inch",
                 3 => "centimeter",
                 _ => "invalid",
             }.to_string())),
-            41495 => Ok(TiffTag::SensingMethod(match <IFDEntry as TryInto<u16>>::try_into(entry)? {
+            41492 => match vec_to_array(entry.try_into()?) {
+                Ok(arr) => Ok(TiffTag::SubjectLocation(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
+            41493 => Ok(TiffTag::ExposureIndex(entry.try_into()?)),
+            41495 => Ok(TiffTag::SensingMethod(match <&IFDEntry as TryInto<u16>>::try_into(entry)? {
                 1 => "Not defined",
                 2 => "One-chip color area sensor",
                 3 => "Two-chip color area sensor",
@@ -543,27 +1108,44 @@ impl TryFrom<IFDEntry> for TiffTag {
                 8 => "Color sequential linear sensor",
                 _ => "Invalid",
             }.to_string())),
-            41986 => Ok(TiffTag::ExposureMode(match <IFDEntry as TryInto<u16>>::try_into(entry)? {
+            41728 => Ok(TiffTag::FileSource(match get_single_undefined_value_from_entry(entry)? {
+                0 => "Others",
+                1 => "Scanner of transparent type",
+                2 => "Scanner of reflex type",
+                3 => "Digital still camera",
+                _ => "Invalid",
+            }.to_string())),
+            41729 => Ok(TiffTag::SceneType(match get_single_undefined_value_from_entry(entry)? {
+                1 => "Directly photographed image",
+                _ => "Invalid",
+            }.to_string())),
+            41730 => Ok(TiffTag::CFAPattern(entry.try_into()?)),
+            41985 => Ok(TiffTag::CustomRendered(match <&IFDEntry as TryInto<u16>>::try_into(entry)? {
+                0 => "Normal process",
+                1 => "Custom process",
+                _ => "Invalid",
+            }.to_string())),
+            41986 => Ok(TiffTag::ExposureMode(match <&IFDEntry as TryInto<u16>>::try_into(entry)? {
                 0 => "Auto exposure",
                 1 => "Manual exposure",
                 2 => "Auto bracket",
                 _ => "Invalid",
             }.to_string())),
-            41987 => Ok(TiffTag::WhiteBalance(match <IFDEntry as TryInto<u16>>::try_into(entry)? {
+            41987 => Ok(TiffTag::WhiteBalance(match <&IFDEntry as TryInto<u16>>::try_into(entry)? {
                 0 => "Auto white balance",
                 1 => "Manual white balance",
                 _ => "Invalid",
             }.to_string())),
             41988 => Ok(TiffTag::DigitalZoomRatio(get_rational_repr_from_ifd_entry(entry)?)),
             41989 => Ok(TiffTag::FocalLengthIn35mmFilm(entry.try_into()?)),
-            41990 => Ok(TiffTag::SceneCaptureType(match <IFDEntry as TryInto<u16>>::try_into(entry)? {
+            41990 => Ok(TiffTag::SceneCaptureType(match <&IFDEntry as TryInto<u16>>::try_into(entry)? {
                 0 => "Standard",
                 1 => "Landscape",
                 2 => "Portrait",
                 3 => "Night scene",
                 _ => "Invalid",
             }.to_string())),
-            41991 => Ok(TiffTag::GainControl(match <IFDEntry as TryInto<u16>>::try_into(entry)? {
+            41991 => Ok(TiffTag::GainControl(match <&IFDEntry as TryInto<u16>>::try_into(entry)? {
                 0 => "None",
                 1 => "Low gain up",
                 2 => "High gain up",
@@ -571,45 +1153,320 @@ impl TryFrom<IFDEntry> for TiffTag {
                 4 => "High gain down",
                 _ => "Invalid",
             }.to_string())),
-            41992 => Ok(TiffTag::Contrast(match <IFDEntry as TryInto<u16>>::try_into(entry)? {
+            41992 => Ok(TiffTag::Contrast(match <&IFDEntry as TryInto<u16>>::try_into(entry)? {
                 0 => "Normal",
                 1 => "Soft",
                 2 => "Hard",
                 _ => "Invalid",
             }.to_string())),
-            41993 => Ok(TiffTag::Saturation(match <IFDEntry as TryInto<u16>>::try_into(entry)? {
+            41993 => Ok(TiffTag::Saturation(match <&IFDEntry as TryInto<u16>>::try_into(entry)? {
                 0 => "Normal",
                 1 => "Low saturation",
                 2 => "High saturation",
                 _ => "Invalid",
             }.to_string())),
-            41994 => Ok(TiffTag::Sharpness(match <IFDEntry as TryInto<u16>>::try_into(entry)? {
+            41994 => Ok(TiffTag::Sharpness(match <&IFDEntry as TryInto<u16>>::try_into(entry)? {
                 0 => "Normal",
                 1 => "Soft",
                 2 => "Hard",
                 _ => "Invalid",
             }.to_string())),
-            41996 => Ok(TiffTag::SubjectDistanceRange(match <IFDEntry as TryInto<u16>>::try_into(entry)? {
+            41995 => Ok(TiffTag::DeviceSettingDescription(entry.try_into()?)),
+            41996 => Ok(TiffTag::SubjectDistanceRange(match <&IFDEntry as TryInto<u16>>::try_into(entry)? {
                 0 => "Unknown",
                 1 => "Macro",
                 2 => "Close view",
                 3 => "Distant view",
                 _ => "Invalid",
             }.to_string())),
+            42016 => Ok(TiffTag::ImageUniqueID(entry.try_into()?)),
+            42032 => Ok(TiffTag::CameraOwnerName(entry.try_into()?)),
+            42033 => Ok(TiffTag::BodySerialNumber(entry.try_into()?)),
+            42034 => match vec_to_array(entry.try_into()?) {
+                Ok(arr) => Ok(TiffTag::LensSpecification(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
             42035 => Ok(TiffTag::LensMake(entry.try_into()?)),
             42036 => Ok(TiffTag::LensModel(entry.try_into()?)),
+            42037 => Ok(TiffTag::LensSerialNumber(entry.try_into()?)),
+            42038 => Ok(TiffTag::ImageTitle(entry.try_into()?)),
+            42039 => Ok(TiffTag::Photographer(entry.try_into()?)),
+            42040 => Ok(TiffTag::ImageEditor(entry.try_into()?)),
+            42041 => Ok(TiffTag::CameraFirmware(entry.try_into()?)),
+            42042 => Ok(TiffTag::RAWDevelopingSoftware(entry.try_into()?)),
+            42043 => Ok(TiffTag::ImageEditingSoftware(entry.try_into()?)),
+            42044 => Ok(TiffTag::MetadataEditingSoftware(entry.try_into()?)),
+            42080 => Ok(TiffTag::CompositeImage(match <&IFDEntry as TryInto<u16>>::try_into(entry)? {
+                0 => "Unknown",
+                1 => "Not a composite image",
+                2 => "General composite image",
+                3 => "Composite image captured while shooting",
+                _ => "Invalid",
+            }.to_string())),
+            42081 => match vec_to_array(entry.try_into()?) {
+                Ok(arr) => Ok(TiffTag::SourceImageNumberOfCompositeImage(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
+            42082 => Ok(TiffTag::SourceExposureTimesOfCompositeImage(entry.try_into()?)),
+            42240 => Ok(TiffTag::Gamma(entry.try_into()?)),
+
+            50706 => match vec_to_array(entry.try_into()?) {
+                Ok(arr) => Ok(TiffTag::DNGVersion(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
+            50707 => match vec_to_array(entry.try_into()?) {
+                Ok(arr) => Ok(TiffTag::DNGBackwardVersion(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
+            50708 => Ok(TiffTag::UniqueCameraModel(entry.try_into()?)),
+            50709 => Ok(TiffTag::LocalizedCameraModel(get_string_from_bytes_or_ascii(entry)?)),
+            50710 => Ok(TiffTag::CFAPlaneColor(entry.try_into()?)),
+            50711 => Ok(TiffTag::CFALayout(match <&IFDEntry as TryInto<u16>>::try_into(entry)? {
+                1 => "Rectangular (or square) layout",
+                2 => "Staggered layout A: even columns are offset down by 1/2 row",
+                3 => "Staggered layout B: even columns are offset up by 1/2 row",
+                4 => "Staggered layout C: even rows are offset right by 1/2 column",
+                5 => "Staggered layout D: even rows are offset left by 1/2 column",
+                6 => "Staggered layout E: even rows are offset up by 1/2 row, even columns are offset left by 1/2 column",
+                7 => "Staggered layout F: even rows are offset up by 1/2 row, even columns are offset right by 1/2 column",
+                8 => "Staggered layout G: even rows are offset down by 1/2 row, even columns are offset left by 1/2 column",
+                9 => "Staggered layout H: even rows are offset down by 1/2 row, even columns are offset right by 1/2 column",
+                _ => "Invalid",
+            }.to_string())),
+            50712 => Ok(TiffTag::LinearizationTable(entry.try_into()?)),
+            50713 => match vec_to_array(entry.try_into()?) {
+                Ok(arr) => Ok(TiffTag::BlackLevelRepeatDim(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
+            50714 => Ok(TiffTag::BlackLevel(get_numbers_from_entry(entry)?)),
+            50715 => Ok(TiffTag::BlackLevelDeltaH(get_numbers_from_entry(entry)?)),
+            50716 => Ok(TiffTag::BlackLevelDeltaV(get_numbers_from_entry(entry)?)),
+            50717 => Ok(TiffTag::WhiteLevel(entry.try_into()?)),
+            50718 => match vec_to_array(get_numbers_from_entry(entry)?) {
+                Ok(arr) => Ok(TiffTag::DefaultScale(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
+            50719 => match vec_to_array(get_numbers_from_entry(entry)?) {
+                Ok(arr) => Ok(TiffTag::DefaultCropOrigin(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
+            50720 => match vec_to_array(get_numbers_from_entry(entry)?) {
+                Ok(arr) => Ok(TiffTag::DefaultCropSize(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
+            50721 => Ok(TiffTag::ColorMatrix1(get_matrix_from_entry(entry, MatrixShape::ThreeColumns)?)),
+            50722 => Ok(TiffTag::ColorMatrix2(get_matrix_from_entry(entry, MatrixShape::ThreeColumns)?)),
+            50723 => Ok(TiffTag::CameraCalibration1(get_matrix_from_entry(entry, MatrixShape::Square)?)),
+            50724 => Ok(TiffTag::CameraCalibration2(get_matrix_from_entry(entry, MatrixShape::Square)?)),
+            50725 => Ok(TiffTag::ReductionMatrix1(get_matrix_from_entry(entry, MatrixShape::ThreeRows)?)),
+            50726 => Ok(TiffTag::ReductionMatrix2(get_matrix_from_entry(entry, MatrixShape::ThreeRows)?)),
+            50727 => Ok(TiffTag::AnalogBalance(entry.try_into()?)),
+            50728 => Ok(TiffTag::AsShotNeutral(get_numbers_from_entry(entry)?)),
+            50729 => match vec_to_array(entry.try_into()?) {
+                Ok(arr) => Ok(TiffTag::AsShotWhiteXY(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
+            50730 => Ok(TiffTag::BaselineExposure(entry.try_into()?)),
+            50731 => Ok(TiffTag::BaselineNoise(entry.try_into()?)),
+            50732 => Ok(TiffTag::BaselineSharpness(entry.try_into()?)),
+            50733 => Ok(TiffTag::BayerGreenSplit(entry.try_into()?)),
+            50734 => Ok(TiffTag::LinearResponseLimit(entry.try_into()?)),
+            50735 => Ok(TiffTag::CameraSerialNumber(entry.try_into()?)),
+            50736 => match vec_to_array(entry.try_into()?) {
+                Ok(arr) => Ok(TiffTag::LensInfo(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
+            50737 => Ok(TiffTag::ChromaBlurRadius(entry.try_into()?)),
+            50738 => Ok(TiffTag::AntiAliasStrength(entry.try_into()?)),
+            50739 => Ok(TiffTag::ShadowScale(entry.try_into()?)),
+            50740 => Ok(TiffTag::DNGPrivateData(entry.try_into()?)),
+            50741 => Ok(TiffTag::MakerNoteSafety(match <&IFDEntry as TryInto<u16>>::try_into(entry)? {
+                0 => "Unsafe",
+                1 => "Safe",
+                _ => "Invalid",
+            }.to_string())),
+            50778 => Ok(TiffTag::CalibrationIlluminant1(get_light_source_name(entry.try_into()?).to_string())),
+            50779 => Ok(TiffTag::CalibrationIlluminant2(get_light_source_name(entry.try_into()?).to_string())),
+            50780 => Ok(TiffTag::BestQualityScale(entry.try_into()?)),
+            50781 => match vec_to_array(entry.try_into()?) {
+                Ok(arr) => Ok(TiffTag::RawDataUniqueID(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
+            50827 => Ok(TiffTag::OriginalRawFileName(get_string_from_bytes_or_ascii(entry)?)),
+            50828 => Ok(TiffTag::OriginalRawFileData(entry.try_into()?)),
+            50829 => match vec_to_array(entry.try_into()?) {
+                Ok(arr) => Ok(TiffTag::ActiveArea(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
+            50830 => {
+                let values: Vec<u32> = entry.try_into()?;
+                match values.len() % 4 {
+                    0 => Ok(TiffTag::MaskedAreas(
+                        values.chunks_exact(4).map(|c| [c[0], c[1], c[2], c[3]]).collect(),
+                    )),
+                    _ => Err(TiffError(format!(
+                        "Expected a multiple of 4 values for MaskedAreas (got {})",
+                        values.len()
+                    ))),
+                }
+            }
+            50831 => Ok(TiffTag::AsShotICCProfile(entry.try_into()?)),
+            50832 => Ok(TiffTag::AsShotPreProfileMatrix(entry.try_into()?)),
+            50833 => Ok(TiffTag::CurrentICCProfile(entry.try_into()?)),
+            50834 => Ok(TiffTag::CurrentPreProfileMatrix(entry.try_into()?)),
+            50879 => Ok(TiffTag::ColorimetricReference(match <&IFDEntry as TryInto<u16>>::try_into(entry)? {
+                0 => "Scene-referred",
+                1 => "Output-referred (ICC profile dynamic range)",
+                2 => "Output-referred (high dynamic range)",
+                _ => "Invalid",
+            }.to_string())),
+            50931 => Ok(TiffTag::CameraCalibrationSignature(get_string_from_bytes_or_ascii(entry)?)),
+            50932 => Ok(TiffTag::ProfileCalibrationSignature(get_string_from_bytes_or_ascii(entry)?)),
+            50933 => Ok(TiffTag::ExtraCameraProfiles(entry.try_into()?)),
+            50934 => Ok(TiffTag::AsShotProfileName(get_string_from_bytes_or_ascii(entry)?)),
+            50935 => Ok(TiffTag::NoiseReductionApplied(entry.try_into()?)),
+            50936 => Ok(TiffTag::ProfileName(get_string_from_bytes_or_ascii(entry)?)),
+            50937 => match vec_to_array(entry.try_into()?) {
+                Ok(arr) => Ok(TiffTag::ProfileHueSatMapDims(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
+            50938 => Ok(TiffTag::ProfileHueSatMapData1(get_numbers_from_entry(entry)?)),
+            50939 => Ok(TiffTag::ProfileHueSatMapData2(get_numbers_from_entry(entry)?)),
+            50940 => Ok(TiffTag::ProfileToneCurve(get_numbers_from_entry(entry)?)),
+            50941 => Ok(TiffTag::ProfileEmbedPolicy(match <&IFDEntry as TryInto<u32>>::try_into(entry)? {
+                0 => "Allow copying",
+                1 => "Embed if used",
+                2 => "Never embed",
+                3 => "No restrictions",
+                _ => "Invalid",
+            }.to_string())),
+            50942 => Ok(TiffTag::ProfileCopyright(get_string_from_bytes_or_ascii(entry)?)),
+            50964 => Ok(TiffTag::ForwardMatrix1(get_matrix_from_entry(entry, MatrixShape::ThreeRows)?)),
+            50965 => Ok(TiffTag::ForwardMatrix2(get_matrix_from_entry(entry, MatrixShape::ThreeRows)?)),
+            50966 => Ok(TiffTag::PreviewApplicationName(get_string_from_bytes_or_ascii(entry)?)),
+            50967 => Ok(TiffTag::PreviewApplicationVersion(get_string_from_bytes_or_ascii(entry)?)),
+            50968 => Ok(TiffTag::PreviewSettingsName(get_string_from_bytes_or_ascii(entry)?)),
+            50969 => match vec_to_array(entry.try_into()?) {
+                Ok(arr) => Ok(TiffTag::PreviewSettingsDigest(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
+            50970 => Ok(TiffTag::PreviewColorSpace(match <&IFDEntry as TryInto<u32>>::try_into(entry)? {
+                0 => "Unknown",
+                1 => "Gray Gamma 2.2",
+                2 => "sRGB",
+                3 => "Adobe RGB",
+                4 => "ProPhoto RGB",
+                _ => "Invalid",
+            }.to_string())),
+            50971 => Ok(TiffTag::PreviewDateTime(entry.try_into()?)),
+            50972 => match vec_to_array(entry.try_into()?) {
+                Ok(arr) => Ok(TiffTag::RawImageDigest(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
+            50973 => match vec_to_array(entry.try_into()?) {
+                Ok(arr) => Ok(TiffTag::OriginalRawFileDigest(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
+            50974 => match vec_to_array(entry.try_into()?) {
+                Ok(arr) => Ok(TiffTag::SubTileBlockSize(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
+            50975 => Ok(TiffTag::RowInterleaveFactor(get_ushort_or_ulong_from_entry(entry)?)),
+            50981 => match vec_to_array(entry.try_into()?) {
+                Ok(arr) => Ok(TiffTag::ProfileLookTableDims(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
+            50982 => Ok(TiffTag::ProfileLookTableData(get_numbers_from_entry(entry)?)),
+            51008 => Ok(TiffTag::OpcodeList1(entry.try_into()?)),
+            51009 => Ok(TiffTag::OpcodeList2(entry.try_into()?)),
+            51022 => Ok(TiffTag::OpcodeList3(entry.try_into()?)),
+            51041 => Ok(TiffTag::NoiseProfile(entry.try_into()?)),
+            51089 => match vec_to_array(entry.try_into()?) {
+                Ok(arr) => Ok(TiffTag::OriginalDefaultFinalSize(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
+            51090 => match vec_to_array(entry.try_into()?) {
+                Ok(arr) => Ok(TiffTag::OriginalBestQualityFinalSize(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
+            51091 => match vec_to_array(get_numbers_from_entry(entry)?) {
+                Ok(arr) => Ok(TiffTag::OriginalDefaultCropSize(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
+            51107 => Ok(TiffTag::ProfileHueSatMapEncoding(match <&IFDEntry as TryInto<u32>>::try_into(entry)? {
+                0 => "Linear",
+                1 => "sRGB",
+                _ => "Invalid",
+            }.to_string())),
+            51108 => Ok(TiffTag::ProfileLookTableEncoding(match <&IFDEntry as TryInto<u32>>::try_into(entry)? {
+                0 => "Linear",
+                1 => "sRGB",
+                _ => "Invalid",
+            }.to_string())),
+            51109 => Ok(TiffTag::BaselineExposureOffset(entry.try_into()?)),
+            51110 => Ok(TiffTag::DefaultBlackRender(match <&IFDEntry as TryInto<u32>>::try_into(entry)? {
+                0 => "Auto",
+                1 => "None",
+                _ => "Invalid",
+            }.to_string())),
+            51111 => match vec_to_array(entry.try_into()?) {
+                Ok(arr) => Ok(TiffTag::NewRawImageDigest(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
+            51112 => Ok(TiffTag::RawToPreviewGain(entry.try_into()?)),
+            51125 => match vec_to_array(entry.try_into()?) {
+                Ok(arr) => Ok(TiffTag::DefaultUserCrop(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
+            51177 => Ok(TiffTag::DepthFormat(match <&IFDEntry as TryInto<u16>>::try_into(entry)? {
+                0 => "Unknown",
+                1 => "Linear",
+                2 => "Inverse",
+                _ => "Invalid",
+            }.to_string())),
+            51178 => Ok(TiffTag::DepthNear(entry.try_into()?)),
+            51179 => Ok(TiffTag::DepthFar(entry.try_into()?)),
+            51180 => Ok(TiffTag::DepthUnits(match <&IFDEntry as TryInto<u16>>::try_into(entry)? {
+                0 => "Unknown",
+                1 => "Meters",
+                _ => "Invalid",
+            }.to_string())),
+            51181 => Ok(TiffTag::DepthMeasureType(match <&IFDEntry as TryInto<u16>>::try_into(entry)? {
+                0 => "Unknown",
+                1 => "Optical axis",
+                2 => "Optical ray",
+                _ => "Invalid",
+            }.to_string())),
+            51182 => Ok(TiffTag::EnhanceParams(entry.try_into()?)),
+            52525 => Ok(TiffTag::ProfileGainTableMap(entry.try_into()?)),
+            52526 => Ok(TiffTag::SemanticName(entry.try_into()?)),
+            52528 => Ok(TiffTag::SemanticInstanceID(entry.try_into()?)),
+            52529 => Ok(TiffTag::CalibrationIlluminant3(get_light_source_name(entry.try_into()?).to_string())),
+            52530 => Ok(TiffTag::CameraCalibration3(get_matrix_from_entry(entry, MatrixShape::Square)?)),
+            52531 => Ok(TiffTag::ColorMatrix3(get_matrix_from_entry(entry, MatrixShape::ThreeColumns)?)),
+            52532 => Ok(TiffTag::ForwardMatrix3(get_matrix_from_entry(entry, MatrixShape::ThreeRows)?)),
+            52533 => Ok(TiffTag::IlluminantData1(entry.try_into()?)),
+            52534 => Ok(TiffTag::IlluminantData2(entry.try_into()?)),
+            52535 => Ok(TiffTag::IlluminantData3(entry.try_into()?)),
+            52536 => match vec_to_array(entry.try_into()?) {
+                Ok(arr) => Ok(TiffTag::MaskSubArea(arr)),
+                Err(message) => Err(TiffError(message)),
+            },
+            52537 => Ok(TiffTag::ProfileHueSatMapData3(get_numbers_from_entry(entry)?)),
+            52538 => Ok(TiffTag::ReductionMatrix3(get_matrix_from_entry(entry, MatrixShape::ThreeRows)?)),
+            52543 => Ok(TiffTag::RGBTables(entry.try_into()?)),
 
-            _ => Ok(TiffTag::Unknown(entry)),
+            _ => Ok(TiffTag::Unknown(entry.clone())),
         }
     }
 }
 
-fn read_ifd_entry_values(
+fn read_ifd_entry_values<T: AsRef<[u8]>>(
     value_type: u16,
     value_type_size: usize,
     value_count: usize,
     endianness: &Endianness,
-    cursor: &mut Cursor<Vec<u8>>,
+    cursor: &mut Cursor<T>,
 ) -> Result<Vec<IFDEntryValue>, TiffError> {
     let mut values: Vec<IFDEntryValue> = vec![];
 
@@ -621,7 +1478,8 @@ fn read_ifd_entry_values(
             1 => IFDEntryValue::BYTE(buf[0]),
             2 => IFDEntryValue::ASCII(buf[0]),
             3 => IFDEntryValue::SHORT(unpack!(vec_to_array(buf)?, u16, endianness)),
-            4 => IFDEntryValue::LONG(unpack!(vec_to_array(buf)?, u32, endianness)),
+            // IFD (13) is an offset to another IFD, it's otherwise identical to LONG
+            4 | 13 => IFDEntryValue::LONG(unpack!(vec_to_array(buf)?, u32, endianness)),
             5 => {
                 let data = (
                     [buf[0], buf[1], buf[2], buf[3]],
@@ -644,6 +1502,7 @@ fn read_ifd_entry_values(
             }
             11 => IFDEntryValue::FLOAT(unpack!(vec_to_array(buf)?, f32, endianness)),
             12 => IFDEntryValue::DOUBLE(unpack!(vec_to_array(buf)?, f64, endianness)),
+            129 => IFDEntryValue::UTF8(buf[0]),
             unknown => return Err(TiffError(format!("Encountered unknown TIFF value type: {}", unknown))),
         })
     }
@@ -651,7 +1510,7 @@ fn read_ifd_entry_values(
     Ok(values)
 }
 
-pub fn read_ifd_entry(cursor: &mut Cursor<Vec<u8>>, endianness: &Endianness) -> Result<IFDEntry, TiffError> {
+pub fn read_ifd_entry<T: AsRef<[u8]>>(cursor: &mut Cursor<T>, endianness: &Endianness) -> Result<IFDEntry, TiffError> {
     let tag = read_unpack!(cursor, u16, endianness);
     let value_type = read_unpack!(cursor, u16, endianness);
     let value_count = read_unpack!(cursor, u32, endianness) as usize;
@@ -686,10 +1545,58 @@ pub fn read_ifd(cursor: &mut Cursor<Vec<u8>>, endianness: &Endianness) -> Result
     Ok(entries)
 }
 
+/// The IFDs a TIFF can contain, since tag IDs are only unique within each kind of IFD
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum IfdKind {
+    Primary, // IFD0 and any following image IFDs
+    Exif,
+    Gps,
+    MakerNote(MakerNoteVendor), // The vendor IFD of a makernote
+}
+
 #[derive(Debug)]
 pub struct Tiff {
     pub tags: Vec<TiffTag>,
     pub endianness: Endianness,
+    pub(crate) thumbnail: Option<Thumbnail>,
+    pub(crate) pages: Vec<Vec<TiffTag>>,
+    pub(crate) unknown_entries: Vec<(IfdKind, IFDEntry)>, // The entries of TiffTag::Unknown tags
+    pub(crate) makernote: Option<MakerNote>,
+}
+
+impl Tiff {
+    /// The tags of each image (IFD0, any following IFDs which aren't a thumbnail and then their
+    /// SubIFDs), unlike `tags` these aren't merged so tags like ImageWidth can be read per page
+    pub fn pages(&self) -> &[Vec<TiffTag>] {
+        &self.pages
+    }
+
+    /// How the image should be transformed for display, if the Orientation tag is present and valid
+    pub fn orientation(&self) -> Option<Orientation> {
+        let value = get_tag_value!(self.tags, TiffTag::Orientation)?;
+
+        Orientation::try_from(*value).ok()
+    }
+
+    /// The embedded ICC profile (InterColorProfile tag), if there is a valid one
+    pub fn icc_profile(&self) -> Option<IccProfile> {
+        let data = get_tag_value!(self.tags, TiffTag::InterColorProfile)?;
+
+        read_icc_profile(data.clone()).ok()
+    }
+
+    /// A little-endian TIFF with a single page made of `tags`, for tests which don't need a file
+    #[cfg(test)]
+    pub(crate) fn from_tags(tags: Vec<TiffTag>) -> Tiff {
+        Tiff {
+            pages: vec![tags.clone()],
+            tags,
+            endianness: Endianness::Little,
+            thumbnail: None,
+            unknown_entries: vec![],
+            makernote: None,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -701,14 +1608,23 @@ impl From<String> for TiffError {
     }
 }
 
-fn ifd_entries_to_tiff_tags(entries: Vec<IFDEntry>) -> Result<Vec<TiffTag>, TiffError> {
-    let mut tags: Vec<TiffTag> = vec![];
-
-    for entry in entries {
-        tags.push(TiffTag::try_from(entry)?);
-    }
+/// The entries of the tags which couldn't be decoded, along with the kind of IFD they're from
+fn get_unknown_entries(tags: &[TiffTag], ifd: IfdKind) -> Vec<(IfdKind, IFDEntry)> {
+    tags.iter()
+        .filter_map(|tag| match tag {
+            TiffTag::Unknown(entry) => Some((ifd, entry.clone())),
+            _ => None,
+        })
+        .collect()
+}
 
-    Ok(tags)
+/// Decodes the entries of an IFD. An entry whose value doesn't match what its tag expects is kept
+/// as [`TiffTag::Unknown`] rather than failing the whole file.
+fn ifd_entries_to_tiff_tags(entries: Vec<IFDEntry>, ifd: IfdKind) -> Vec<TiffTag> {
+    entries
+        .into_iter()
+        .map(|entry| tiff_tag_from_entry(&entry, ifd).unwrap_or(TiffTag::Unknown(entry)))
+        .collect()
 }
 
 pub fn read_tiff_header(cursor: &mut Cursor<Vec<u8>>) -> Result<Endianness, TiffError> {
@@ -739,42 +1655,148 @@ pub fn read_tiff_header(cursor: &mut Cursor<Vec<u8>>) -> Result<Endianness, Tiff
 }
 
 pub fn read_tiff(cursor: &mut Cursor<Vec<u8>>) -> Result<Tiff, TiffError> {
-    let mut entries: Vec<IFDEntry> = Vec::new();
+    read_tiff_with_thumbnail_ifd(cursor, false)
+}
+
+/// IPTC-NAA data is a byte stream but is often written as LONGs, turn those back into the bytes
+/// they were in the file so the IptcNaa tag can decode them
+fn iptc_entry_to_bytes(entry: IFDEntry, endianness: Endianness) -> IFDEntry {
+    if entry.tag != 33723 || !entry.values.iter().all(|v| matches!(v, IFDEntryValue::LONG(_))) {
+        return entry;
+    }
+
+    let values = entry
+        .values
+        .iter()
+        .flat_map(|v| match (v, endianness) {
+            (IFDEntryValue::LONG(v), Endianness::Little) => v.to_le_bytes(),
+            (IFDEntryValue::LONG(v), Endianness::Big) => v.to_be_bytes(),
+            _ => unreachable!(),
+        })
+        .map(IFDEntryValue::UNDEFINED)
+        .collect();
+
+    IFDEntry { tag: entry.tag, values }
+}
+
+/// Reads a TIFF, treating IFD1 as a thumbnail if `ifd1_is_thumbnail` is true (as it is for EXIF
+/// data) or if IFD1 itself looks like a thumbnail. A thumbnail IFD's tags are stored in the
+/// [`Thumbnail`] instead of being merged with the rest of the tags.
+fn read_tiff_with_thumbnail_ifd(
+    cursor: &mut Cursor<Vec<u8>>,
+    ifd1_is_thumbnail: bool,
+) -> Result<Tiff, TiffError> {
+    let mut ifds: Vec<Vec<IFDEntry>> = Vec::new();
+    let mut ifd_offsets: Vec<u32> = Vec::new();
 
     let endianness = read_tiff_header(cursor)?;
 
     loop {
         let offset = read_unpack!(cursor, u32, endianness);
 
-        // Offset of zero means no more IFDs
-        if offset == 0 {
+        // Offset of zero means no more IFDs, also guard against IFDs which link back to each other
+        if offset == 0 || ifd_offsets.contains(&offset) {
             break;
         }
 
+        ifd_offsets.push(offset);
         cursor.set_position(offset as u64);
 
-        entries.extend(read_ifd(cursor, &endianness)?);
+        ifds.push(read_ifd(cursor, &endianness)?);
+    }
+
+    let mut ifds = ifds.into_iter();
+    let ifd0_entries = ifds.next().unwrap_or_default().into_iter().map(|entry| iptc_entry_to_bytes(entry, endianness)).collect();
+    let mut tags: Vec<TiffTag> = ifd_entries_to_tiff_tags(ifd0_entries, IfdKind::Primary);
+    let mut pages: Vec<Vec<TiffTag>> = vec![tags.clone()];
+
+    let mut thumbnail: Option<Thumbnail> = None;
+    for (i, entries) in ifds.enumerate() {
+        let ifd_tags = ifd_entries_to_tiff_tags(entries, IfdKind::Primary);
+
+        if i == 0 && (ifd1_is_thumbnail || is_thumbnail_ifd(&ifd_tags)) {
+            thumbnail = read_thumbnail(cursor, ifd_tags);
+        } else {
+            pages.push(ifd_tags.clone());
+            tags.extend(ifd_tags);
+        }
     }
 
-    let mut tags: Vec<TiffTag> = ifd_entries_to_tiff_tags(entries)?;
+    // SubIFDs are only added as pages, since they often describe the same image at a different
+    // resolution (or, for DNGs, the raw data) their tags would clash with the main image's
+    let sub_ifd_offsets: Vec<u32> = pages
+        .iter()
+        .filter_map(|page| get_tag_value!(page, TiffTag::SubIFDs))
+        .flatten()
+        .copied()
+        .collect();
+    for offset in sub_ifd_offsets {
+        if ifd_offsets.contains(&offset) {
+            continue;
+        }
 
-    let mut extra_found_entries: Vec<IFDEntry> = vec![];
+        ifd_offsets.push(offset);
+
+        // A broken SubIFD is skipped rather than failing the whole file
+        if let Ok(entries) = read_ifd_checked(cursor.get_ref(), offset as usize, endianness) {
+            pages.push(ifd_entries_to_tiff_tags(entries, IfdKind::Primary));
+        }
+    }
+
+    let mut exif_entries: Vec<IFDEntry> = vec![];
+    let mut gps_entries: Vec<IFDEntry> = vec![];
+    let mut makernote_offset: Option<usize> = None;
     for tag in &tags {
         match tag {
             TiffTag::ExifIfdPointer(ifd_ptr) => {
                 cursor.set_position(*ifd_ptr as u64);
-                extra_found_entries.extend(read_ifd(cursor, &endianness)?);
+                exif_entries.extend(read_ifd(cursor, &endianness)?);
+                makernote_offset = makernote_offset
+                    .or(find_value_offset(cursor.get_ref(), *ifd_ptr as usize, 37500, endianness));
             }
             TiffTag::GpsIfdPointer(ifd_ptr) => {
                 cursor.set_position(*ifd_ptr as u64);
-                extra_found_entries.extend(read_ifd(cursor, &endianness)?);
+                gps_entries.extend(read_ifd(cursor, &endianness)?);
             }
             _ => {}
         }
     }
-    tags.extend(ifd_entries_to_tiff_tags(extra_found_entries)?);
+    let exif_tags = ifd_entries_to_tiff_tags(exif_entries, IfdKind::Exif);
+    let gps_tags = ifd_entries_to_tiff_tags(gps_entries, IfdKind::Gps);
+
+    let makernote = match (get_tag_value!(exif_tags, TiffTag::MakerNote), makernote_offset) {
+        (Some(data), Some(offset)) => Some(read_makernote(
+            cursor.get_ref(),
+            offset,
+            data.clone(),
+            (
+                get_tag_value!(tags, TiffTag::Make).map(|m| m.as_str()).unwrap_or_default(),
+                get_tag_value!(tags, TiffTag::Model).map(|m| m.as_str()).unwrap_or_default(),
+            ),
+            endianness,
+        )),
+        _ => None,
+    };
+
+    let mut unknown_entries = get_unknown_entries(&tags, IfdKind::Primary);
+    unknown_entries.extend(get_unknown_entries(&exif_tags, IfdKind::Exif));
+    unknown_entries.extend(get_unknown_entries(&gps_tags, IfdKind::Gps));
+    if let Some(makernote) = &makernote {
+        let ifd = IfdKind::MakerNote(makernote.vendor);
+        unknown_entries.extend(makernote.unknown_entries().into_iter().map(|entry| (ifd, entry)));
+    }
 
-    Ok(Tiff { tags, endianness })
+    tags.extend(exif_tags);
+    tags.extend(gps_tags);
+
+    Ok(Tiff {
+        tags,
+        endianness,
+        thumbnail,
+        pages,
+        unknown_entries,
+        makernote,
+    })
 }
 
 pub fn read_exif_section(data: &[u8]) -> Result<Tiff, TiffError> {
@@ -794,7 +1816,7 @@ pub fn read_exif_section(data: &[u8]) -> Result<Tiff, TiffError> {
 
     let mut cursor = Cursor::new(data[6..].to_vec());
 
-    Ok(read_tiff(&mut cursor)?)
+    read_tiff_with_thumbnail_ifd(&mut cursor, true)
 }
 
 
@@ -807,10 +1829,17 @@ pub(crate) fn read_tiff_file(data: &[u8]) -> Result<Tiff, TiffError> {
 
 #[cfg(test)]
 mod tests {
-    use super::{read_tiff_file, TiffTag};
+    use super::{ifd_entries_to_tiff_tags, read_tiff_file, tiff_tag_from_entry, IFDEntry, IFDEntryValue, IfdKind, TiffTag};
     use crate::{get_tag_value, utils::Endianness};
     use std::fs;
 
+    fn undefined_entry(tag: u16, data: &[u8]) -> IFDEntry {
+        IFDEntry {
+            tag,
+            values: data.iter().map(|b| IFDEntryValue::UNDEFINED(*b)).collect(),
+        }
+    }
+
     #[test]
     fn test_read_tiff() {
         let data = fs::read("test_images/test.tif").unwrap();
@@ -849,5 +1878,114 @@ mod tests {
             *get_tag_value!(tiff.tags, TiffTag::ResolutionUnit).unwrap(),
             "centimeter",
         );
+
+        assert_eq!(
+            *get_tag_value!(tiff.tags, TiffTag::PageNumber).unwrap(),
+            [0, 1],
+        );
+
+        assert_eq!(
+            get_tag_value!(tiff.tags, TiffTag::WhitePoint).unwrap().len(),
+            2,
+        );
+    }
+
+    #[test]
+    fn test_gps_tags_decoded_by_ifd() {
+        let entry = IFDEntry { tag: 11, values: vec![IFDEntryValue::RATIONAL(25, 10)] };
+        assert!(matches!(tiff_tag_from_entry(&entry, IfdKind::Gps).unwrap(), TiffTag::GPSDop(v) if v == 2.5));
+
+        let entry = IFDEntry { tag: 11, values: vec![IFDEntryValue::RATIONAL(25, 10)] };
+        assert!(matches!(tiff_tag_from_entry(&entry, IfdKind::Primary).unwrap(), TiffTag::Unknown(_)));
+    }
+
+    #[test]
+    fn test_character_code_strings() {
+        let entry = undefined_entry(27, b"ASCII\0\0\0GPS\0");
+        assert!(matches!(tiff_tag_from_entry(&entry, IfdKind::Gps).unwrap(), TiffTag::GPSProcessingMethod(v) if v == "GPS"));
+
+        let entry = undefined_entry(28, b"UNICODE\0N\0Y\0C\0");
+        assert!(matches!(tiff_tag_from_entry(&entry, IfdKind::Gps).unwrap(), TiffTag::GPSAreaInformation(v) if v == "NYC"));
+
+        let entry = undefined_entry(37510, b"UNICODE\0\0h\0i");
+        assert!(matches!(tiff_tag_from_entry(&entry, IfdKind::Exif).unwrap(), TiffTag::UserComment(v) if v == "hi"));
+
+        let entry = undefined_entry(27, b"ASCII");
+        assert!(tiff_tag_from_entry(&entry, IfdKind::Gps).is_err());
+
+        let entries = vec![undefined_entry(37510, b"A"), IFDEntry { tag: 33434, values: vec![IFDEntryValue::RATIONAL(1, 100)] }];
+        let tags = ifd_entries_to_tiff_tags(entries, IfdKind::Exif);
+        assert!(matches!(&tags[0], TiffTag::Unknown(entry) if entry.tag == 37510));
+        assert!(matches!(tags[1], TiffTag::ExposureTime(_)));
+    }
+
+    #[test]
+    fn test_dng_tags() {
+        let entry = IFDEntry { tag: 50706, values: [1, 6, 0, 0].iter().map(|v| IFDEntryValue::BYTE(*v)).collect() };
+        assert!(matches!(tiff_tag_from_entry(&entry, IfdKind::Primary).unwrap(), TiffTag::DNGVersion([1, 6, 0, 0])));
+
+        let values = [10, 0, 0, 0, 20, 0, 0, 0, 30, -10, 0, 0].iter().map(|v| IFDEntryValue::SRATIONAL(*v, 10)).collect();
+        let entry = IFDEntry { tag: 50721, values };
+        assert!(matches!(
+            tiff_tag_from_entry(&entry, IfdKind::Primary).unwrap(),
+            TiffTag::ColorMatrix1(m) if m == vec![vec![1.0, 0.0, 0.0], vec![0.0, 2.0, 0.0], vec![0.0, 0.0, 3.0], vec![-1.0, 0.0, 0.0]]
+        ));
+
+        let values = (0..12).map(|v| IFDEntryValue::SRATIONAL(v, 1)).collect();
+        let entry = IFDEntry { tag: 50964, values };
+        assert!(matches!(
+            tiff_tag_from_entry(&entry, IfdKind::Primary).unwrap(),
+            TiffTag::ForwardMatrix1(m) if m.len() == 3 && m[2] == vec![8.0, 9.0, 10.0, 11.0]
+        ));
+
+        let values = (0..5).map(|v| IFDEntryValue::SRATIONAL(v, 1)).collect();
+        assert!(tiff_tag_from_entry(&IFDEntry { tag: 50723, values }, IfdKind::Primary).is_err());
+
+        let entry = IFDEntry { tag: 50778, values: vec![IFDEntryValue::SHORT(21)] };
+        assert!(matches!(tiff_tag_from_entry(&entry, IfdKind::Primary).unwrap(), TiffTag::CalibrationIlluminant1(v) if v == "D65"));
+
+        let entry = IFDEntry { tag: 50714, values: vec![IFDEntryValue::SHORT(512), IFDEntryValue::RATIONAL(1025, 2)] };
+        assert!(matches!(tiff_tag_from_entry(&entry, IfdKind::Primary).unwrap(), TiffTag::BlackLevel(v) if v == vec![512.0, 512.5]));
+
+        let entry = IFDEntry { tag: 50719, values: vec![IFDEntryValue::LONG(8), IFDEntryValue::LONG(16)] };
+        assert!(matches!(tiff_tag_from_entry(&entry, IfdKind::Primary).unwrap(), TiffTag::DefaultCropOrigin([8.0, 16.0])));
+    }
+
+    #[test]
+    fn test_iptc_written_as_longs() {
+        // A single IptcNaa entry of type LONG, whose value holds the IPTC bytes in file order
+        let little = b"II*\0\x08\0\0\0\x01\0\xBB\x83\x04\0\x01\0\0\0\x1C\x02\x05\x00\0\0\0\0";
+        let big = b"MM\0*\0\0\0\x08\0\x01\x83\xBB\0\x04\0\0\0\x01\x1C\x02\x05\x00\0\0\0\0";
+
+        for data in [&little[..], &big[..]] {
+            let tiff = read_tiff_file(data).unwrap();
+            assert_eq!(*get_tag_value!(tiff.tags, TiffTag::IptcNaa).unwrap(), [0x1C, 0x02, 0x05, 0x00]);
+        }
+    }
+
+    #[test]
+    fn test_sub_ifds() {
+        let mut data = b"II*\0\x08\0\0\0".to_vec();
+        data.extend([2, 0]);
+        data.extend([0x00, 0x01, 3, 0, 1, 0, 0, 0, 100, 0, 0, 0]); // ImageWidth = 100
+        data.extend([0x4A, 0x01, 13, 0, 1, 0, 0, 0, 38, 0, 0, 0]); // SubIFDs = [38]
+        data.extend([0, 0, 0, 0]);
+        data.extend([2, 0]);
+        data.extend([0x00, 0x01, 3, 0, 1, 0, 0, 0, 0xA0, 0x0F, 0, 0]); // ImageWidth = 4000
+        data.extend([0x1D, 0xC6, 3, 0, 1, 0, 0, 0, 0xFF, 0x0F, 0, 0]); // WhiteLevel = 4095
+        data.extend([0, 0, 0, 0]);
+
+        let tiff = read_tiff_file(&data).unwrap();
+        assert_eq!(tiff.pages().len(), 2);
+        assert_eq!(get_tag_value!(tiff.tags, TiffTag::ImageWidth), Some(&100));
+
+        let page = &tiff.pages()[1];
+        assert_eq!(get_tag_value!(page, TiffTag::ImageWidth), Some(&4000));
+        assert_eq!(get_tag_value!(page, TiffTag::WhiteLevel), Some(&vec![4095]));
+
+        // SubIFDs which point outside of the file are skipped
+        data[30..34].copy_from_slice(&9999_u32.to_le_bytes());
+        let tiff = read_tiff_file(&data).unwrap();
+        assert_eq!(tiff.pages().len(), 1);
     }
 }
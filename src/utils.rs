@@ -6,7 +6,7 @@ use std::{
 use crate::heif;
 use crate::tiff;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Copy, Clone)]
 pub enum Endianness {
     Little,
     Big,